@@ -0,0 +1,384 @@
+use anyhow::{anyhow, Result};
+use openssl::asn1::Asn1Time;
+use openssl::bn::{BigNum, MsbOption};
+use openssl::hash::MessageDigest;
+use openssl::nid::Nid;
+use openssl::pkey::PKey;
+use openssl::rsa::Rsa;
+use openssl::x509::extension::{
+    ExtendedKeyUsage, KeyUsage, SubjectAlternativeName, SubjectKeyIdentifier,
+};
+use openssl::x509::{X509NameBuilder, X509NameRef, X509};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+/// Directory of the client's certificate store
+pub const PKI_DIR: &str = "pki";
+/// Generated client certificate inside `PKI_DIR`. Named apart from `own/cert.der`, where the
+/// opcua client writes its sample keypair, so the two are never mistaken for each other.
+pub const OWN_CERT_FILE: &str = "own/opcua-tui-client.der";
+/// Private key of the generated client certificate inside `PKI_DIR`
+pub const OWN_KEY_FILE: &str = "private/opcua-tui-client.pem";
+
+/// Sub-directory of the certificate store a certificate was found in
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CertificateLocation {
+    Own,
+    Trusted,
+    Rejected,
+}
+
+impl CertificateLocation {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CertificateLocation::Own => "Own",
+            CertificateLocation::Trusted => "Trusted",
+            CertificateLocation::Rejected => "Rejected",
+        }
+    }
+
+    pub fn dir_name(&self) -> &'static str {
+        match self {
+            CertificateLocation::Own => "own",
+            CertificateLocation::Trusted => "trusted",
+            CertificateLocation::Rejected => "rejected",
+        }
+    }
+}
+
+/// A certificate file with the fields shown on the certificate screen
+#[derive(Clone, Debug)]
+pub struct CertificateInfo {
+    pub location: CertificateLocation,
+    pub path: PathBuf,
+    pub subject: String,
+    pub issuer: String,
+    pub not_before: String,
+    pub not_after: String,
+    pub expired: bool,
+    pub thumbprint: String, // SHA-1 of the DER encoding, as used by OPC UA
+    pub application_uri: Option<String>,
+    pub error: Option<String>, // Set when the file could not be parsed
+}
+
+/// Contents of a generated self-signed application instance certificate
+#[derive(Clone, Debug)]
+pub struct CertificateSettings {
+    pub application_name: String,
+    pub application_uri: String,
+    pub host_names: Vec<String>, // DNS names or IP addresses
+    pub validity_days: u32,
+    pub key_size: u32,
+}
+
+impl CertificateSettings {
+    /// Certificate for this client on the local host, valid for a year
+    pub fn for_local_host() -> Self {
+        let host_name = CertUtils::local_host_name();
+        Self {
+            application_name: "OPC UA TUI Client".to_string(),
+            application_uri: format!("urn:{host_name}:opcua-tui-client"),
+            host_names: vec![host_name, "localhost".to_string()],
+            validity_days: 365,
+            key_size: 2048,
+        }
+    }
+}
+
+/// Utility functions for the certificates in the `pki` directory
+pub struct CertUtils;
+
+impl CertUtils {
+    /// Parse a PEM or DER certificate file
+    pub fn load_x509(path: &Path) -> Result<X509> {
+        let cert_data =
+            std::fs::read(path).map_err(|e| anyhow!("Cannot read certificate file: {}", e))?;
+
+        if cert_data.is_empty() {
+            return Err(anyhow!("Certificate file is empty"));
+        }
+
+        if cert_data.starts_with(b"-----BEGIN CERTIFICATE-----") {
+            X509::from_pem(&cert_data)
+                .map_err(|e| anyhow!("Failed to parse PEM certificate: {}", e))
+        } else {
+            X509::from_der(&cert_data)
+                .map_err(|e| anyhow!("Failed to parse DER certificate: {}", e))
+        }
+    }
+
+    /// The application URI from the Subject Alternative Name, or a Common Name that is a URN
+    pub fn application_uri(cert: &X509) -> Option<String> {
+        // OPC UA application URIs typically start with "urn:"
+        let from_san = cert.subject_alt_names().and_then(|san_list| {
+            san_list
+                .iter()
+                .filter_map(|san| san.uri().map(str::to_string))
+                .find(|uri| uri.starts_with("urn:"))
+        });
+        if from_san.is_some() {
+            return from_san;
+        }
+
+        cert.subject_name()
+            .entries_by_nid(Nid::COMMONNAME)
+            .filter_map(|entry| entry.data().as_utf8().ok())
+            .map(|cn| cn.to_string())
+            .find(|cn| cn.starts_with("urn:"))
+    }
+
+    /// List the own, trusted and rejected certificates, each sorted by file name
+    pub fn list_certificates(pki_dir: &Path) -> Vec<CertificateInfo> {
+        let mut certificates = Vec::new();
+        for location in [
+            CertificateLocation::Own,
+            CertificateLocation::Trusted,
+            CertificateLocation::Rejected,
+        ] {
+            let Ok(entries) = std::fs::read_dir(pki_dir.join(location.dir_name())) else {
+                continue;
+            };
+            let mut paths: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| {
+                    path.extension()
+                        .and_then(|extension| extension.to_str())
+                        .is_some_and(|extension| {
+                            ["der", "pem", "crt"].contains(&extension.to_lowercase().as_str())
+                        })
+                })
+                .collect();
+            paths.sort();
+            certificates.extend(
+                paths
+                    .into_iter()
+                    .map(|path| Self::certificate_info(location, path)),
+            );
+        }
+        certificates
+    }
+
+    /// Details of a certificate file, with `error` set when it cannot be parsed
+    pub fn certificate_info(location: CertificateLocation, path: PathBuf) -> CertificateInfo {
+        let cert = match Self::load_x509(&path) {
+            Ok(cert) => cert,
+            Err(e) => {
+                return CertificateInfo {
+                    location,
+                    path,
+                    subject: String::new(),
+                    issuer: String::new(),
+                    not_before: String::new(),
+                    not_after: String::new(),
+                    expired: false,
+                    thumbprint: String::new(),
+                    application_uri: None,
+                    error: Some(e.to_string()),
+                }
+            }
+        };
+
+        let expired = openssl::asn1::Asn1Time::days_from_now(0)
+            .ok()
+            .and_then(|now| cert.not_after().compare(&now).ok())
+            .is_some_and(|ordering| ordering.is_lt());
+
+        CertificateInfo {
+            location,
+            subject: Self::format_name(cert.subject_name()),
+            issuer: Self::format_name(cert.issuer_name()),
+            not_before: cert.not_before().to_string(),
+            not_after: cert.not_after().to_string(),
+            expired,
+            thumbprint: Self::thumbprint(&cert),
+            application_uri: Self::application_uri(&cert),
+            error: None,
+            path,
+        }
+    }
+
+    /// SHA-1 of the DER encoding as uppercase hex, as used by OPC UA
+    pub fn thumbprint(cert: &X509) -> String {
+        cert.digest(MessageDigest::sha1())
+            .map(|digest| digest.iter().map(|byte| format!("{byte:02X}")).collect())
+            .unwrap_or_default()
+    }
+
+    /// File name the opcua certificate store uses for a DER certificate in `trusted` and
+    /// `rejected`, e.g. "OpcPlc [thumbprint].der"
+    pub fn store_file_name(der: &[u8]) -> Result<String> {
+        let cert = opcua::crypto::X509::from_der(der)
+            .map_err(|e| anyhow!("Failed to parse DER certificate: {:?}", e))?;
+        Ok(opcua::crypto::CertificateStore::cert_file_name(&cert))
+    }
+
+    /// Format a name as "CN=..., O=..."
+    fn format_name(name: &X509NameRef) -> String {
+        name.entries()
+            .map(|entry| {
+                let key = entry.object().nid().short_name().unwrap_or("?");
+                let value = entry
+                    .data()
+                    .as_utf8()
+                    .map(|value| value.to_string())
+                    .unwrap_or_default();
+                format!("{key}={value}")
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Move a certificate to the trusted or rejected directory and return its new path. Fails
+    /// if that directory already holds a file of the same name.
+    pub fn move_certificate(
+        pki_dir: &Path,
+        certificate: &CertificateInfo,
+        location: CertificateLocation,
+    ) -> Result<PathBuf> {
+        let file_name = certificate
+            .path
+            .file_name()
+            .ok_or_else(|| anyhow!("{} is not a file", certificate.path.display()))?;
+        let dir = pki_dir.join(location.dir_name());
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| anyhow!("Cannot create {}: {}", dir.display(), e))?;
+        let target = dir.join(file_name);
+        if target == certificate.path {
+            return Ok(target);
+        }
+        // rename would silently replace a certificate of the same name
+        if target.exists() {
+            return Err(anyhow!(
+                "Cannot move {}: {} already exists",
+                certificate.path.display(),
+                target.display()
+            ));
+        }
+        std::fs::rename(&certificate.path, &target)
+            .map_err(|e| anyhow!("Cannot move {}: {}", certificate.path.display(), e))?;
+        Ok(target)
+    }
+
+    /// Name of this machine from the environment or `/etc/hostname`, else "localhost"
+    pub fn local_host_name() -> String {
+        ["HOSTNAME", "COMPUTERNAME"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .chain(std::fs::read_to_string("/etc/hostname").ok())
+            .map(|name| name.trim().to_string())
+            .find(|name| !name.is_empty())
+            .unwrap_or_else(|| "localhost".to_string())
+    }
+
+    /// Create an RSA key pair and a self-signed application instance certificate with the
+    /// application URI and host names as SubjectAltName. Writes `OWN_CERT_FILE` (DER) and
+    /// `OWN_KEY_FILE` (PEM) below `pki_dir` and returns both paths; existing files are only
+    /// replaced with `overwrite`.
+    pub fn generate_self_signed(
+        pki_dir: &Path,
+        settings: &CertificateSettings,
+        overwrite: bool,
+    ) -> Result<(PathBuf, PathBuf)> {
+        let cert_path = pki_dir.join(OWN_CERT_FILE);
+        let key_path = pki_dir.join(OWN_KEY_FILE);
+        if !overwrite {
+            if let Some(existing) = [&cert_path, &key_path].into_iter().find(|p| p.exists()) {
+                return Err(anyhow!("{} already exists", existing.display()));
+            }
+        }
+
+        let pkey = PKey::from_rsa(Rsa::generate(settings.key_size)?)?;
+
+        let mut name = X509NameBuilder::new()?;
+        name.append_entry_by_nid(Nid::COMMONNAME, &settings.application_name)?;
+        name.append_entry_by_nid(Nid::DOMAINCOMPONENT, &Self::local_host_name())?;
+        let name = name.build();
+
+        let mut builder = X509::builder()?;
+        builder.set_version(2)?; // X.509 v3
+        let mut serial = BigNum::new()?;
+        serial.rand(128, MsbOption::MAYBE_ZERO, false)?;
+        let serial = serial.to_asn1_integer()?;
+        builder.set_serial_number(&serial)?;
+        // Self-signed, so subject and issuer are the same
+        builder.set_subject_name(&name)?;
+        builder.set_issuer_name(&name)?;
+        builder.set_pubkey(&pkey)?;
+        let not_before = Asn1Time::days_from_now(0)?;
+        let not_after = Asn1Time::days_from_now(settings.validity_days)?;
+        builder.set_not_before(&not_before)?;
+        builder.set_not_after(&not_after)?;
+
+        // Key usage required by OPC UA Part 6 for application instance certificates
+        builder.append_extension(
+            KeyUsage::new()
+                .critical()
+                .digital_signature()
+                .non_repudiation()
+                .key_encipherment()
+                .data_encipherment()
+                .key_cert_sign()
+                .build()?,
+        )?;
+        builder.append_extension(
+            ExtendedKeyUsage::new()
+                .client_auth()
+                .server_auth()
+                .build()?,
+        )?;
+        let subject_key_identifier =
+            SubjectKeyIdentifier::new().build(&builder.x509v3_context(None, None))?;
+        builder.append_extension(subject_key_identifier)?;
+
+        // The application URI must be the URI entry of the SubjectAltName
+        let mut alt_names = SubjectAlternativeName::new();
+        alt_names.uri(&settings.application_uri);
+        for host_name in &settings.host_names {
+            if host_name.parse::<IpAddr>().is_ok() {
+                alt_names.ip(host_name);
+            } else {
+                alt_names.dns(host_name);
+            }
+        }
+        let alt_names = alt_names.build(&builder.x509v3_context(None, None))?;
+        builder.append_extension(alt_names)?;
+
+        builder.sign(&pkey, MessageDigest::sha256())?;
+        let cert = builder.build();
+
+        for path in [&cert_path, &key_path] {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)
+                    .map_err(|e| anyhow!("Cannot create {}: {}", dir.display(), e))?;
+            }
+        }
+        std::fs::write(&cert_path, cert.to_der()?)
+            .map_err(|e| anyhow!("Cannot write {}: {}", cert_path.display(), e))?;
+        Self::write_private_key(&key_path, &pkey.private_key_to_pem_pkcs8()?)
+            .map_err(|e| anyhow!("Cannot write {}: {}", key_path.display(), e))?;
+
+        Ok((cert_path, key_path))
+    }
+
+    /// Write a key file that only the owner can read from the moment it is created
+    fn write_private_key(path: &Path, pem: &[u8]) -> std::io::Result<()> {
+        use std::io::Write;
+
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options.open(path)?.write_all(pem)
+    }
+
+    pub fn delete_certificate(certificate: &CertificateInfo) -> Result<()> {
+        std::fs::remove_file(&certificate.path)
+            .map_err(|e| anyhow!("Cannot delete {}: {}", certificate.path.display(), e))
+    }
+}
@@ -0,0 +1,839 @@
+//! Non-interactive subcommands for scripting and smoke tests. Results go to stdout as a table
+//! or JSON, logs to stderr.
+
+use crate::cert_utils::{CertUtils, CertificateSettings, PKI_DIR};
+use crate::client::{MonitoringSettings, OpcUaClientManager, OpcUaDataChange, OpcUaNode};
+use crate::connection_manager::{ConnectionConfig, ConnectionManager};
+use crate::export_utils::ExportUtils;
+use crate::snapshot_utils::{NodeDiff, Snapshot, SnapshotUtils};
+use crate::value_utils::ValueUtils;
+use crate::Args;
+use anyhow::{anyhow, Result};
+use clap::{Subcommand, ValueEnum};
+use opcua::types::{AttributeId, DateTime, EndpointDescription, NodeId, StatusCode, Variant};
+use serde_json::{json, Value};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, RwLock};
+
+/// Exit code when the command ran but the server reported a bad StatusCode
+pub const EXIT_BAD_STATUS: i32 = 2;
+/// Exit code when the command could not run (invalid input, connection or service failure)
+pub const EXIT_FAILURE: i32 = 1;
+/// Exit code of `diff` when the snapshots differ
+pub const EXIT_DIFFERENCES: i32 = 3;
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Browse the hierarchy below a node
+    Browse {
+        /// NodeId to start from, e.g. "i=85" or "ns=2;s=Demo"
+        node: String,
+        /// Number of levels to browse
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        depth: u32,
+    },
+    /// Read an attribute of a node
+    Read {
+        /// NodeId of the node to read
+        node: String,
+        /// Attribute name (e.g. Value, DisplayName, DataType) or numeric AttributeId
+        #[arg(default_value = "Value")]
+        attribute: String,
+    },
+    /// Write the Value of a node, encoded by its DataType and ValueRank
+    Write {
+        /// NodeId of the Variable to write
+        node: String,
+        /// New value; arrays as comma separated lists, e.g. "[1, 2, 3]"; quote elements that contain commas
+        value: String,
+    },
+    /// Call a Method on an Object
+    Call {
+        /// NodeId of the Object the Method is called on
+        object: String,
+        /// NodeId of the Method
+        method: String,
+        /// Input arguments in the order of the Method's InputArguments
+        args: Vec<String>,
+    },
+    /// Monitor the Value of nodes and print one JSON object per data change until stopped
+    Subscribe {
+        /// NodeId to monitor; repeat for several nodes
+        #[arg(long = "node", required = true)]
+        nodes: Vec<String>,
+        /// Publishing and sampling interval in milliseconds
+        #[arg(long, default_value_t = 500)]
+        interval: u32,
+        /// Append the JSON lines to this file instead of stdout
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+    /// Save a subtree with the attributes of every node to a snapshot file
+    Snapshot {
+        /// NodeId of the subtree root
+        node: String,
+        /// Number of levels below the node
+        #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+        depth: u32,
+        /// Also save the Value of every Variable
+        #[arg(long)]
+        values: bool,
+        /// Snapshot file to write
+        #[arg(long)]
+        file: PathBuf,
+    },
+    /// Compare two snapshots, or a snapshot against the live server when only one is given
+    Diff {
+        /// Older snapshot file
+        old: PathBuf,
+        /// Newer snapshot file; the server is captured with the old snapshot's root and depth
+        /// when omitted
+        new: Option<PathBuf>,
+    },
+    /// List the endpoints offered by a server
+    Endpoints {
+        /// Discovery URL of the server, e.g. "opc.tcp://localhost:4840"
+        url: String,
+    },
+    /// Create a self-signed client certificate and private key in the pki directory
+    GenCert {
+        /// Application URI written to the SubjectAltName, "urn:<host>:opcua-tui-client" by default
+        #[arg(long)]
+        application_uri: Option<String>,
+        /// Application name used as the certificate's common name
+        #[arg(long)]
+        name: Option<String>,
+        /// DNS name or IP address for the SubjectAltName; repeat for several, defaults to this
+        /// host and localhost
+        #[arg(long = "host")]
+        hosts: Vec<String>,
+        /// Validity in days
+        #[arg(long, default_value_t = 365, value_parser = clap::value_parser!(u32).range(1..))]
+        days: u32,
+        /// RSA key size in bits
+        #[arg(long, default_value_t = 2048, value_parser = clap::value_parser!(u32).range(1024..=8192))]
+        key_size: u32,
+        /// Replace an existing client certificate and key
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    Table,
+    Json,
+}
+
+/// Run a subcommand and return the process exit code
+pub async fn run(command: &Command, args: &Args) -> i32 {
+    let mut connection = None;
+    let result = run_command(command, args, &mut connection).await;
+    if let Some(client_manager) = connection {
+        client_manager.write().await.disconnect();
+    }
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            log::error!("{e}");
+            EXIT_FAILURE
+        }
+    }
+}
+
+async fn run_command(
+    command: &Command,
+    args: &Args,
+    connection: &mut Option<Arc<RwLock<OpcUaClientManager>>>,
+) -> Result<i32> {
+    match command {
+        Command::Endpoints { url } => endpoints(url, args.output).await,
+        Command::GenCert {
+            application_uri,
+            name,
+            hosts,
+            days,
+            key_size,
+            force,
+        } => {
+            let mut settings = CertificateSettings::for_local_host();
+            if let Some(application_uri) = application_uri {
+                settings.application_uri = application_uri.clone();
+            }
+            if let Some(name) = name {
+                settings.application_name = name.clone();
+            }
+            if !hosts.is_empty() {
+                settings.host_names = hosts.clone();
+            }
+            settings.validity_days = *days;
+            settings.key_size = *key_size;
+            gen_cert(&settings, *force, args.output)
+        }
+        Command::Diff {
+            old,
+            new: Some(new),
+        } => {
+            let old_snapshot = SnapshotUtils::load(old)?;
+            let new_snapshot = SnapshotUtils::load(new)?;
+            Ok(diff(&old_snapshot, &new_snapshot, args.output))
+        }
+        Command::Diff { old, new: None } => {
+            let client_manager = connect(args, connection).await?;
+            diff_live(client_manager, old, args.output).await
+        }
+        Command::Subscribe {
+            nodes,
+            interval,
+            file,
+        } => {
+            let client_manager = connect(args, connection).await?;
+            subscribe(client_manager, nodes, *interval, file.as_deref()).await
+        }
+        Command::Snapshot {
+            node,
+            depth,
+            values,
+            file,
+        } => {
+            let client_manager = connect(args, connection).await?;
+            snapshot(client_manager, node, *depth, *values, file).await
+        }
+        Command::Browse { node, depth } => {
+            let client = connect(args, connection).await?.read().await;
+            browse(&client, node, *depth, args.output).await
+        }
+        Command::Read { node, attribute } => {
+            let client = connect(args, connection).await?.read().await;
+            read(&client, node, attribute, args.output).await
+        }
+        Command::Write { node, value } => {
+            let client = connect(args, connection).await?.read().await;
+            write(&client, node, value, args.output).await
+        }
+        Command::Call {
+            object,
+            method,
+            args: inputs,
+        } => {
+            let client = connect(args, connection).await?.read().await;
+            call(&client, object, method, inputs, args.output).await
+        }
+    }
+}
+
+/// Connect to the server given on the command line; `run` disconnects when the command is done
+async fn connect<'a>(
+    args: &Args,
+    connection: &'a mut Option<Arc<RwLock<OpcUaClientManager>>>,
+) -> Result<&'a Arc<RwLock<OpcUaClientManager>>> {
+    let server_url = args
+        .server_url
+        .clone()
+        .ok_or_else(|| anyhow!("--server-url or --profile is required"))?;
+
+    let client_manager = connection.insert(Arc::new(RwLock::new(OpcUaClientManager::new())));
+    crate::connect_via_command_line(args, &server_url, client_manager.clone()).await?;
+    Ok(client_manager)
+}
+
+fn parse_node_id(text: &str) -> Result<NodeId> {
+    NodeId::from_str(text).map_err(|_| anyhow!("'{}' is not a NodeId", text))
+}
+
+/// Accept attribute names in any case, or the numeric AttributeId
+fn parse_attribute(text: &str) -> Result<AttributeId> {
+    if let Ok(id) = text.parse::<u32>() {
+        return AttributeId::from_u32(id).map_err(|_| anyhow!("Unknown AttributeId {}", id));
+    }
+    (1..=27)
+        .filter_map(|id| AttributeId::from_u32(id).ok())
+        .find(|attribute| format!("{attribute:?}").eq_ignore_ascii_case(text))
+        .ok_or_else(|| anyhow!("Unknown attribute '{}'", text))
+}
+
+fn exit_code(status: StatusCode) -> i32 {
+    if status.is_bad() {
+        EXIT_BAD_STATUS
+    } else {
+        0
+    }
+}
+
+/// The bad StatusCode a failed service call reported, if the error carries one
+fn bad_status(error: &anyhow::Error) -> Option<StatusCode> {
+    error
+        .downcast_ref::<StatusCode>()
+        .copied()
+        .filter(|status| status.is_bad())
+}
+
+async fn browse(
+    client: &OpcUaClientManager,
+    node: &str,
+    depth: u32,
+    output: OutputFormat,
+) -> Result<i32> {
+    let node_id = parse_node_id(node)?;
+    let rows = match browse_rows(client, &node_id, depth).await {
+        Ok(rows) => rows,
+        Err(e) if bad_status(&e).is_some() => {
+            log::error!("{e}");
+            return Ok(EXIT_BAD_STATUS);
+        }
+        Err(e) => return Err(e),
+    };
+
+    match output {
+        OutputFormat::Table => print_table(
+            &["BrowseName", "NodeClass", "NodeId", "DisplayName"],
+            rows.iter()
+                .map(|(level, node)| {
+                    vec![
+                        format!("{}{}", "  ".repeat(*level), node.browse_name),
+                        format!("{:?}", node.node_class),
+                        node.node_id.to_string(),
+                        node.display_name.clone(),
+                    ]
+                })
+                .collect(),
+        ),
+        OutputFormat::Json => {
+            let nodes = rows.iter().map(|(level, node)| {
+                let node = json!({
+                    "node_id": node.node_id.to_string(),
+                    "browse_name": node.browse_name,
+                    "display_name": node.display_name,
+                    "node_class": format!("{:?}", node.node_class),
+                });
+                (*level, node)
+            });
+            print_json(&Value::Array(ExportUtils::nest_json(nodes)));
+        }
+    }
+    Ok(0)
+}
+
+/// Browse `depth` levels below a node depth-first, so each node is followed by its descendants
+async fn browse_rows(
+    client: &OpcUaClientManager,
+    node_id: &NodeId,
+    depth: u32,
+) -> Result<Vec<(usize, OpcUaNode)>> {
+    let mut rows: Vec<(usize, OpcUaNode)> = Vec::new();
+    let mut pending: Vec<(usize, OpcUaNode)> = client
+        .browse_node(node_id)
+        .await?
+        .into_iter()
+        .rev()
+        .map(|child| (0, child))
+        .collect();
+    while let Some((level, node)) = pending.pop() {
+        if level + 1 < depth as usize {
+            let children = client.browse_node(&node.node_id).await?;
+            pending.extend(children.into_iter().rev().map(|child| (level + 1, child)));
+        }
+        rows.push((level, node));
+    }
+    Ok(rows)
+}
+
+async fn read(
+    client: &OpcUaClientManager,
+    node: &str,
+    attribute: &str,
+    output: OutputFormat,
+) -> Result<i32> {
+    let node_id = parse_node_id(node)?;
+    let attribute_id = parse_attribute(attribute)?;
+    let data_value = client.read_attribute(&node_id, attribute_id).await?;
+    let status = data_value.status();
+
+    let value = data_value.value.clone().unwrap_or(Variant::Empty);
+    let source_timestamp = format_timestamp(data_value.source_timestamp.as_ref());
+    let server_timestamp = format_timestamp(data_value.server_timestamp.as_ref());
+
+    match output {
+        OutputFormat::Table => print_table(
+            &["Field", "Value"],
+            vec![
+                vec!["Node".to_string(), node_id.to_string()],
+                vec!["Attribute".to_string(), format!("{attribute_id:?}")],
+                vec!["Value".to_string(), ValueUtils::format_for_edit(&value)],
+                vec!["Type".to_string(), variant_type_name(&value)],
+                vec!["Status".to_string(), status.to_string()],
+                vec![
+                    "SourceTimestamp".to_string(),
+                    source_timestamp.clone().unwrap_or_default(),
+                ],
+                vec![
+                    "ServerTimestamp".to_string(),
+                    server_timestamp.clone().unwrap_or_default(),
+                ],
+            ],
+        ),
+        OutputFormat::Json => print_json(&json!({
+            "node_id": node_id.to_string(),
+            "attribute": format!("{attribute_id:?}"),
+            "value": ValueUtils::to_json(&value),
+            "type": variant_type_name(&value),
+            "status": status.to_string(),
+            "source_timestamp": source_timestamp,
+            "server_timestamp": server_timestamp,
+        })),
+    }
+    Ok(exit_code(status))
+}
+
+async fn write(
+    client: &OpcUaClientManager,
+    node: &str,
+    text: &str,
+    output: OutputFormat,
+) -> Result<i32> {
+    let node_id = parse_node_id(node)?;
+    let info = client.read_value_info(&node_id).await?;
+    let value_type = ValueUtils::resolve_scalar_type(&info.data_type, info.current_value.as_ref())
+        .ok_or_else(|| {
+            anyhow!(
+                "Unsupported DataType {}",
+                OpcUaClientManager::format_data_type(&info.data_type)
+            )
+        })?;
+    let value = ValueUtils::parse_variant(text, value_type, info.value_rank)
+        .map_err(|e| anyhow!("Invalid value: {}", e))?;
+    let written = ValueUtils::format_for_edit(&value);
+    let status = client.write_node_value(&node_id, value).await?;
+
+    match output {
+        OutputFormat::Table => print_table(
+            &["Node", "Value", "Status"],
+            vec![vec![node_id.to_string(), written, status.to_string()]],
+        ),
+        OutputFormat::Json => print_json(&json!({
+            "node_id": node_id.to_string(),
+            "value": written,
+            "status": status.to_string(),
+        })),
+    }
+    Ok(exit_code(status))
+}
+
+async fn call(
+    client: &OpcUaClientManager,
+    object: &str,
+    method: &str,
+    inputs: &[String],
+    output: OutputFormat,
+) -> Result<i32> {
+    let object_id = parse_node_id(object)?;
+    let method_id = parse_node_id(method)?;
+    let arguments = client.read_method_arguments(&method_id).await?;
+    if inputs.len() != arguments.input_arguments.len() {
+        return Err(anyhow!(
+            "Method {} takes {} input argument(s), {} given",
+            method_id,
+            arguments.input_arguments.len(),
+            inputs.len()
+        ));
+    }
+
+    let inputs: Vec<&str> = inputs.iter().map(String::as_str).collect();
+    let input_values = client
+        .parse_method_inputs(&arguments.input_arguments, &inputs)
+        .await?;
+
+    let result = client
+        .call_method(&object_id, &method_id, input_values)
+        .await?;
+
+    let outputs: Vec<(String, Variant)> = result
+        .output_arguments
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let name = arguments
+                .output_arguments
+                .get(i)
+                .map(|argument| argument.name.as_ref().to_string())
+                .unwrap_or_else(|| format!("Output {i}"));
+            (name, value)
+        })
+        .collect();
+    let input_results: Vec<(String, StatusCode)> = result
+        .input_argument_results
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .zip(&arguments.input_arguments)
+        .map(|(status, argument)| (argument.name.as_ref().to_string(), status))
+        .collect();
+
+    match output {
+        OutputFormat::Table => {
+            let mut rows = vec![vec!["Status".to_string(), result.status_code.to_string()]];
+            rows.extend(
+                outputs
+                    .iter()
+                    .map(|(name, value)| vec![name.clone(), ValueUtils::format_for_edit(value)]),
+            );
+            // Per-argument results only matter when the server rejected an input
+            if input_results.iter().any(|(_, status)| !status.is_good()) {
+                rows.extend(
+                    input_results
+                        .iter()
+                        .map(|(name, status)| vec![format!("{name} (input)"), status.to_string()]),
+                );
+            }
+            print_table(&["Name", "Value"], rows);
+        }
+        OutputFormat::Json => print_json(&json!({
+            "object_id": object_id.to_string(),
+            "method_id": method_id.to_string(),
+            "status": result.status_code.to_string(),
+            "outputs": outputs
+                .iter()
+                .map(|(name, value)| json!({ "name": name, "value": ValueUtils::to_json(value) }))
+                .collect::<Vec<_>>(),
+            "input_argument_results": input_results
+                .iter()
+                .map(|(name, status)| json!({ "name": name, "status": status.to_string() }))
+                .collect::<Vec<_>>(),
+        })),
+    }
+    Ok(exit_code(result.status_code))
+}
+
+async fn subscribe(
+    client_manager: &Arc<RwLock<OpcUaClientManager>>,
+    nodes: &[String],
+    interval: u32,
+    file: Option<&Path>,
+) -> Result<i32> {
+    let node_ids = nodes
+        .iter()
+        .map(|node| parse_node_id(node))
+        .collect::<Result<Vec<NodeId>>>()?;
+    let mut out: Box<dyn Write> = match file {
+        Some(path) => Box::new(
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| anyhow!("Cannot open {}: {}", path.display(), e))?,
+        ),
+        None => Box::new(std::io::stdout()),
+    };
+
+    let mut changes = start_subscription(client_manager, &node_ids, interval).await?;
+    log::info!("Monitoring {} node(s), stop with Ctrl+C", node_ids.len());
+
+    let mut session_check = tokio::time::interval(Duration::from_secs(1));
+    // One signal listener for the whole loop, so a Ctrl+C between iterations is not lost
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    loop {
+        tokio::select! {
+            _ = &mut ctrl_c => break,
+            Some(change) = changes.recv() => {
+                // Client handles are the 1-based index into the node list
+                let Some(node_id) = (change.client_handle as usize)
+                    .checked_sub(1)
+                    .and_then(|index| node_ids.get(index))
+                else {
+                    continue;
+                };
+                let value = change.value.value.clone().unwrap_or(Variant::Empty);
+                let line = json!({
+                    "node": node_id.to_string(),
+                    "value": ValueUtils::to_json(&value),
+                    "status": change.value.status().to_string(),
+                    "source_timestamp": format_timestamp(change.value.source_timestamp.as_ref()),
+                    "server_timestamp": format_timestamp(change.value.server_timestamp.as_ref()),
+                });
+                writeln!(out, "{line}")
+                    .and_then(|_| out.flush())
+                    .map_err(|e| anyhow!("Failed to write output: {}", e))?;
+            }
+            _ = session_check.tick() => {
+                // A recovered session has lost the subscription, so it is created again
+                if client_manager.write().await.process_session_events() {
+                    changes = start_subscription(client_manager, &node_ids, interval).await?;
+                }
+            }
+        }
+    }
+    Ok(0)
+}
+
+/// Create a subscription with one monitored item per node and return its data changes
+async fn start_subscription(
+    client_manager: &Arc<RwLock<OpcUaClientManager>>,
+    node_ids: &[NodeId],
+    interval: u32,
+) -> Result<mpsc::UnboundedReceiver<OpcUaDataChange>> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let mut client = client_manager.write().await;
+    client
+        .create_watch_subscription(interval as f64, tx)
+        .await?;
+
+    let settings = MonitoringSettings {
+        sampling_interval: interval as f64,
+        // Keep the samples of a whole publishing interval instead of only the last one
+        queue_size: 10,
+        ..MonitoringSettings::default()
+    };
+    for (index, node_id) in node_ids.iter().enumerate() {
+        client
+            .add_monitored_item(node_id, index as u32 + 1, &settings)
+            .await
+            .map_err(|e| anyhow!("Cannot monitor {}: {}", node_id, e))?;
+    }
+    Ok(rx)
+}
+
+async fn snapshot(
+    client_manager: &Arc<RwLock<OpcUaClientManager>>,
+    node: &str,
+    depth: u32,
+    include_values: bool,
+    file: &Path,
+) -> Result<i32> {
+    let node_id = parse_node_id(node)?;
+    let snapshot = capture(client_manager, &node_id, depth as usize, include_values).await?;
+    SnapshotUtils::save(file, &snapshot)?;
+    log::info!(
+        "Saved {} nodes below {} to {}",
+        snapshot.nodes.len(),
+        node_id,
+        file.display()
+    );
+    Ok(0)
+}
+
+async fn diff_live(
+    client_manager: &Arc<RwLock<OpcUaClientManager>>,
+    old: &Path,
+    output: OutputFormat,
+) -> Result<i32> {
+    let old_snapshot = SnapshotUtils::load(old)?;
+    let root_id = parse_node_id(&old_snapshot.root)?;
+    let live = capture(
+        client_manager,
+        &root_id,
+        old_snapshot.depth,
+        old_snapshot.include_values,
+    )
+    .await?;
+    Ok(diff(&old_snapshot, &live, output))
+}
+
+async fn capture(
+    client_manager: &Arc<RwLock<OpcUaClientManager>>,
+    root_id: &NodeId,
+    depth: usize,
+    include_values: bool,
+) -> Result<Snapshot> {
+    let mut on_progress = |count: usize, path: &str| {
+        log::debug!("snapshot: {count} {path}");
+        true
+    };
+    SnapshotUtils::capture(
+        client_manager,
+        root_id,
+        depth,
+        include_values,
+        &mut on_progress,
+    )
+    .await?
+    .ok_or_else(|| anyhow!("Snapshot cancelled"))
+}
+
+/// Print the differences and return `EXIT_DIFFERENCES` when there are any
+fn diff(old: &Snapshot, new: &Snapshot, output: OutputFormat) -> i32 {
+    let diffs = SnapshotUtils::diff(old, new);
+    match output {
+        OutputFormat::Table => {
+            let mut rows = Vec::new();
+            for node in &diffs {
+                rows.push(vec![
+                    node.kind.marker().to_string(),
+                    node.path.clone(),
+                    node.node_id.clone(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                ]);
+                rows.extend(node.changes.iter().map(|change| {
+                    vec![
+                        String::new(),
+                        String::new(),
+                        String::new(),
+                        change.name.clone(),
+                        change.old.clone().unwrap_or_default(),
+                        change.new.clone().unwrap_or_default(),
+                    ]
+                }));
+            }
+            print_table(&["", "Path", "NodeId", "Attribute", "Old", "New"], rows);
+        }
+        OutputFormat::Json => print_json(&Value::Array(diffs.iter().map(diff_json).collect())),
+    }
+    log::info!("{} node(s) differ", diffs.len());
+    if diffs.is_empty() {
+        0
+    } else {
+        EXIT_DIFFERENCES
+    }
+}
+
+fn diff_json(node: &NodeDiff) -> Value {
+    json!({
+        "change": node.kind.label(),
+        "path": node.path,
+        "node_id": node.node_id,
+        "node_class": node.node_class,
+        "attributes": node
+            .changes
+            .iter()
+            .map(|change| json!({ "name": change.name, "old": change.old, "new": change.new }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn gen_cert(settings: &CertificateSettings, force: bool, output: OutputFormat) -> Result<i32> {
+    let (cert_path, key_path) =
+        CertUtils::generate_self_signed(Path::new(PKI_DIR), settings, force)?;
+
+    match output {
+        OutputFormat::Table => print_table(
+            &["Certificate", "PrivateKey", "ApplicationUri"],
+            vec![vec![
+                cert_path.display().to_string(),
+                key_path.display().to_string(),
+                settings.application_uri.clone(),
+            ]],
+        ),
+        OutputFormat::Json => print_json(&json!({
+            "certificate": cert_path.display().to_string(),
+            "private_key": key_path.display().to_string(),
+            "application_uri": settings.application_uri,
+            "host_names": settings.host_names,
+            "valid_days": settings.validity_days,
+        })),
+    }
+    Ok(0)
+}
+
+async fn endpoints(url: &str, output: OutputFormat) -> Result<i32> {
+    let endpoints =
+        ConnectionManager::discover_endpoints(url, &ConnectionConfig::ui_discovery()).await?;
+
+    let user_tokens = |endpoint: &EndpointDescription| -> Vec<String> {
+        endpoint
+            .user_identity_tokens
+            .iter()
+            .flatten()
+            .map(|token| format!("{:?}", token.token_type))
+            .collect()
+    };
+    let policy = |endpoint: &EndpointDescription| {
+        ConnectionManager::policy_uri_to_name(endpoint.security_policy_uri.as_ref()).to_string()
+    };
+
+    match output {
+        OutputFormat::Table => print_table(
+            &[
+                "EndpointUrl",
+                "SecurityPolicy",
+                "SecurityMode",
+                "Level",
+                "UserTokens",
+            ],
+            endpoints
+                .iter()
+                .map(|endpoint| {
+                    vec![
+                        endpoint.endpoint_url.as_ref().to_string(),
+                        policy(endpoint),
+                        format!("{:?}", endpoint.security_mode),
+                        endpoint.security_level.to_string(),
+                        user_tokens(endpoint).join(", "),
+                    ]
+                })
+                .collect(),
+        ),
+        OutputFormat::Json => print_json(&Value::Array(
+            endpoints
+                .iter()
+                .map(|endpoint| {
+                    json!({
+                        "endpoint_url": endpoint.endpoint_url.as_ref(),
+                        "security_policy": policy(endpoint),
+                        "security_mode": format!("{:?}", endpoint.security_mode),
+                        "security_level": endpoint.security_level,
+                        "user_tokens": user_tokens(endpoint),
+                    })
+                })
+                .collect(),
+        )),
+    }
+    Ok(0)
+}
+
+fn format_timestamp(timestamp: Option<&DateTime>) -> Option<String> {
+    timestamp.map(|timestamp| timestamp.as_chrono().to_rfc3339())
+}
+
+fn variant_type_name(value: &Variant) -> String {
+    match value {
+        Variant::Empty => "Empty".to_string(),
+        Variant::Array(array) => format!("{:?}[]", array.value_type),
+        other => format!("{:?}", other.type_id()),
+    }
+}
+
+fn print_json(value: &Value) {
+    match serde_json::to_string_pretty(value) {
+        Ok(text) => println!("{text}"),
+        Err(e) => log::error!("Failed to format JSON output: {e}"),
+    }
+}
+
+/// Print rows as left-aligned columns under a header
+fn print_table(header: &[&str], rows: Vec<Vec<String>>) {
+    let mut widths: Vec<usize> = header.iter().map(|title| title.chars().count()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    let separator: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    println!("{}", format_row(header.to_vec()));
+    println!(
+        "{}",
+        format_row(separator.iter().map(String::as_str).collect())
+    );
+    for row in &rows {
+        println!("{}", format_row(row.iter().map(String::as_str).collect()));
+    }
+}
@@ -1,21 +1,76 @@
+use crate::connection_manager::{ConnectionManager, ConnectionTarget};
+use crate::data_type_decoder::{DataTypeCache, DataTypeDecoder};
+use crate::reverse_connect::ReverseTarget;
+use crate::value_utils::ValueUtils;
 use anyhow::Result;
 use opcua::client::prelude::*;
-use parking_lot::RwLock;
+use opcua::types::argument::Argument;
+use parking_lot::{Mutex, RwLock};
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+
+// Interval of the keep-alive read that detects a lost connection
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(5);
+// Consecutive failed keep-alive reads after which a connected session counts as lost
+const MAX_FAILED_KEEP_ALIVES: u32 = 3;
+// Delay before the first reconnect attempt, doubled after every failed attempt up to the maximum
+const RECONNECT_DELAY_MIN: Duration = Duration::from_secs(1);
+const RECONNECT_DELAY_MAX: Duration = Duration::from_secs(30);
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConnectionStatus {
     Connected,
     Disconnected,
     Connecting,
+    Reconnecting(u32), // Number of the current reconnect attempt
     Error(String),
 }
 
+/// Outcome of one keep-alive read
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum KeepAlive {
+    Alive,
+    Failed,       // No answer, or the server no longer knows the session
+    Disconnected, // The client stack closed the channel
+}
+
+/// Progress reported by the session monitor, applied by `process_session_events`
+enum SessionEvent {
+    Lost,
+    Reconnecting(u32),
+    Reactivated(Arc<RwLock<Session>>),
+    Recreated(Box<Client>, Arc<RwLock<Session>>),
+}
+
+/// Background task that checks the session with keep-alive reads and reconnects after a loss
+struct SessionMonitor {
+    task: tokio::task::JoinHandle<()>,
+    events: mpsc::UnboundedReceiver<SessionEvent>,
+}
+
+impl Drop for SessionMonitor {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
 pub struct OpcUaClientManager {
     pub connection_status: ConnectionStatus,
     pub client: Option<Client>,
     pub session: Option<Arc<RwLock<Session>>>,
     pub server_url: String,
+    // Keeps the session run loop alive while subscriptions need publish processing
+    session_runner: Option<oneshot::Sender<SessionCommand>>,
+    watch_subscription_id: Option<u32>,
+    event_subscription_id: Option<u32>,
+    // Server's MaxNodesPerRead and MaxNodesPerBrowse operation limits; 0 when not limited
+    max_nodes_per_read: usize,
+    max_nodes_per_browse: usize,
+    session_monitor: Option<SessionMonitor>,
+    // DataTypeDefinitions and supertypes read for decoding, valid for the current session
+    data_type_cache: Mutex<DataTypeCache>,
 }
 
 #[derive(Clone, Debug)]
@@ -27,11 +82,236 @@ pub struct OpcUaNode {
     pub has_children: bool,
 }
 
+/// One page of a node's children and the continuation point for the rest, if any
+#[derive(Clone, Debug, Default)]
+pub struct OpcUaBrowsePage {
+    pub nodes: Vec<OpcUaNode>,
+    pub continuation_point: Option<ByteString>,
+}
+
+/// A forward or inverse reference of a node, with its ReferenceType resolved to a name
+#[derive(Clone, Debug)]
+pub struct OpcUaReference {
+    pub reference_type: String,
+    pub reference_type_id: NodeId,
+    pub is_forward: bool,
+    pub target_id: NodeId,
+    pub browse_name: String,
+    pub node_class: NodeClass,
+}
+
 #[derive(Clone, Debug)]
 pub struct OpcUaAttribute {
     pub name: String,
     pub value: String,
     pub is_value_good: bool, // True if this is a Value attribute with Good status
+    pub depth: usize,        // 0 for attributes, >0 for decoded structure and array fields
+}
+
+/// Attributes of a Variable that decide how a new value has to be encoded before writing
+#[derive(Clone, Debug)]
+pub struct OpcUaValueInfo {
+    pub data_type: NodeId,
+    pub value_rank: i32,
+    pub user_access_level: u8,
+    pub current_value: Option<Variant>,
+}
+
+/// Elements of an array Value, optionally limited to an index range
+#[derive(Clone, Debug)]
+pub struct OpcUaArrayValue {
+    pub value_type: String,
+    pub dimensions: Vec<u32>, // Dimensions of the returned elements, row-major
+    pub elements: Vec<String>,
+    pub status: StatusCode,
+}
+
+/// Attributes of a node that recursive search matches against
+#[derive(Clone, Debug)]
+pub struct OpcUaSearchAttributes {
+    pub browse_name: String,
+    pub display_name: String,
+    pub value: Option<String>, // Only read when searching by value
+    pub node_class: NodeClass,
+}
+
+/// Attributes of a node written by the subtree export; unset when the node has no such attribute
+#[derive(Clone, Debug, Default)]
+pub struct OpcUaExportAttributes {
+    pub browse_name: Option<QualifiedName>,
+    pub description: Option<LocalizedText>,
+    pub data_type: Option<NodeId>,
+    pub value_rank: Option<i32>,
+    pub array_dimensions: Option<Vec<u32>>,
+    pub access_level: Option<u8>,
+    pub user_access_level: Option<u8>,
+    pub minimum_sampling_interval: Option<f64>,
+    pub historizing: Option<bool>,
+    pub event_notifier: Option<u8>,
+    pub executable: Option<bool>,
+    pub is_abstract: Option<bool>,
+    pub symmetric: Option<bool>,
+    pub inverse_name: Option<LocalizedText>,
+    pub value: Option<Variant>,
+}
+
+/// Input and output argument definitions of a Method node
+#[derive(Clone, Debug, Default)]
+pub struct OpcUaMethodArguments {
+    pub input_arguments: Vec<Argument>,
+    pub output_arguments: Vec<Argument>,
+}
+
+/// Folder the browse tree starts from
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BrowseRoot {
+    Root,
+    Objects,
+    Types,
+    Views,
+}
+
+impl BrowseRoot {
+    pub const ALL: [BrowseRoot; 4] = [
+        BrowseRoot::Root,
+        BrowseRoot::Objects,
+        BrowseRoot::Types,
+        BrowseRoot::Views,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BrowseRoot::Root => "Root",
+            BrowseRoot::Objects => "Objects",
+            BrowseRoot::Types => "Types",
+            BrowseRoot::Views => "Views",
+        }
+    }
+
+    pub fn node_id(&self) -> NodeId {
+        match self {
+            BrowseRoot::Root => ObjectId::RootFolder.into(),
+            BrowseRoot::Objects => ObjectId::ObjectsFolder.into(),
+            BrowseRoot::Types => ObjectId::TypesFolder.into(),
+            BrowseRoot::Views => ObjectId::ViewsFolder.into(),
+        }
+    }
+}
+
+/// Which history to read: raw values or an aggregate over fixed processing intervals
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HistoryReadMode {
+    Raw,
+    Average,
+    Minimum,
+    Maximum,
+    Count,
+}
+
+impl HistoryReadMode {
+    pub const ALL: [HistoryReadMode; 5] = [
+        HistoryReadMode::Raw,
+        HistoryReadMode::Average,
+        HistoryReadMode::Minimum,
+        HistoryReadMode::Maximum,
+        HistoryReadMode::Count,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HistoryReadMode::Raw => "Raw",
+            HistoryReadMode::Average => "Average",
+            HistoryReadMode::Minimum => "Min",
+            HistoryReadMode::Maximum => "Max",
+            HistoryReadMode::Count => "Count",
+        }
+    }
+
+    fn aggregate_id(&self) -> Option<NodeId> {
+        match self {
+            HistoryReadMode::Raw => None,
+            HistoryReadMode::Average => Some(ObjectId::AggregateFunction_Average.into()),
+            HistoryReadMode::Minimum => Some(ObjectId::AggregateFunction_Minimum.into()),
+            HistoryReadMode::Maximum => Some(ObjectId::AggregateFunction_Maximum.into()),
+            HistoryReadMode::Count => Some(ObjectId::AggregateFunction_Count.into()),
+        }
+    }
+}
+
+/// Time range and mode of a HistoryRead request
+#[derive(Clone, Debug)]
+pub struct HistoryQuery {
+    pub mode: HistoryReadMode,
+    pub start_time: DateTime,
+    pub end_time: DateTime,
+    pub processing_interval: f64, // Milliseconds, only used for aggregates
+    pub values_per_page: u32,     // Only used for raw reads
+}
+
+/// One page of history values and the continuation point for the next page
+#[derive(Clone, Debug)]
+pub struct OpcUaHistoryPage {
+    pub values: Vec<DataValue>,
+    pub continuation_point: Option<ByteString>,
+}
+
+/// A value change delivered by a monitored item of the watch subscription
+#[derive(Clone, Debug)]
+pub struct OpcUaDataChange {
+    pub client_handle: u32,
+    pub value: DataValue,
+}
+
+/// Per-item monitoring parameters for a watched node
+#[derive(Clone, Debug)]
+pub struct MonitoringSettings {
+    pub sampling_interval: f64,
+    pub queue_size: u32,
+    pub deadband: f64, // Absolute deadband, 0 disables the filter
+}
+
+/// Selected fields of an event delivered by the event subscription
+#[derive(Clone, Debug)]
+pub struct OpcUaEvent {
+    pub client_handle: u32,
+    pub fields: Vec<Variant>, // In select clause order
+}
+
+/// Methods that can be called on a condition from the event monitor
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConditionAction {
+    Acknowledge,
+    Confirm,
+    AddComment,
+}
+
+impl ConditionAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConditionAction::Acknowledge => "Acknowledge",
+            ConditionAction::Confirm => "Confirm",
+            ConditionAction::AddComment => "AddComment",
+        }
+    }
+
+    fn method_id(&self) -> NodeId {
+        match self {
+            ConditionAction::Acknowledge => MethodId::AcknowledgeableConditionType_Acknowledge,
+            ConditionAction::Confirm => MethodId::AcknowledgeableConditionType_Confirm,
+            ConditionAction::AddComment => MethodId::ConditionType_AddComment,
+        }
+        .into()
+    }
+}
+
+impl Default for MonitoringSettings {
+    fn default() -> Self {
+        Self {
+            sampling_interval: 500.0,
+            queue_size: 1,
+            deadband: 0.0,
+        }
+    }
 }
 
 impl OpcUaClientManager {
@@ -41,6 +321,13 @@ impl OpcUaClientManager {
             client: None,
             session: None,
             server_url: String::new(),
+            session_runner: None,
+            watch_subscription_id: None,
+            event_subscription_id: None,
+            max_nodes_per_read: 0,
+            max_nodes_per_browse: 0,
+            session_monitor: None,
+            data_type_cache: Mutex::default(),
         }
     }
 
@@ -51,7 +338,34 @@ impl OpcUaClientManager {
     pub fn set_connection_status(&mut self, status: ConnectionStatus) {
         self.connection_status = status;
     }
+    /// Browse all hierarchical children of a node, following continuation points.
+    /// `has_children` is only guessed from the NodeClass; tree pages use `browse_node_page`.
     pub async fn browse_node(&self, node_id: &NodeId) -> Result<Vec<OpcUaNode>> {
+        let mut page = self.browse_first_page(node_id).await?;
+        let mut nodes = std::mem::take(&mut page.nodes);
+        while let Some(continuation_point) = page.continuation_point.take() {
+            page = self.browse_continuation(continuation_point).await?;
+            nodes.append(&mut page.nodes);
+        }
+        Ok(nodes)
+    }
+
+    /// Browse the first page of a node's hierarchical children. Servers decide the page size;
+    /// the returned continuation point must be passed to `browse_next_page` or released.
+    /// `has_children` is guessed from the NodeClass until checked with `detect_children`.
+    pub async fn browse_node_page(&self, node_id: &NodeId) -> Result<OpcUaBrowsePage> {
+        self.browse_first_page(node_id).await
+    }
+
+    /// Fetch the next page of a browse with BrowseNext
+    pub async fn browse_next_page(
+        &self,
+        continuation_point: ByteString,
+    ) -> Result<OpcUaBrowsePage> {
+        self.browse_continuation(continuation_point).await
+    }
+
+    async fn browse_first_page(&self, node_id: &NodeId) -> Result<OpcUaBrowsePage> {
         if let Some(session) = &self.session {
             // Add timeout to browse operation to prevent hanging
             let browse_future = async {
@@ -70,53 +384,11 @@ impl OpcUaClientManager {
                 session_guard.browse(&[browse_description])
             }; // Apply timeout to the browse operation
             match tokio::time::timeout(tokio::time::Duration::from_secs(5), browse_future).await {
-                Ok(Ok(results)) => {
-                    let mut nodes = Vec::new();
-                    if let Some(results_vec) = results {
-                        if let Some(result) = results_vec.first() {
-                            if let Some(references) = &result.references {
-                                for reference in references {
-                                    let node_id = &reference.node_id.node_id;
-                                    let display_name = reference
-                                        .display_name
-                                        .text
-                                        .value()
-                                        .as_ref()
-                                        .map(|s| s.as_str())
-                                        .unwrap_or("<No Name>");
-                                    let browse_name = reference
-                                        .browse_name
-                                        .name
-                                        .value()
-                                        .as_ref()
-                                        .map(|s| s.as_str())
-                                        .unwrap_or("<No Name>");
-
-                                    // Determine if the node has children by checking if it's an object
-                                    let has_children = matches!(
-                                        reference.node_class,
-                                        NodeClass::Object
-                                            | NodeClass::Variable
-                                            | NodeClass::ObjectType
-                                    );
-
-                                    nodes.push(OpcUaNode {
-                                        node_id: node_id.clone(),
-                                        browse_name: browse_name.to_string(),
-                                        display_name: display_name.to_string(),
-                                        node_class: reference.node_class,
-                                        has_children,
-                                    });
-                                }
-                            }
-                        }
-                    }
-                    Ok(nodes)
-                }
+                Ok(Ok(results)) => Self::browse_page_from_results(results),
                 Ok(Err(e)) => {
                     // Browse operation failed
                     log::warn!("Failed to browse node {node_id}: {e}");
-                    Err(anyhow::anyhow!("Browse operation failed: {}", e))
+                    Err(anyhow::Error::new(e).context(format!("Browse operation failed: {e}")))
                 }
                 Err(_timeout) => {
                     // Browse operation timed out
@@ -129,10 +401,272 @@ impl OpcUaClientManager {
             Err(anyhow::anyhow!("Not connected to OPC UA server"))
         }
     }
+
+    async fn browse_continuation(&self, continuation_point: ByteString) -> Result<OpcUaBrowsePage> {
+        if let Some(session) = &self.session {
+            let browse_future = async {
+                let session_guard = session.read();
+                session_guard.browse_next(false, &[continuation_point])
+            };
+            match tokio::time::timeout(tokio::time::Duration::from_secs(5), browse_future).await {
+                Ok(Ok(results)) => Self::browse_page_from_results(results),
+                Ok(Err(e)) => {
+                    log::warn!("BrowseNext failed: {e}");
+                    Err(anyhow::Error::new(e).context(format!("BrowseNext failed: {e}")))
+                }
+                Err(_timeout) => {
+                    log::warn!("BrowseNext timed out");
+                    Err(anyhow::anyhow!("BrowseNext timed out"))
+                }
+            }
+        } else {
+            Err(anyhow::anyhow!("Not connected to OPC UA server"))
+        }
+    }
+
+    /// Whether each node has hierarchical children, `None` where the server returned a bad
+    /// status. Sends one Browse per MaxNodesPerBrowse nodes that asks for at most one NodeId-only
+    /// reference per node, and releases the continuation points of nodes with more children.
+    pub async fn detect_children(&self, node_ids: &[NodeId]) -> Result<Vec<Option<bool>>> {
+        let Some(session) = &self.session else {
+            return Err(anyhow::anyhow!("Not connected to OPC UA server"));
+        };
+        let session_guard = session.read();
+
+        let batch_size = match self.max_nodes_per_browse {
+            0 => node_ids.len().max(1),
+            limit => limit,
+        };
+        let mut has_children = Vec::with_capacity(node_ids.len());
+        for batch in node_ids.chunks(batch_size) {
+            let request = BrowseRequest {
+                request_header: session_guard.make_request_header(),
+                view: ViewDescription {
+                    view_id: NodeId::null(),
+                    timestamp: DateTime::null(),
+                    view_version: 0,
+                },
+                requested_max_references_per_node: 1,
+                nodes_to_browse: Some(
+                    batch
+                        .iter()
+                        .map(|node_id| BrowseDescription {
+                            node_id: node_id.clone(),
+                            browse_direction: BrowseDirection::Forward,
+                            reference_type_id: ReferenceTypeId::HierarchicalReferences.into(),
+                            include_subtypes: true,
+                            node_class_mask: 0,
+                            result_mask: 0,
+                        })
+                        .collect(),
+                ),
+            };
+            let SupportedMessage::BrowseResponse(response) = session_guard.send_request(request)?
+            else {
+                return Err(anyhow::anyhow!("Unexpected response to Browse"));
+            };
+            if response.response_header.service_result.is_bad() {
+                return Err(anyhow::anyhow!(
+                    "Browse operation failed: {}",
+                    response.response_header.service_result
+                ));
+            }
+
+            let results = response.results.unwrap_or_default();
+            let mut continuation_points = Vec::new();
+            for index in 0..batch.len() {
+                let Some(result) = results.get(index) else {
+                    has_children.push(None);
+                    continue;
+                };
+                if result.status_code.is_bad() {
+                    has_children.push(None);
+                    continue;
+                }
+                let has_continuation = !result.continuation_point.is_null()
+                    && !result.continuation_point.as_ref().is_empty();
+                if has_continuation {
+                    continuation_points.push(result.continuation_point.clone());
+                }
+                has_children.push(Some(
+                    has_continuation
+                        || result
+                            .references
+                            .as_ref()
+                            .is_some_and(|refs| !refs.is_empty()),
+                ));
+            }
+            if !continuation_points.is_empty() {
+                if let Err(e) = session_guard.browse_next(true, &continuation_points) {
+                    log::debug!("Failed to release continuation points: {e}");
+                }
+            }
+        }
+        Ok(has_children)
+    }
+
+    /// Release a browse continuation point that will not be used, freeing it on the server
+    pub async fn release_browse_continuation_point(
+        &self,
+        continuation_point: ByteString,
+    ) -> Result<()> {
+        if let Some(session) = &self.session {
+            let session_guard = session.read();
+            session_guard.browse_next(true, &[continuation_point])?;
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("Not connected to OPC UA server"))
+        }
+    }
+
+    fn browse_page_from_results(results: Option<Vec<BrowseResult>>) -> Result<OpcUaBrowsePage> {
+        let Some(result) = results.and_then(|results| results.into_iter().next()) else {
+            return Ok(OpcUaBrowsePage::default());
+        };
+        if result.status_code.is_bad() {
+            // Keep the StatusCode so callers can tell a server verdict from other failures
+            return Err(anyhow::Error::new(result.status_code)
+                .context(format!("Browse operation failed: {}", result.status_code)));
+        }
+
+        let mut nodes = Vec::new();
+        for reference in result.references.iter().flatten() {
+            let node_id = &reference.node_id.node_id;
+            let display_name = reference
+                .display_name
+                .text
+                .value()
+                .as_ref()
+                .map(|s| s.as_str())
+                .unwrap_or("<No Name>");
+            let browse_name = reference
+                .browse_name
+                .name
+                .value()
+                .as_ref()
+                .map(|s| s.as_str())
+                .unwrap_or("<No Name>");
+
+            // Determine if the node has children by checking if it's an object
+            let has_children = matches!(
+                reference.node_class,
+                NodeClass::Object | NodeClass::Variable | NodeClass::ObjectType
+            );
+
+            nodes.push(OpcUaNode {
+                node_id: node_id.clone(),
+                browse_name: browse_name.to_string(),
+                display_name: display_name.to_string(),
+                node_class: reference.node_class,
+                has_children,
+            });
+        }
+
+        let continuation_point =
+            Some(result.continuation_point).filter(|cp| !cp.is_null() && !cp.as_ref().is_empty());
+        Ok(OpcUaBrowsePage {
+            nodes,
+            continuation_point,
+        })
+    }
+
+    /// Browse every forward and inverse reference of a node, of any ReferenceType
+    pub async fn browse_references(&self, node_id: &NodeId) -> Result<Vec<OpcUaReference>> {
+        if let Some(session) = &self.session {
+            let session_guard = session.read();
+
+            let browse_description = BrowseDescription {
+                node_id: node_id.clone(),
+                browse_direction: BrowseDirection::Both,
+                reference_type_id: ReferenceTypeId::References.into(),
+                include_subtypes: true,
+                node_class_mask: 0, // Include all node classes
+                result_mask: 0x3F,  // All browse result attributes
+            };
+
+            let mut result = session_guard
+                .browse(&[browse_description])
+                .map_err(|e| anyhow::anyhow!("Browse operation failed: {}", e))?
+                .and_then(|results| results.into_iter().next());
+            let mut references = Vec::new();
+            while let Some(page) = result.take() {
+                references.extend(page.references.unwrap_or_default());
+                if !page.continuation_point.is_null()
+                    && !page.continuation_point.as_ref().is_empty()
+                {
+                    result = session_guard
+                        .browse_next(false, &[page.continuation_point])
+                        .map_err(|e| anyhow::anyhow!("BrowseNext failed: {}", e))?
+                        .and_then(|results| results.into_iter().next());
+                }
+            }
+
+            // Resolve the BrowseName of each distinct ReferenceType in one read
+            let mut reference_type_ids: Vec<NodeId> = Vec::new();
+            for reference in &references {
+                if !reference_type_ids.contains(&reference.reference_type_id) {
+                    reference_type_ids.push(reference.reference_type_id.clone());
+                }
+            }
+            let read_values: Vec<ReadValueId> = reference_type_ids
+                .iter()
+                .map(|type_id| ReadValueId {
+                    node_id: type_id.clone(),
+                    attribute_id: AttributeId::BrowseName as u32,
+                    index_range: UAString::null(),
+                    data_encoding: QualifiedName::null(),
+                })
+                .collect();
+            let type_names = if read_values.is_empty() {
+                Vec::new()
+            } else {
+                self.read_batched(&session_guard, &read_values, TimestampsToReturn::Neither)
+                    .unwrap_or_else(|e| {
+                        log::debug!("Failed to read ReferenceType names: {e}");
+                        Vec::new()
+                    })
+            };
+            let type_name = |type_id: &NodeId| {
+                reference_type_ids
+                    .iter()
+                    .position(|id| id == type_id)
+                    .and_then(|i| type_names.get(i))
+                    .and_then(|result| match &result.value {
+                        Some(Variant::QualifiedName(name)) => name.name.value().clone(),
+                        _ => None,
+                    })
+                    .unwrap_or_else(|| type_id.to_string())
+            };
+
+            Ok(references
+                .iter()
+                .map(|reference| OpcUaReference {
+                    reference_type: type_name(&reference.reference_type_id),
+                    reference_type_id: reference.reference_type_id.clone(),
+                    is_forward: reference.is_forward,
+                    target_id: reference.node_id.node_id.clone(),
+                    browse_name: reference
+                        .browse_name
+                        .name
+                        .value()
+                        .clone()
+                        .unwrap_or_default(),
+                    node_class: reference.node_class,
+                })
+                .collect())
+        } else {
+            Err(anyhow::anyhow!("Not connected to OPC UA server"))
+        }
+    }
+
     pub async fn read_node_attributes(&self, node_id: &NodeId) -> Result<Vec<OpcUaAttribute>> {
         if let Some(session) = &self.session {
             let session_guard = session.read();
-            let mut attributes = Vec::new(); // Define all the standard OPC UA attributes we want to read (excluding Value for special handling)
+            // Decodes structures and enumerations against the server's DataTypeDefinitions
+            let mut data_type_cache = self.data_type_cache.lock();
+            let mut decoder = DataTypeDecoder::new(&session_guard, &mut data_type_cache);
+            let mut node_data_type: Option<NodeId> = None;
+            let mut attributes = Vec::new(); // Define all the standard OPC UA attributes we want to read (Value is appended and handled separately)
             let attribute_ids = vec![
                 AttributeId::NodeId,
                 AttributeId::NodeClass,
@@ -162,163 +696,177 @@ impl OpcUaClientManager {
                 AttributeId::AccessLevelEx,
             ];
 
-            // Read standard attributes
-            for attr_id in attribute_ids {
-                let read_value_id = ReadValueId {
+            // Read all attributes and the Value in as few requests as the server allows.
+            // Attributes the NodeClass does not have come back as BadAttributeIdInvalid.
+            let read_values: Vec<ReadValueId> = attribute_ids
+                .iter()
+                .chain(std::iter::once(&AttributeId::Value))
+                .map(|attr_id| ReadValueId {
                     node_id: node_id.clone(),
-                    attribute_id: attr_id as u32,
+                    attribute_id: *attr_id as u32,
                     index_range: UAString::null(),
                     data_encoding: QualifiedName::null(),
-                };
+                })
+                .collect();
+            let results = self
+                .read_batched(&session_guard, &read_values, TimestampsToReturn::Both)
+                .map_err(|e| {
+                    log::warn!("Failed to read attributes for node {node_id}: {e}");
+                    anyhow::anyhow!("Failed to read attributes: {}", e)
+                })?;
 
-                match session_guard.read(&[read_value_id], TimestampsToReturn::Both, 0.0) {
-                    Ok(results) => {
-                        if let Some(result) = results.first() {
-                            let name = match attr_id {
-                                AttributeId::NodeId => "NodeId",
-                                AttributeId::NodeClass => "NodeClass",
-                                AttributeId::BrowseName => "BrowseName",
-                                AttributeId::DisplayName => "DisplayName",
-                                AttributeId::Description => "Description",
-                                AttributeId::WriteMask => "WriteMask",
-                                AttributeId::UserWriteMask => "UserWriteMask",
-                                AttributeId::IsAbstract => "IsAbstract",
-                                AttributeId::Symmetric => "Symmetric",
-                                AttributeId::InverseName => "InverseName",
-                                AttributeId::ContainsNoLoops => "ContainsNoLoops",
-                                AttributeId::EventNotifier => "EventNotifier",
-                                AttributeId::DataType => "DataType",
-                                AttributeId::ValueRank => "ValueRank",
-                                AttributeId::ArrayDimensions => "ArrayDimensions",
-                                AttributeId::AccessLevel => "AccessLevel",
-                                AttributeId::UserAccessLevel => "UserAccessLevel",
-                                AttributeId::MinimumSamplingInterval => "MinimumSamplingInterval",
-                                AttributeId::Historizing => "Historizing",
-                                AttributeId::Executable => "Executable",
-                                AttributeId::UserExecutable => "UserExecutable",
-                                AttributeId::DataTypeDefinition => "DataTypeDefinition",
-                                AttributeId::RolePermissions => "RolePermissions",
-                                AttributeId::UserRolePermissions => "UserRolePermissions",
-                                AttributeId::AccessRestrictions => "AccessRestrictions",
-                                AttributeId::AccessLevelEx => "AccessLevelEx",
-                                _ => "Unknown Attribute",
+            for (attr_id, result) in attribute_ids.iter().copied().zip(&results) {
+                let name = match attr_id {
+                    AttributeId::NodeId => "NodeId",
+                    AttributeId::NodeClass => "NodeClass",
+                    AttributeId::BrowseName => "BrowseName",
+                    AttributeId::DisplayName => "DisplayName",
+                    AttributeId::Description => "Description",
+                    AttributeId::WriteMask => "WriteMask",
+                    AttributeId::UserWriteMask => "UserWriteMask",
+                    AttributeId::IsAbstract => "IsAbstract",
+                    AttributeId::Symmetric => "Symmetric",
+                    AttributeId::InverseName => "InverseName",
+                    AttributeId::ContainsNoLoops => "ContainsNoLoops",
+                    AttributeId::EventNotifier => "EventNotifier",
+                    AttributeId::DataType => "DataType",
+                    AttributeId::ValueRank => "ValueRank",
+                    AttributeId::ArrayDimensions => "ArrayDimensions",
+                    AttributeId::AccessLevel => "AccessLevel",
+                    AttributeId::UserAccessLevel => "UserAccessLevel",
+                    AttributeId::MinimumSamplingInterval => "MinimumSamplingInterval",
+                    AttributeId::Historizing => "Historizing",
+                    AttributeId::Executable => "Executable",
+                    AttributeId::UserExecutable => "UserExecutable",
+                    AttributeId::DataTypeDefinition => "DataTypeDefinition",
+                    AttributeId::RolePermissions => "RolePermissions",
+                    AttributeId::UserRolePermissions => "UserRolePermissions",
+                    AttributeId::AccessRestrictions => "AccessRestrictions",
+                    AttributeId::AccessLevelEx => "AccessLevelEx",
+                    _ => "Unknown Attribute",
+                }
+                .to_string();
+                let mut decoded_fields = Vec::new();
+                let (value, _data_type) = match &result.value {
+                    Some(val) => {
+                        let (value_str, type_str) = match val {
+                            Variant::Boolean(b) => (b.to_string(), "Boolean"),
+                            Variant::SByte(n) => (n.to_string(), "SByte"),
+                            Variant::Byte(n) => {
+                                // Special handling for AccessLevel attribute
+                                if attr_id == AttributeId::AccessLevel {
+                                    (Self::format_access_level(*n), "AccessLevel")
+                                } else {
+                                    (n.to_string(), "Byte")
+                                }
                             }
-                            .to_string();
-                            let (value, _data_type) = match &result.value {
-                                Some(val) => {
-                                    let (value_str, type_str) = match val {
-                                        Variant::Boolean(b) => (b.to_string(), "Boolean"),
-                                        Variant::SByte(n) => (n.to_string(), "SByte"),
-                                        Variant::Byte(n) => {
-                                            // Special handling for AccessLevel attribute
-                                            if attr_id == AttributeId::AccessLevel {
-                                                (Self::format_access_level(*n), "AccessLevel")
-                                            } else {
-                                                (n.to_string(), "Byte")
-                                            }
-                                        }
-                                        Variant::Int16(n) => (n.to_string(), "Int16"),
-                                        Variant::UInt16(n) => (n.to_string(), "UInt16"),
-                                        Variant::Int32(n) => {
-                                            // Special handling for NodeClass attribute
-                                            if attr_id == AttributeId::NodeClass {
-                                                (Self::format_node_class(*n), "NodeClass")
-                                            } else {
-                                                (n.to_string(), "Int32")
-                                            }
-                                        }
-                                        Variant::UInt32(n) => (n.to_string(), "UInt32"),
-                                        Variant::Int64(n) => (n.to_string(), "Int64"),
-                                        Variant::UInt64(n) => (n.to_string(), "UInt64"),
-                                        Variant::Float(f) => (f.to_string(), "Float"),
-                                        Variant::Double(f) => (f.to_string(), "Double"),
-                                        Variant::String(s) => (
-                                            s.value()
-                                                .as_ref()
-                                                .map(|s| s.as_str())
-                                                .unwrap_or("(empty)")
-                                                .to_string(),
-                                            "String",
-                                        ),
-                                        Variant::DateTime(dt) => (dt.to_string(), "DateTime"),
-                                        Variant::Guid(g) => (g.to_string(), "Guid"),
-                                        Variant::ByteString(bs) => (
-                                            format!("ByteString[{}]", bs.as_ref().len()),
-                                            "ByteString",
-                                        ),
-                                        Variant::NodeId(id) => {
-                                            // Special handling for DataType attribute
-                                            if attr_id == AttributeId::DataType {
-                                                (Self::format_data_type(id), "DataType")
-                                            } else {
-                                                (id.to_string(), "NodeId")
-                                            }
-                                        }
-                                        Variant::QualifiedName(qn) => (
-                                            qn.name
-                                                .value()
-                                                .as_ref()
-                                                .map(|s| s.as_str())
-                                                .unwrap_or("(empty)")
-                                                .to_string(),
-                                            "QualifiedName",
-                                        ),
-                                        Variant::LocalizedText(lt) => (
-                                            lt.text
-                                                .value()
-                                                .as_ref()
-                                                .map(|s| s.as_str())
-                                                .unwrap_or("(empty)")
-                                                .to_string(),
-                                            "LocalizedText",
-                                        ),
-                                        Variant::StatusCode(sc) => {
-                                            (format!("{sc:?}"), "StatusCode")
-                                        }
-                                        _ => (format!("{val:?}"), "Unknown"),
-                                    };
-                                    (value_str, type_str.to_string())
+                            Variant::Int16(n) => (n.to_string(), "Int16"),
+                            Variant::UInt16(n) => (n.to_string(), "UInt16"),
+                            Variant::Int32(n) => {
+                                // Special handling for NodeClass attribute
+                                if attr_id == AttributeId::NodeClass {
+                                    (Self::format_node_class(*n), "NodeClass")
+                                } else {
+                                    (n.to_string(), "Int32")
                                 }
-                                None => ("(null)".to_string(), "Unknown".to_string()),
-                            };
+                            }
+                            Variant::UInt32(n) => (n.to_string(), "UInt32"),
+                            Variant::Int64(n) => (n.to_string(), "Int64"),
+                            Variant::UInt64(n) => (n.to_string(), "UInt64"),
+                            Variant::Float(f) => (f.to_string(), "Float"),
+                            Variant::Double(f) => (f.to_string(), "Double"),
+                            Variant::String(s) => (
+                                s.value()
+                                    .as_ref()
+                                    .map(|s| s.as_str())
+                                    .unwrap_or("(empty)")
+                                    .to_string(),
+                                "String",
+                            ),
+                            Variant::DateTime(dt) => (dt.to_string(), "DateTime"),
+                            Variant::Guid(g) => (g.to_string(), "Guid"),
+                            Variant::ByteString(bs) => {
+                                (format!("ByteString[{}]", bs.as_ref().len()), "ByteString")
+                            }
+                            Variant::NodeId(id) => {
+                                // Special handling for DataType attribute
+                                if attr_id == AttributeId::DataType {
+                                    node_data_type = Some((**id).clone());
+                                    (Self::format_data_type(id), "DataType")
+                                } else {
+                                    (id.to_string(), "NodeId")
+                                }
+                            }
+                            Variant::QualifiedName(qn) => (
+                                qn.name
+                                    .value()
+                                    .as_ref()
+                                    .map(|s| s.as_str())
+                                    .unwrap_or("(empty)")
+                                    .to_string(),
+                                "QualifiedName",
+                            ),
+                            Variant::LocalizedText(lt) => (
+                                lt.text
+                                    .value()
+                                    .as_ref()
+                                    .map(|s| s.as_str())
+                                    .unwrap_or("(empty)")
+                                    .to_string(),
+                                "LocalizedText",
+                            ),
+                            Variant::StatusCode(sc) => (format!("{sc:?}"), "StatusCode"),
+                            _ => {
+                                let decoded = decoder.decode_variant(val, None);
+                                decoded.flatten_fields(1, &mut decoded_fields);
+                                (decoded.text, "Structured")
+                            }
+                        };
+                        (value_str, type_str.to_string())
+                    }
+                    None => ("(null)".to_string(), "Unknown".to_string()),
+                };
 
-                            // Filter out null/empty attributes (except for Value which is handled separately)
-                            let should_include = match &result.value {
-                                Some(val) => match val {
-                                    Variant::String(s) => {
-                                        s.value().as_ref().map(|s| !s.is_empty()).unwrap_or(false)
-                                    }
-                                    Variant::LocalizedText(lt) => lt
-                                        .text
-                                        .value()
-                                        .as_ref()
-                                        .map(|s| !s.is_empty())
-                                        .unwrap_or(false),
-                                    Variant::QualifiedName(qn) => qn
-                                        .name
-                                        .value()
-                                        .as_ref()
-                                        .map(|s| !s.is_empty())
-                                        .unwrap_or(false),
-                                    Variant::ByteString(bs) => !bs.as_ref().is_empty(),
-                                    _ => true, // Include all other non-null variants
-                                },
-                                None => false, // Exclude null values
-                            };
+                // Filter out null/empty attributes (except for Value which is handled separately)
+                let should_include = match &result.value {
+                    Some(val) => match val {
+                        Variant::String(s) => {
+                            s.value().as_ref().map(|s| !s.is_empty()).unwrap_or(false)
+                        }
+                        Variant::LocalizedText(lt) => lt
+                            .text
+                            .value()
+                            .as_ref()
+                            .map(|s| !s.is_empty())
+                            .unwrap_or(false),
+                        Variant::QualifiedName(qn) => qn
+                            .name
+                            .value()
+                            .as_ref()
+                            .map(|s| !s.is_empty())
+                            .unwrap_or(false),
+                        Variant::ByteString(bs) => !bs.as_ref().is_empty(),
+                        _ => true, // Include all other non-null variants
+                    },
+                    None => false, // Exclude null values
+                };
 
-                            // Only add non-null/non-empty attributes
-                            if should_include {
-                                attributes.push(OpcUaAttribute {
-                                    name,
-                                    value,
-                                    is_value_good: false,
-                                });
-                            }
+                // Only add non-null/non-empty attributes
+                if should_include {
+                    attributes.push(OpcUaAttribute {
+                        name,
+                        value,
+                        is_value_good: false,
+                        depth: 0,
+                    });
+                    attributes.extend(decoded_fields.into_iter().map(|(depth, name, value)| {
+                        OpcUaAttribute {
+                            name,
+                            value,
+                            is_value_good: false,
+                            depth,
                         }
-                    }
-                    Err(e) => {
-                        log::warn!("Failed to read attribute {attr_id:?} for node {node_id}: {e}");
-                    }
+                    }));
                 }
             } // Check if this node can have a Value attribute by examining its NodeClass
             let node_class_from_attributes = attributes
@@ -329,133 +877,134 @@ impl OpcUaClientManager {
             let can_have_value = matches!(
                 node_class_from_attributes,
                 Some("Variable") | Some("VariableType")
-            ); // Only read Value attribute for Variable and VariableType nodes
+            ); // Only show the Value attribute for Variable and VariableType nodes
             if can_have_value {
-                let read_value_id = ReadValueId {
-                    node_id: node_id.clone(),
-                    attribute_id: AttributeId::Value as u32,
-                    index_range: UAString::null(),
-                    data_encoding: QualifiedName::null(),
-                };
-
-                match session_guard.read(&[read_value_id], TimestampsToReturn::Both, 0.0) {
-                    Ok(results) => {
-                        if let Some(data_value) = results.first() {
-                            let (value, _data_type) = match &data_value.value {
-                                Some(val) => {
-                                    let (value_str, type_str) = match val {
-                                        Variant::Boolean(b) => (b.to_string(), "Boolean"),
-                                        Variant::SByte(n) => (n.to_string(), "SByte"),
-                                        Variant::Byte(n) => (n.to_string(), "Byte"),
-                                        Variant::Int16(n) => (n.to_string(), "Int16"),
-                                        Variant::UInt16(n) => (n.to_string(), "UInt16"),
-                                        Variant::Int32(n) => (n.to_string(), "Int32"),
-                                        Variant::UInt32(n) => (n.to_string(), "UInt32"),
-                                        Variant::Int64(n) => (n.to_string(), "Int64"),
-                                        Variant::UInt64(n) => (n.to_string(), "UInt64"),
-                                        Variant::Float(f) => (f.to_string(), "Float"),
-                                        Variant::Double(f) => (f.to_string(), "Double"),
-                                        Variant::String(s) => (
-                                            s.value()
-                                                .as_ref()
-                                                .map(|s| s.as_str())
-                                                .unwrap_or("(empty)")
-                                                .to_string(),
-                                            "String",
-                                        ),
-                                        Variant::DateTime(dt) => (dt.to_string(), "DateTime"),
-                                        Variant::Guid(g) => (g.to_string(), "Guid"),
-                                        Variant::ByteString(bs) => (
-                                            format!("ByteString[{}]", bs.as_ref().len()),
-                                            "ByteString",
-                                        ),
-                                        Variant::NodeId(id) => (id.to_string(), "NodeId"),
-                                        Variant::QualifiedName(qn) => (
-                                            qn.name
-                                                .value()
-                                                .as_ref()
-                                                .map(|s| s.as_str())
-                                                .unwrap_or("(empty)")
-                                                .to_string(),
-                                            "QualifiedName",
-                                        ),
-                                        Variant::LocalizedText(lt) => (
-                                            lt.text
-                                                .value()
-                                                .as_ref()
-                                                .map(|s| s.as_str())
-                                                .unwrap_or("(empty)")
-                                                .to_string(),
-                                            "LocalizedText",
-                                        ),
-                                        Variant::StatusCode(sc) => {
-                                            (format!("{sc:?}"), "StatusCode")
-                                        }
-                                        _ => (format!("{val:?}"), "Unknown"),
-                                    };
-                                    (value_str, type_str.to_string())
+                if let Some(data_value) = results.get(attribute_ids.len()) {
+                    let mut decoded_fields = Vec::new();
+                    let (value, _data_type) = match &data_value.value {
+                        Some(val) => {
+                            let (value_str, type_str) = match val {
+                                Variant::Boolean(b) => (b.to_string(), "Boolean"),
+                                Variant::SByte(n) => (n.to_string(), "SByte"),
+                                Variant::Byte(n) => (n.to_string(), "Byte"),
+                                Variant::Int16(n) => (n.to_string(), "Int16"),
+                                Variant::UInt16(n) => (n.to_string(), "UInt16"),
+                                Variant::Int32(n) => match node_data_type
+                                    .as_ref()
+                                    .and_then(|dt| decoder.enum_name(dt, *n as i64))
+                                {
+                                    Some(name) => (format!("{name} ({n})"), "Enumeration"),
+                                    None => (n.to_string(), "Int32"),
+                                },
+                                Variant::UInt32(n) => (n.to_string(), "UInt32"),
+                                Variant::Int64(n) => (n.to_string(), "Int64"),
+                                Variant::UInt64(n) => (n.to_string(), "UInt64"),
+                                Variant::Float(f) => (f.to_string(), "Float"),
+                                Variant::Double(f) => (f.to_string(), "Double"),
+                                Variant::String(s) => (
+                                    s.value()
+                                        .as_ref()
+                                        .map(|s| s.as_str())
+                                        .unwrap_or("(empty)")
+                                        .to_string(),
+                                    "String",
+                                ),
+                                Variant::DateTime(dt) => (dt.to_string(), "DateTime"),
+                                Variant::Guid(g) => (g.to_string(), "Guid"),
+                                Variant::ByteString(bs) => {
+                                    (format!("ByteString[{}]", bs.as_ref().len()), "ByteString")
+                                }
+                                Variant::NodeId(id) => (id.to_string(), "NodeId"),
+                                Variant::QualifiedName(qn) => (
+                                    qn.name
+                                        .value()
+                                        .as_ref()
+                                        .map(|s| s.as_str())
+                                        .unwrap_or("(empty)")
+                                        .to_string(),
+                                    "QualifiedName",
+                                ),
+                                Variant::LocalizedText(lt) => (
+                                    lt.text
+                                        .value()
+                                        .as_ref()
+                                        .map(|s| s.as_str())
+                                        .unwrap_or("(empty)")
+                                        .to_string(),
+                                    "LocalizedText",
+                                ),
+                                Variant::StatusCode(sc) => (format!("{sc:?}"), "StatusCode"),
+                                _ => {
+                                    let decoded =
+                                        decoder.decode_variant(val, node_data_type.as_ref());
+                                    decoded.flatten_fields(1, &mut decoded_fields);
+                                    (decoded.text, "Structured")
                                 }
-                                None => ("(null)".to_string(), "Unknown".to_string()),
                             };
+                            (value_str, type_str.to_string())
+                        }
+                        None => ("(null)".to_string(), "Unknown".to_string()),
+                    };
 
-                            // Use DataValue.is_valid() to determine if value should be colored green
-                            let is_value_good = data_value.is_valid();
-
-                            attributes.push(OpcUaAttribute {
-                                name: "Value".to_string(),
-                                value,
-                                is_value_good,
-                            }); // Add custom debug attributes with indentation
-                            let value_status_text = if let Some(status_code) = &data_value.status {
-                                format!("{status_code:?}")
-                            } else {
-                                "Good".to_string()
-                            };
+                    // Use DataValue.is_valid() to determine if value should be colored green
+                    let is_value_good = data_value.is_valid();
 
-                            attributes.push(OpcUaAttribute {
-                                name: "   Status".to_string(),
-                                value: value_status_text,
-                                is_value_good: false,
-                            });
+                    attributes.push(OpcUaAttribute {
+                        name: "Value".to_string(),
+                        value,
+                        is_value_good,
+                        depth: 0,
+                    });
+                    attributes.extend(decoded_fields.into_iter().map(|(depth, name, value)| {
+                        OpcUaAttribute {
+                            name,
+                            value,
+                            is_value_good,
+                            depth,
+                        }
+                    })); // Add custom debug attributes with indentation
+                    let value_status_text = if let Some(status_code) = &data_value.status {
+                        format!("{status_code:?}")
+                    } else {
+                        "Good".to_string()
+                    };
 
-                            // Add SourceTimestamp attribute
-                            let source_timestamp_text =
-                                if let Some(timestamp) = &data_value.source_timestamp {
-                                    timestamp.to_string()
-                                } else {
-                                    "None".to_string()
-                                };
+                    attributes.push(OpcUaAttribute {
+                        name: "   Status".to_string(),
+                        value: value_status_text,
+                        is_value_good: false,
+                        depth: 0,
+                    });
 
-                            attributes.push(OpcUaAttribute {
-                                name: "   SourceTimestamp".to_string(),
-                                value: source_timestamp_text,
-                                is_value_good: false,
-                            });
+                    // Add SourceTimestamp attribute
+                    let source_timestamp_text =
+                        if let Some(timestamp) = &data_value.source_timestamp {
+                            timestamp.to_string()
+                        } else {
+                            "None".to_string()
+                        };
 
-                            // Add ServerTimestamp attribute
-                            let server_timestamp_text =
-                                if let Some(timestamp) = &data_value.server_timestamp {
-                                    timestamp.to_string()
-                                } else {
-                                    "None".to_string()
-                                };
+                    attributes.push(OpcUaAttribute {
+                        name: "   SourceTimestamp".to_string(),
+                        value: source_timestamp_text,
+                        is_value_good: false,
+                        depth: 0,
+                    });
 
-                            attributes.push(OpcUaAttribute {
-                                name: "   ServerTimestamp".to_string(),
-                                value: server_timestamp_text,
-                                is_value_good: false,
-                            });
-                        }
-                    }
-                    Err(e) => {
-                        log::warn!("Failed to read Value attribute for node {node_id}: {e}");
-                        // Add a placeholder Value attribute with error status
-                        attributes.push(OpcUaAttribute {
-                            name: "Value".to_string(),
-                            value: format!("Read Error: {e}"),
-                            is_value_good: false,
-                        });
-                    }
+                    // Add ServerTimestamp attribute
+                    let server_timestamp_text =
+                        if let Some(timestamp) = &data_value.server_timestamp {
+                            timestamp.to_string()
+                        } else {
+                            "None".to_string()
+                        };
+
+                    attributes.push(OpcUaAttribute {
+                        name: "   ServerTimestamp".to_string(),
+                        value: server_timestamp_text,
+                        is_value_good: false,
+                        depth: 0,
+                    });
                 }
             }
             // Note: For nodes that cannot have values (Objects, Methods, etc.),
@@ -474,120 +1023,376 @@ impl OpcUaClientManager {
         }
     }
 
-    /// Read only the attributes needed for search (BrowseName, DisplayName, and optionally Value)
-    /// This is much more efficient than reading all node attributes
-    pub async fn read_node_search_attributes(
-        &self,
-        node_id: &NodeId,
-        include_value: bool,
-    ) -> Result<(String, String, Option<String>, NodeClass)> {
+    /// Read DataType, ValueRank, UserAccessLevel and the current Value of a node in one request
+    pub async fn read_value_info(&self, node_id: &NodeId) -> Result<OpcUaValueInfo> {
         if let Some(session) = &self.session {
             let session_guard = session.read();
 
-            // Build read values - always include BrowseName, DisplayName, and NodeClass
-            let mut read_values = vec![
-                ReadValueId {
-                    node_id: node_id.clone(),
-                    attribute_id: AttributeId::BrowseName as u32,
-                    index_range: UAString::null(),
-                    data_encoding: QualifiedName::null(),
-                },
-                ReadValueId {
-                    node_id: node_id.clone(),
-                    attribute_id: AttributeId::DisplayName as u32,
-                    index_range: UAString::null(),
-                    data_encoding: QualifiedName::null(),
-                },
-                ReadValueId {
-                    node_id: node_id.clone(),
-                    attribute_id: AttributeId::NodeClass as u32,
-                    index_range: UAString::null(),
-                    data_encoding: QualifiedName::null(),
-                },
-            ];
+            let read_values: Vec<ReadValueId> = [
+                AttributeId::DataType,
+                AttributeId::ValueRank,
+                AttributeId::UserAccessLevel,
+                AttributeId::Value,
+            ]
+            .iter()
+            .map(|attr_id| ReadValueId {
+                node_id: node_id.clone(),
+                attribute_id: *attr_id as u32,
+                index_range: UAString::null(),
+                data_encoding: QualifiedName::null(),
+            })
+            .collect();
 
-            // Optionally include Value attribute
-            if include_value {
-                read_values.push(ReadValueId {
-                    node_id: node_id.clone(),
-                    attribute_id: AttributeId::Value as u32,
-                    index_range: UAString::null(),
-                    data_encoding: QualifiedName::null(),
-                });
-            }
+            let results = self
+                .read_batched(&session_guard, &read_values, TimestampsToReturn::Neither)
+                .map_err(|e| anyhow::anyhow!("Failed to read value info: {}", e))?;
 
-            match session_guard.read(&read_values, TimestampsToReturn::Neither, 0.0) {
-                Ok(results) => {
-                    let browse_name = if let Some(result) = results.first() {
-                        if let Some(Variant::QualifiedName(qname)) = &result.value {
-                            qname
-                                .name
-                                .value()
-                                .as_ref()
-                                .map(|s| s.as_str())
-                                .unwrap_or("(empty)")
-                                .to_string()
-                        } else {
-                            String::new()
-                        }
-                    } else {
-                        String::new()
-                    };
+            let data_type = match results.first().and_then(|r| r.value.as_ref()) {
+                Some(Variant::NodeId(id)) => (**id).clone(),
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "Node {} has no DataType attribute",
+                        node_id
+                    ))
+                }
+            };
+            let value_rank = match results.get(1).and_then(|r| r.value.as_ref()) {
+                Some(Variant::Int32(rank)) => *rank,
+                _ => -1, // Scalar
+            };
+            let user_access_level = match results.get(2).and_then(|r| r.value.as_ref()) {
+                Some(Variant::Byte(level)) => *level,
+                _ => 0,
+            };
+            let current_value = results.get(3).and_then(|r| r.value.clone());
 
-                    let display_name = if let Some(result) = results.get(1) {
-                        if let Some(Variant::LocalizedText(ltext)) = &result.value {
-                            ltext
-                                .text
-                                .value()
-                                .as_ref()
-                                .map(|s| s.as_str())
-                                .unwrap_or("(empty)")
-                                .to_string()
-                        } else {
-                            String::new()
-                        }
-                    } else {
-                        String::new()
-                    };
+            Ok(OpcUaValueInfo {
+                data_type,
+                value_rank,
+                user_access_level,
+                current_value,
+            })
+        } else {
+            Err(anyhow::anyhow!("Not connected to OPC UA server"))
+        }
+    }
 
-                    let node_class = if let Some(result) = results.get(2) {
-                        if let Some(Variant::Int32(class_value)) = &result.value {
-                            match *class_value {
-                                1 => NodeClass::Object,
-                                2 => NodeClass::Variable,
-                                4 => NodeClass::Method,
-                                8 => NodeClass::ObjectType,
-                                16 => NodeClass::VariableType,
-                                32 => NodeClass::ReferenceType,
-                                64 => NodeClass::DataType,
-                                128 => NodeClass::View,
-                                _ => NodeClass::Unspecified,
-                            }
-                        } else {
-                            NodeClass::Unspecified
-                        }
-                    } else {
-                        NodeClass::Unspecified
-                    };
+    /// Read one attribute of a node with its status and timestamps
+    pub async fn read_attribute(
+        &self,
+        node_id: &NodeId,
+        attribute_id: AttributeId,
+    ) -> Result<DataValue> {
+        if let Some(session) = &self.session {
+            let session_guard = session.read();
 
-                    // Handle Value attribute if requested
-                    let value_attr = if include_value {
-                        if let Some(result) = results.get(3) {
-                            // Check if the status is good and value is present
-                            result.value.as_ref().map(|variant| format!("{variant}"))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    };
+            let read_value_id = ReadValueId {
+                node_id: node_id.clone(),
+                attribute_id: attribute_id as u32,
+                index_range: UAString::null(),
+                data_encoding: QualifiedName::null(),
+            };
+            let results = session_guard
+                .read(&[read_value_id], TimestampsToReturn::Both, 0.0)
+                .map_err(|e| anyhow::anyhow!("Read operation failed: {}", e))?;
+            results
+                .into_iter()
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("Server returned no result for {}", node_id))
+        } else {
+            Err(anyhow::anyhow!("Not connected to OPC UA server"))
+        }
+    }
+
+    /// Read an array Value, or only the slice selected by `index_range` (e.g. "10:20" or
+    /// "0:1,2:3" for matrices), and format each element for display.
+    pub async fn read_array_value(
+        &self,
+        node_id: &NodeId,
+        index_range: &str,
+    ) -> Result<OpcUaArrayValue> {
+        let index_range = index_range.trim();
+        if NumericRange::from_str(index_range).is_err() {
+            return Err(anyhow::anyhow!("Invalid index range '{}'", index_range));
+        }
+
+        if let Some(session) = &self.session {
+            let session_guard = session.read();
+
+            let read_values = [
+                (AttributeId::DataType, UAString::null()),
+                (AttributeId::ArrayDimensions, UAString::null()),
+                (AttributeId::Value, UAString::from(index_range)),
+            ]
+            .into_iter()
+            .map(|(attr_id, index_range)| ReadValueId {
+                node_id: node_id.clone(),
+                attribute_id: attr_id as u32,
+                index_range,
+                data_encoding: QualifiedName::null(),
+            })
+            .collect::<Vec<_>>();
+
+            let results = self
+                .read_batched(&session_guard, &read_values, TimestampsToReturn::Neither)
+                .map_err(|e| anyhow::anyhow!("Failed to read array value: {}", e))?;
+
+            let data_type = match results.first().and_then(|r| r.value.as_ref()) {
+                Some(Variant::NodeId(id)) => Some((**id).clone()),
+                _ => None,
+            };
+            let node_dimensions = match results.get(1).and_then(|r| r.value.as_ref()) {
+                Some(Variant::Array(array)) => array
+                    .values
+                    .iter()
+                    .filter_map(|v| match v {
+                        Variant::UInt32(dimension) => Some(*dimension),
+                        _ => None,
+                    })
+                    .collect::<Vec<u32>>(),
+                _ => Vec::new(),
+            };
+            let Some(value) = results.get(2) else {
+                return Err(anyhow::anyhow!("Server returned no Value for {}", node_id));
+            };
+            let status = value.status.unwrap_or(StatusCode::Good);
+            if status.is_bad() {
+                return Err(anyhow::anyhow!("Failed to read array value: {}", status));
+            }
+
+            let array = match &value.value {
+                Some(Variant::Array(array)) => array,
+                Some(Variant::Empty) | None => {
+                    return Err(anyhow::anyhow!("Node {} has no value", node_id))
+                }
+                Some(_) => return Err(anyhow::anyhow!("Value of {} is not an array", node_id)),
+            };
+
+            // Prefer the dimensions encoded with the value; a whole matrix read may only
+            // carry them in the ArrayDimensions attribute
+            let element_count = array.values.len() as u64;
+            let dimensions = match &array.dimensions {
+                Some(dimensions) if dimensions.len() > 1 => dimensions.clone(),
+                _ if node_dimensions.len() > 1
+                    && node_dimensions.iter().map(|d| *d as u64).product::<u64>()
+                        == element_count =>
+                {
+                    node_dimensions
+                }
+                _ => vec![array.values.len() as u32],
+            };
+
+            let mut data_type_cache = self.data_type_cache.lock();
+            let mut decoder = DataTypeDecoder::new(&session_guard, &mut data_type_cache);
+            let elements = array
+                .values
+                .iter()
+                .map(|element| decoder.decode_variant(element, data_type.as_ref()).text)
+                .collect();
+
+            Ok(OpcUaArrayValue {
+                value_type: data_type
+                    .as_ref()
+                    .map(Self::format_data_type)
+                    .unwrap_or_else(|| format!("{:?}", array.value_type)),
+                dimensions,
+                elements,
+                status,
+            })
+        } else {
+            Err(anyhow::anyhow!("Not connected to OPC UA server"))
+        }
+    }
+
+    /// Write a new Value to a node and return the StatusCode reported by the server
+    pub async fn write_node_value(&self, node_id: &NodeId, value: Variant) -> Result<StatusCode> {
+        if let Some(session) = &self.session {
+            let session_guard = session.read();
+
+            let write_value = WriteValue {
+                node_id: node_id.clone(),
+                attribute_id: AttributeId::Value as u32,
+                index_range: UAString::null(),
+                value: DataValue::value_only(value),
+            };
+
+            match session_guard.write(&[write_value]) {
+                Ok(results) => {
+                    let status = results
+                        .first()
+                        .copied()
+                        .unwrap_or(StatusCode::BadUnexpectedError);
+                    log::info!("Write to node {node_id} returned {status}");
+                    Ok(status)
+                }
+                Err(e) => {
+                    log::warn!("Failed to write value to node {node_id}: {e}");
+                    Err(anyhow::anyhow!("Write operation failed: {}", e))
+                }
+            }
+        } else {
+            Err(anyhow::anyhow!("Not connected to OPC UA server"))
+        }
+    }
+
+    /// Read the InputArguments and OutputArguments properties of a Method node
+    pub async fn read_method_arguments(&self, method_id: &NodeId) -> Result<OpcUaMethodArguments> {
+        if let Some(session) = &self.session {
+            let session_guard = session.read();
+
+            let browse_description = BrowseDescription {
+                node_id: method_id.clone(),
+                browse_direction: BrowseDirection::Forward,
+                reference_type_id: ReferenceTypeId::HasProperty.into(),
+                include_subtypes: true,
+                node_class_mask: NodeClassMask::VARIABLE.bits(),
+                result_mask: 0x3F,
+            };
+            let results = session_guard
+                .browse(&[browse_description])
+                .map_err(|e| anyhow::anyhow!("Failed to browse method properties: {}", e))?;
+
+            // Find the property nodes holding the argument definitions
+            let mut input_node = None;
+            let mut output_node = None;
+            let references = results
+                .and_then(|results| results.into_iter().next())
+                .and_then(|result| result.references)
+                .unwrap_or_default();
+            for reference in references {
+                match reference.browse_name.name.as_ref() {
+                    "InputArguments" => input_node = Some(reference.node_id.node_id),
+                    "OutputArguments" => output_node = Some(reference.node_id.node_id),
+                    _ => {}
+                }
+            }
+
+            let mut arguments = OpcUaMethodArguments::default();
+            let property_nodes: Vec<(NodeId, bool)> = [(input_node, true), (output_node, false)]
+                .into_iter()
+                .filter_map(|(node, is_input)| node.map(|node| (node, is_input)))
+                .collect();
+            if property_nodes.is_empty() {
+                return Ok(arguments);
+            }
+
+            let read_values: Vec<ReadValueId> = property_nodes
+                .iter()
+                .map(|(node_id, _)| ReadValueId {
+                    node_id: node_id.clone(),
+                    attribute_id: AttributeId::Value as u32,
+                    index_range: UAString::null(),
+                    data_encoding: QualifiedName::null(),
+                })
+                .collect();
+            let values = session_guard
+                .read(&read_values, TimestampsToReturn::Neither, 0.0)
+                .map_err(|e| anyhow::anyhow!("Failed to read method arguments: {}", e))?;
+
+            for ((_, is_input), data_value) in property_nodes.iter().zip(values) {
+                let decoded = Self::decode_arguments(data_value.value.as_ref());
+                if *is_input {
+                    arguments.input_arguments = decoded;
+                } else {
+                    arguments.output_arguments = decoded;
+                }
+            }
+
+            Ok(arguments)
+        } else {
+            Err(anyhow::anyhow!("Not connected to OPC UA server"))
+        }
+    }
+
+    /// Parse typed input arguments by the DataType and ValueRank of each InputArgument.
+    /// Subtypes such as custom enumerations are encoded as their built-in supertype, and
+    /// abstract DataTypes like BaseDataType or Number take the type of the literal.
+    pub async fn parse_method_inputs(
+        &self,
+        arguments: &[Argument],
+        inputs: &[&str],
+    ) -> Result<Vec<Variant>> {
+        let Some(session) = &self.session else {
+            return Err(anyhow::anyhow!("Not connected to OPC UA server"));
+        };
+        let session_guard = session.read();
+        let mut data_type_cache = self.data_type_cache.lock();
+        let mut decoder = DataTypeDecoder::new(&session_guard, &mut data_type_cache);
+
+        arguments
+            .iter()
+            .zip(inputs)
+            .map(|(argument, input)| {
+                match decoder.value_type(&argument.data_type) {
+                    Some(value_type) => {
+                        ValueUtils::parse_variant(input, value_type, argument.value_rank)
+                    }
+                    None => ValueUtils::infer_variant(input, argument.value_rank),
+                }
+                .map_err(|e| anyhow::anyhow!("{}: {}", argument.name.as_ref(), e))
+            })
+            .collect()
+    }
+
+    /// Find the Object that owns a Method through an inverse HasComponent reference
+    pub async fn find_method_owner(&self, method_id: &NodeId) -> Result<NodeId> {
+        if let Some(session) = &self.session {
+            let session_guard = session.read();
+
+            let browse_description = BrowseDescription {
+                node_id: method_id.clone(),
+                browse_direction: BrowseDirection::Inverse,
+                reference_type_id: ReferenceTypeId::HasComponent.into(),
+                include_subtypes: true,
+                node_class_mask: (NodeClassMask::OBJECT | NodeClassMask::OBJECT_TYPE).bits(),
+                result_mask: 0x3F,
+            };
+            let results = session_guard
+                .browse(&[browse_description])
+                .map_err(|e| anyhow::anyhow!("Failed to browse method owner: {}", e))?;
+
+            results
+                .and_then(|results| results.into_iter().next())
+                .and_then(|result| result.references)
+                .and_then(|references| references.into_iter().next())
+                .map(|reference| reference.node_id.node_id)
+                .ok_or_else(|| anyhow::anyhow!("No owning object found for method {}", method_id))
+        } else {
+            Err(anyhow::anyhow!("Not connected to OPC UA server"))
+        }
+    }
+
+    /// Invoke a method on an object with the given input arguments
+    pub async fn call_method(
+        &self,
+        object_id: &NodeId,
+        method_id: &NodeId,
+        input_arguments: Vec<Variant>,
+    ) -> Result<CallMethodResult> {
+        if let Some(session) = &self.session {
+            let session_guard = session.read();
+
+            let request = CallMethodRequest {
+                object_id: object_id.clone(),
+                method_id: method_id.clone(),
+                input_arguments: if input_arguments.is_empty() {
+                    None
+                } else {
+                    Some(input_arguments)
+                },
+            };
 
-                    Ok((browse_name, display_name, value_attr, node_class))
+            match session_guard.call(request) {
+                Ok(result) => {
+                    log::info!(
+                        "Call of method {method_id} on {object_id} returned {}",
+                        result.status_code
+                    );
+                    Ok(result)
                 }
                 Err(e) => {
-                    log::debug!("Failed to read search attributes for node {node_id}: {e}");
-                    // Return empty strings and default NodeClass if we can't read the attributes
-                    Ok((String::new(), String::new(), None, NodeClass::Unspecified))
+                    log::warn!("Failed to call method {method_id} on {object_id}: {e}");
+                    Err(anyhow::anyhow!("Call operation failed: {}", e))
                 }
             }
         } else {
@@ -595,9 +1400,680 @@ impl OpcUaClientManager {
         }
     }
 
-    pub async fn get_root_node(&self) -> Result<NodeId> {
-        // Return the Objects folder as the root
-        Ok(ObjectId::ObjectsFolder.into())
+    /// Call Acknowledge, Confirm or AddComment on a condition for the given event
+    pub async fn call_condition_method(
+        &self,
+        condition_id: &NodeId,
+        action: ConditionAction,
+        event_id: ByteString,
+        comment: &str,
+    ) -> Result<StatusCode> {
+        let input_arguments = vec![
+            Variant::ByteString(event_id),
+            Variant::LocalizedText(Box::new(LocalizedText::new("", comment))),
+        ];
+        let result = self
+            .call_method(condition_id, &action.method_id(), input_arguments)
+            .await?;
+        Ok(result.status_code)
+    }
+
+    /// Decode the Value of an InputArguments or OutputArguments property
+    pub fn decode_arguments(value: Option<&Variant>) -> Vec<Argument> {
+        let decoding_options = opcua::types::DecodingOptions::default();
+        let decode = |variant: &Variant| match variant {
+            Variant::ExtensionObject(extension_object) => extension_object
+                .decode_inner::<Argument>(&decoding_options)
+                .map_err(|e| log::warn!("Failed to decode method argument: {e}"))
+                .ok(),
+            _ => None,
+        };
+
+        match value {
+            Some(Variant::Array(array)) => array.values.iter().filter_map(decode).collect(),
+            Some(variant) => decode(variant).into_iter().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Read one page of history for a node. Pass the continuation point of the previous
+    /// page to continue where it left off.
+    pub async fn history_read(
+        &self,
+        node_id: &NodeId,
+        query: &HistoryQuery,
+        continuation_point: Option<ByteString>,
+    ) -> Result<OpcUaHistoryPage> {
+        let result = self
+            .send_history_read(node_id, query, continuation_point, false)
+            .await?;
+
+        if result.status_code.is_bad() {
+            return Err(anyhow::anyhow!(
+                "HistoryRead failed: {}",
+                result.status_code
+            ));
+        }
+
+        let history_data = result
+            .history_data
+            .decode_inner::<HistoryData>(&opcua::types::DecodingOptions::default())
+            .map_err(|e| anyhow::anyhow!("Failed to decode history data: {}", e))?;
+
+        Ok(OpcUaHistoryPage {
+            values: history_data.data_values.unwrap_or_default(),
+            continuation_point: if result.continuation_point.is_null_or_empty() {
+                None
+            } else {
+                Some(result.continuation_point)
+            },
+        })
+    }
+
+    /// Release a continuation point the server is holding for a history read that will not be continued
+    pub async fn release_history_continuation_point(
+        &self,
+        node_id: &NodeId,
+        query: &HistoryQuery,
+        continuation_point: ByteString,
+    ) -> Result<()> {
+        self.send_history_read(node_id, query, Some(continuation_point), true)
+            .await
+            .map(|_| ())
+    }
+
+    async fn send_history_read(
+        &self,
+        node_id: &NodeId,
+        query: &HistoryQuery,
+        continuation_point: Option<ByteString>,
+        release_continuation_points: bool,
+    ) -> Result<HistoryReadResult> {
+        if let Some(session) = &self.session {
+            let session_guard = session.read();
+
+            let details = match query.mode.aggregate_id() {
+                None => HistoryReadAction::ReadRawModifiedDetails(ReadRawModifiedDetails {
+                    is_read_modified: false,
+                    start_time: query.start_time,
+                    end_time: query.end_time,
+                    num_values_per_node: query.values_per_page,
+                    return_bounds: false,
+                }),
+                Some(aggregate) => HistoryReadAction::ReadProcessedDetails(ReadProcessedDetails {
+                    start_time: query.start_time,
+                    end_time: query.end_time,
+                    processing_interval: query.processing_interval,
+                    aggregate_type: Some(vec![aggregate]),
+                    aggregate_configuration: AggregateConfiguration {
+                        use_server_capabilities_defaults: true,
+                        treat_uncertain_as_bad: false,
+                        percent_data_bad: 100,
+                        percent_data_good: 100,
+                        use_sloped_extrapolation: false,
+                    },
+                }),
+            };
+
+            let value_id = HistoryReadValueId {
+                node_id: node_id.clone(),
+                index_range: UAString::null(),
+                data_encoding: QualifiedName::null(),
+                continuation_point: continuation_point.unwrap_or_else(ByteString::null),
+            };
+
+            let results = session_guard
+                .history_read(
+                    details,
+                    TimestampsToReturn::Both,
+                    release_continuation_points,
+                    &[value_id],
+                )
+                .map_err(|e| {
+                    log::warn!("HistoryRead of node {node_id} failed: {e}");
+                    anyhow::anyhow!("HistoryRead failed: {}", e)
+                })?;
+
+            results
+                .into_iter()
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("No HistoryRead result for node {}", node_id))
+        } else {
+            Err(anyhow::anyhow!("Not connected to OPC UA server"))
+        }
+    }
+
+    /// Create the subscription backing the watch list. DataChange notifications are
+    /// forwarded to `sender` so the UI can pick them up on its next tick.
+    pub async fn create_watch_subscription(
+        &mut self,
+        publishing_interval: f64,
+        sender: mpsc::UnboundedSender<OpcUaDataChange>,
+    ) -> Result<u32> {
+        let Some(session) = self.session.clone() else {
+            return Err(anyhow::anyhow!("Not connected to OPC UA server"));
+        };
+
+        let subscription_id = {
+            let session_guard = session.read();
+            session_guard
+                .create_subscription(
+                    publishing_interval,
+                    60,
+                    10,
+                    0,
+                    0,
+                    true,
+                    DataChangeCallback::new(move |changed_items| {
+                        for item in changed_items {
+                            let _ = sender.send(OpcUaDataChange {
+                                client_handle: item.client_handle(),
+                                value: item.last_value().clone(),
+                            });
+                        }
+                    }),
+                )
+                .map_err(|e| anyhow::anyhow!("Failed to create subscription: {}", e))?
+        };
+        log::info!("Created watch subscription {subscription_id}");
+
+        // Publish responses are only dispatched while the session run loop is polling
+        if self.session_runner.is_none() {
+            self.session_runner = Some(Session::run_async(session));
+        }
+
+        self.watch_subscription_id = Some(subscription_id);
+        Ok(subscription_id)
+    }
+
+    /// Add a monitored item for the Value of `node_id` to the watch subscription
+    pub async fn add_monitored_item(
+        &self,
+        node_id: &NodeId,
+        client_handle: u32,
+        settings: &MonitoringSettings,
+    ) -> Result<u32> {
+        let (session, subscription_id) = self.watch_subscription()?;
+        let session_guard = session.read();
+
+        let request = MonitoredItemCreateRequest {
+            item_to_monitor: ReadValueId {
+                node_id: node_id.clone(),
+                attribute_id: AttributeId::Value as u32,
+                index_range: UAString::null(),
+                data_encoding: QualifiedName::null(),
+            },
+            monitoring_mode: MonitoringMode::Reporting,
+            requested_parameters: Self::monitoring_parameters(client_handle, settings),
+        };
+
+        let results = session_guard
+            .create_monitored_items(subscription_id, TimestampsToReturn::Both, &[request])
+            .map_err(|e| anyhow::anyhow!("Failed to create monitored item: {}", e))?;
+        match results.first() {
+            Some(result) if result.status_code.is_good() => Ok(result.monitored_item_id),
+            Some(result) => Err(anyhow::anyhow!(
+                "Server rejected monitored item: {}",
+                result.status_code
+            )),
+            None => Err(anyhow::anyhow!("No result for monitored item")),
+        }
+    }
+
+    /// Change the sampling interval, queue size and deadband of a monitored item
+    pub async fn modify_monitored_item(
+        &self,
+        monitored_item_id: u32,
+        client_handle: u32,
+        settings: &MonitoringSettings,
+    ) -> Result<()> {
+        let (session, subscription_id) = self.watch_subscription()?;
+        let session_guard = session.read();
+
+        let request = MonitoredItemModifyRequest {
+            monitored_item_id,
+            requested_parameters: Self::monitoring_parameters(client_handle, settings),
+        };
+
+        let results = session_guard
+            .modify_monitored_items(subscription_id, TimestampsToReturn::Both, &[request])
+            .map_err(|e| anyhow::anyhow!("Failed to modify monitored item: {}", e))?;
+        match results.first() {
+            Some(result) if result.status_code.is_good() => Ok(()),
+            Some(result) => Err(anyhow::anyhow!(
+                "Server rejected monitoring parameters: {}",
+                result.status_code
+            )),
+            None => Err(anyhow::anyhow!("No result for monitored item")),
+        }
+    }
+
+    /// Remove a monitored item from the watch subscription
+    pub async fn remove_monitored_item(&self, monitored_item_id: u32) -> Result<()> {
+        let (session, subscription_id) = self.watch_subscription()?;
+        let session_guard = session.read();
+
+        session_guard
+            .delete_monitored_items(subscription_id, &[monitored_item_id])
+            .map_err(|e| anyhow::anyhow!("Failed to delete monitored item: {}", e))?;
+        Ok(())
+    }
+
+    /// Create a subscription with a single event monitored item on `notifier_id`.
+    /// Event field lists are forwarded to `sender` so the UI can pick them up on its next tick.
+    pub async fn create_event_subscription(
+        &mut self,
+        notifier_id: &NodeId,
+        client_handle: u32,
+        filter: &EventFilter,
+        sender: mpsc::UnboundedSender<OpcUaEvent>,
+    ) -> Result<u32> {
+        let Some(session) = self.session.clone() else {
+            return Err(anyhow::anyhow!("Not connected to OPC UA server"));
+        };
+
+        let subscription_id = {
+            let session_guard = session.read();
+            let subscription_id = session_guard
+                .create_subscription(
+                    500.0,
+                    60,
+                    10,
+                    0,
+                    0,
+                    true,
+                    EventCallback::new(move |notification| {
+                        for event in notification.events.iter().flatten() {
+                            let _ = sender.send(OpcUaEvent {
+                                client_handle: event.client_handle,
+                                fields: event.event_fields.clone().unwrap_or_default(),
+                            });
+                        }
+                    }),
+                )
+                .map_err(|e| anyhow::anyhow!("Failed to create event subscription: {}", e))?;
+
+            let request = MonitoredItemCreateRequest {
+                item_to_monitor: ReadValueId {
+                    node_id: notifier_id.clone(),
+                    attribute_id: AttributeId::EventNotifier as u32,
+                    index_range: UAString::null(),
+                    data_encoding: QualifiedName::null(),
+                },
+                monitoring_mode: MonitoringMode::Reporting,
+                requested_parameters: MonitoringParameters {
+                    client_handle,
+                    sampling_interval: 0.0,
+                    filter: ExtensionObject::from_encodable(
+                        ObjectId::EventFilter_Encoding_DefaultBinary,
+                        filter,
+                    ),
+                    queue_size: 1000,
+                    discard_oldest: true,
+                },
+            };
+
+            let created = session_guard
+                .create_monitored_items(subscription_id, TimestampsToReturn::Neither, &[request])
+                .map_err(|e| anyhow::anyhow!("Failed to create event monitored item: {}", e))
+                .and_then(|results| match results.first() {
+                    Some(result) if result.status_code.is_good() => {
+                        Self::log_event_filter_result(&result.filter_result);
+                        Ok(())
+                    }
+                    Some(result) => Err(anyhow::anyhow!(
+                        "Server rejected event filter: {}",
+                        result.status_code
+                    )),
+                    None => Err(anyhow::anyhow!("No result for event monitored item")),
+                });
+            if let Err(e) = created {
+                let _ = session_guard.delete_subscription(subscription_id);
+                return Err(e);
+            }
+            subscription_id
+        };
+        log::info!("Created event subscription {subscription_id} on {notifier_id}");
+
+        // Publish responses are only dispatched while the session run loop is polling
+        if self.session_runner.is_none() {
+            self.session_runner = Some(Session::run_async(session));
+        }
+
+        self.event_subscription_id = Some(subscription_id);
+        Ok(subscription_id)
+    }
+
+    /// Delete the event subscription, if one exists
+    pub async fn delete_event_subscription(&mut self) -> Result<()> {
+        let (Some(session), Some(subscription_id)) =
+            (&self.session, self.event_subscription_id.take())
+        else {
+            return Ok(());
+        };
+
+        let session_guard = session.read();
+        session_guard
+            .delete_subscription(subscription_id)
+            .map_err(|e| anyhow::anyhow!("Failed to delete event subscription: {}", e))?;
+        log::info!("Deleted event subscription {subscription_id}");
+        Ok(())
+    }
+
+    fn log_event_filter_result(filter_result: &ExtensionObject) {
+        if filter_result.is_null() {
+            return;
+        }
+        let decoding_options = opcua::types::DecodingOptions::default();
+        let Ok(result) = filter_result.decode_inner::<EventFilterResult>(&decoding_options) else {
+            return;
+        };
+
+        for (i, status) in result.select_clause_results.iter().flatten().enumerate() {
+            if !status.is_good() {
+                log::warn!("Event select clause {i} was rejected: {status}");
+            }
+        }
+        for (i, element) in result
+            .where_clause_result
+            .element_results
+            .iter()
+            .flatten()
+            .enumerate()
+        {
+            if !element.status_code.is_good() {
+                log::warn!(
+                    "Event where clause element {i} was rejected: {}",
+                    element.status_code
+                );
+            }
+        }
+    }
+
+    fn watch_subscription(&self) -> Result<(Arc<RwLock<Session>>, u32)> {
+        match (&self.session, self.watch_subscription_id) {
+            (Some(session), Some(subscription_id)) => Ok((session.clone(), subscription_id)),
+            (None, _) => Err(anyhow::anyhow!("Not connected to OPC UA server")),
+            (Some(_), None) => Err(anyhow::anyhow!("No watch subscription has been created")),
+        }
+    }
+
+    fn monitoring_parameters(
+        client_handle: u32,
+        settings: &MonitoringSettings,
+    ) -> MonitoringParameters {
+        // An absolute deadband is only meaningful for numeric values, so only send a filter when set
+        let filter = if settings.deadband > 0.0 {
+            ExtensionObject::from_encodable(
+                ObjectId::DataChangeFilter_Encoding_DefaultBinary,
+                &DataChangeFilter {
+                    trigger: DataChangeTrigger::StatusValue,
+                    deadband_type: DeadbandType::Absolute as u32,
+                    deadband_value: settings.deadband,
+                },
+            )
+        } else {
+            ExtensionObject::null()
+        };
+
+        MonitoringParameters {
+            client_handle,
+            sampling_interval: settings.sampling_interval,
+            filter,
+            queue_size: settings.queue_size,
+            discard_oldest: true,
+        }
+    }
+
+    /// Read BrowseName, DisplayName, NodeClass and optionally the Value of many nodes at once,
+    /// batched by the server's MaxNodesPerRead. Results are in the order of `node_ids`; nodes
+    /// that could not be read have empty names and an unspecified NodeClass.
+    pub async fn read_nodes_search_attributes(
+        &self,
+        node_ids: &[NodeId],
+        include_value: bool,
+    ) -> Result<Vec<OpcUaSearchAttributes>> {
+        if let Some(session) = &self.session {
+            let session_guard = session.read();
+
+            // Always include BrowseName, DisplayName and NodeClass, optionally the Value
+            let mut attribute_ids = vec![
+                AttributeId::BrowseName,
+                AttributeId::DisplayName,
+                AttributeId::NodeClass,
+            ];
+            if include_value {
+                attribute_ids.push(AttributeId::Value);
+            }
+
+            let read_values: Vec<ReadValueId> = node_ids
+                .iter()
+                .flat_map(|node_id| {
+                    attribute_ids.iter().map(|attr_id| ReadValueId {
+                        node_id: node_id.clone(),
+                        attribute_id: *attr_id as u32,
+                        index_range: UAString::null(),
+                        data_encoding: QualifiedName::null(),
+                    })
+                })
+                .collect();
+
+            let results = match self.read_batched(
+                &session_guard,
+                &read_values,
+                TimestampsToReturn::Neither,
+            ) {
+                Ok(results) => results,
+                Err(e) => {
+                    log::debug!(
+                        "Failed to read search attributes for {} nodes: {e}",
+                        node_ids.len()
+                    );
+                    Vec::new()
+                }
+            };
+
+            let attributes = (0..node_ids.len())
+                .map(|i| {
+                    let node_results = results
+                        .get(i * attribute_ids.len()..(i + 1) * attribute_ids.len())
+                        .unwrap_or_default();
+                    Self::search_attributes_from_results(node_results)
+                })
+                .collect();
+            Ok(attributes)
+        } else {
+            Err(anyhow::anyhow!("Not connected to OPC UA server"))
+        }
+    }
+
+    /// Read the attributes written by the subtree export of many nodes at once, batched by the
+    /// server's MaxNodesPerRead. Results are in the order of `node_ids`.
+    pub async fn read_nodes_export_attributes(
+        &self,
+        node_ids: &[NodeId],
+    ) -> Result<Vec<OpcUaExportAttributes>> {
+        if let Some(session) = &self.session {
+            let session_guard = session.read();
+
+            let attribute_ids = [
+                AttributeId::BrowseName,
+                AttributeId::Description,
+                AttributeId::DataType,
+                AttributeId::ValueRank,
+                AttributeId::ArrayDimensions,
+                AttributeId::AccessLevel,
+                AttributeId::UserAccessLevel,
+                AttributeId::MinimumSamplingInterval,
+                AttributeId::Historizing,
+                AttributeId::EventNotifier,
+                AttributeId::Executable,
+                AttributeId::IsAbstract,
+                AttributeId::Symmetric,
+                AttributeId::InverseName,
+                AttributeId::Value,
+            ];
+            let read_values: Vec<ReadValueId> = node_ids
+                .iter()
+                .flat_map(|node_id| {
+                    attribute_ids.iter().map(|attr_id| ReadValueId {
+                        node_id: node_id.clone(),
+                        attribute_id: *attr_id as u32,
+                        index_range: UAString::null(),
+                        data_encoding: QualifiedName::null(),
+                    })
+                })
+                .collect();
+
+            let results = self
+                .read_batched(&session_guard, &read_values, TimestampsToReturn::Neither)
+                .map_err(|e| anyhow::anyhow!("Failed to read export attributes: {}", e))?;
+
+            // Objects and Methods answer BadAttributeIdInvalid, which leaves the field unset
+            let attributes = results
+                .chunks(attribute_ids.len())
+                .map(|node_results| {
+                    let good_value = |index: usize| {
+                        node_results
+                            .get(index)
+                            .filter(|result| result.status().is_good())
+                            .and_then(|result| result.value.clone())
+                    };
+                    let boolean = |index: usize| match good_value(index) {
+                        Some(Variant::Boolean(value)) => Some(value),
+                        _ => None,
+                    };
+                    let byte = |index: usize| match good_value(index) {
+                        Some(Variant::Byte(value)) => Some(value),
+                        _ => None,
+                    };
+                    let localized_text = |index: usize| match good_value(index) {
+                        Some(Variant::LocalizedText(text)) => Some(*text),
+                        _ => None,
+                    };
+                    OpcUaExportAttributes {
+                        browse_name: match good_value(0) {
+                            Some(Variant::QualifiedName(name)) => Some(*name),
+                            _ => None,
+                        },
+                        description: localized_text(1),
+                        data_type: match good_value(2) {
+                            Some(Variant::NodeId(id)) => Some(*id),
+                            _ => None,
+                        },
+                        value_rank: match good_value(3) {
+                            Some(Variant::Int32(rank)) => Some(rank),
+                            _ => None,
+                        },
+                        array_dimensions: match good_value(4) {
+                            Some(Variant::Array(array)) => Some(
+                                array
+                                    .values
+                                    .iter()
+                                    .filter_map(|dimension| match dimension {
+                                        Variant::UInt32(length) => Some(*length),
+                                        _ => None,
+                                    })
+                                    .collect(),
+                            ),
+                            _ => None,
+                        },
+                        access_level: byte(5),
+                        user_access_level: byte(6),
+                        minimum_sampling_interval: match good_value(7) {
+                            Some(Variant::Double(interval)) => Some(interval),
+                            _ => None,
+                        },
+                        historizing: boolean(8),
+                        event_notifier: byte(9),
+                        executable: boolean(10),
+                        is_abstract: boolean(11),
+                        symmetric: boolean(12),
+                        inverse_name: localized_text(13),
+                        value: good_value(14),
+                    }
+                })
+                .collect();
+            Ok(attributes)
+        } else {
+            Err(anyhow::anyhow!("Not connected to OPC UA server"))
+        }
+    }
+
+    /// Read the server's NamespaceArray; the index of a URI is its namespace index
+    pub async fn read_namespace_array(&self) -> Result<Vec<String>> {
+        let node_id: NodeId = VariableId::Server_NamespaceArray.into();
+        match self
+            .read_attribute(&node_id, AttributeId::Value)
+            .await?
+            .value
+        {
+            Some(Variant::Array(array)) => Ok(array
+                .values
+                .iter()
+                .map(|uri| match uri {
+                    Variant::String(uri) => uri.value().clone().unwrap_or_default(),
+                    _ => String::new(),
+                })
+                .collect()),
+            _ => Err(anyhow::anyhow!("Server returned no NamespaceArray")),
+        }
+    }
+
+    fn search_attributes_from_results(results: &[DataValue]) -> OpcUaSearchAttributes {
+        let browse_name = match results.first().and_then(|r| r.value.as_ref()) {
+            Some(Variant::QualifiedName(qname)) => qname
+                .name
+                .value()
+                .as_ref()
+                .map(|s| s.as_str())
+                .unwrap_or("(empty)")
+                .to_string(),
+            _ => String::new(),
+        };
+
+        let display_name = match results.get(1).and_then(|r| r.value.as_ref()) {
+            Some(Variant::LocalizedText(ltext)) => ltext
+                .text
+                .value()
+                .as_ref()
+                .map(|s| s.as_str())
+                .unwrap_or("(empty)")
+                .to_string(),
+            _ => String::new(),
+        };
+
+        let node_class = match results.get(2).and_then(|r| r.value.as_ref()) {
+            Some(Variant::Int32(class_value)) => match *class_value {
+                1 => NodeClass::Object,
+                2 => NodeClass::Variable,
+                4 => NodeClass::Method,
+                8 => NodeClass::ObjectType,
+                16 => NodeClass::VariableType,
+                32 => NodeClass::ReferenceType,
+                64 => NodeClass::DataType,
+                128 => NodeClass::View,
+                _ => NodeClass::Unspecified,
+            },
+            _ => NodeClass::Unspecified,
+        };
+
+        // Value attribute, present only if it was requested
+        let value = results
+            .get(3)
+            .and_then(|r| r.value.as_ref())
+            .map(|variant| format!("{variant}"));
+
+        OpcUaSearchAttributes {
+            browse_name,
+            display_name,
+            value,
+            node_class,
+        }
+    }
+
+    pub async fn get_root_node(&self, root: BrowseRoot) -> Result<NodeId> {
+        Ok(root.node_id())
     }
 
     pub fn is_connected(&self) -> bool {
@@ -620,7 +2096,7 @@ impl OpcUaClientManager {
     }
 
     // Helper function to format AccessLevel values into human-readable text
-    fn format_access_level(access_level: u8) -> String {
+    pub fn format_access_level(access_level: u8) -> String {
         let mut permissions = Vec::new();
 
         if access_level & 0x01 != 0 {
@@ -653,7 +2129,7 @@ impl OpcUaClientManager {
     }
 
     // Helper function to format DataType NodeIds into human-readable text
-    fn format_data_type(data_type_id: &NodeId) -> String {
+    pub fn format_data_type(data_type_id: &NodeId) -> String {
         // Common OPC UA data type NodeIds
         match data_type_id.to_string().as_str() {
             "i=1" => "Boolean".to_string(),
@@ -690,16 +2166,311 @@ impl OpcUaClientManager {
         }
     }
 
-    /// Set an existing connection (transfer from ConnectScreen)
+    /// Set an existing connection (transfer from ConnectScreen or the command line). With a
+    /// target, a lost session is re-created from the same endpoint and settings.
     pub fn set_connection(
         &mut self,
         client: Client,
         session: Arc<RwLock<Session>>,
         server_url: String,
+        target: Option<ConnectionTarget>,
     ) {
         self.client = Some(client);
-        self.session = Some(session);
         self.server_url = server_url;
+        self.session_runner = None;
+        self.watch_subscription_id = None;
+        self.event_subscription_id = None;
+        *self.data_type_cache.get_mut() = DataTypeCache::default();
+        self.read_operation_limits(&session);
+
+        let (sender, events) = mpsc::unbounded_channel();
+        let task = tokio::spawn(Self::monitor_session(session.clone(), target, sender));
+        self.session_monitor = Some(SessionMonitor { task, events });
+
+        self.session = Some(session);
         self.connection_status = ConnectionStatus::Connected;
     }
+
+    fn read_operation_limits(&mut self, session: &Arc<RwLock<Session>>) {
+        let session_guard = session.read();
+        self.max_nodes_per_read = Self::read_operation_limit(
+            &session_guard,
+            VariableId::Server_ServerCapabilities_OperationLimits_MaxNodesPerRead,
+        );
+        self.max_nodes_per_browse = Self::read_operation_limit(
+            &session_guard,
+            VariableId::Server_ServerCapabilities_OperationLimits_MaxNodesPerBrowse,
+        );
+    }
+
+    /// Close the session and stop monitoring it
+    pub fn disconnect(&mut self) {
+        self.session_monitor = None;
+        if let Some(runner) = self.session_runner.take() {
+            let _ = runner.send(SessionCommand::Stop);
+        }
+        if let Some(session) = self.session.take() {
+            session.read().disconnect();
+        }
+        self.client = None;
+        self.watch_subscription_id = None;
+        self.event_subscription_id = None;
+        self.connection_status = ConnectionStatus::Disconnected;
+    }
+
+    /// Apply the session monitor's progress. Returns true once a lost session is usable again,
+    /// after which the caller restores its subscriptions and browse state.
+    pub fn process_session_events(&mut self) -> bool {
+        let mut recovered = false;
+        while let Some(event) = self
+            .session_monitor
+            .as_mut()
+            .and_then(|monitor| monitor.events.try_recv().ok())
+        {
+            match event {
+                SessionEvent::Lost => {
+                    log::warn!("Connection to {} lost, reconnecting", self.server_url);
+                    // Calls fail fast while reconnecting instead of waiting for timeouts
+                    self.session = None;
+                    if let Some(runner) = self.session_runner.take() {
+                        let _ = runner.send(SessionCommand::Stop);
+                    }
+                    self.watch_subscription_id = None;
+                    self.event_subscription_id = None;
+                    self.connection_status = ConnectionStatus::Reconnecting(0);
+                }
+                SessionEvent::Reconnecting(attempt) => {
+                    self.connection_status = ConnectionStatus::Reconnecting(attempt);
+                }
+                SessionEvent::Reactivated(session) => {
+                    log::info!("Session to {} re-activated", self.server_url);
+                    self.session = Some(session);
+                    self.connection_status = ConnectionStatus::Connected;
+                    recovered = true;
+                }
+                SessionEvent::Recreated(client, session) => {
+                    log::info!("Session to {} re-created", self.server_url);
+                    self.client = Some(*client);
+                    // The new session may belong to a restarted server with other types
+                    *self.data_type_cache.get_mut() = DataTypeCache::default();
+                    self.read_operation_limits(&session);
+                    self.session = Some(session);
+                    self.connection_status = ConnectionStatus::Connected;
+                    recovered = true;
+                }
+            }
+        }
+        recovered
+    }
+
+    async fn monitor_session(
+        mut session: Arc<RwLock<Session>>,
+        target: Option<ConnectionTarget>,
+        events: mpsc::UnboundedSender<SessionEvent>,
+    ) {
+        let mut failed_keep_alives = 0;
+        loop {
+            tokio::time::sleep(KEEP_ALIVE_INTERVAL).await;
+            match Self::keep_alive(session.clone()).await {
+                KeepAlive::Alive => {
+                    failed_keep_alives = 0;
+                    continue;
+                }
+                // One slow answer on a healthy channel is not worth tearing down subscriptions
+                KeepAlive::Failed if failed_keep_alives + 1 < MAX_FAILED_KEEP_ALIVES => {
+                    failed_keep_alives += 1;
+                    continue;
+                }
+                KeepAlive::Failed | KeepAlive::Disconnected => failed_keep_alives = 0,
+            }
+            if events.send(SessionEvent::Lost).is_err() {
+                return;
+            }
+
+            let mut attempt = 0;
+            let mut delay = RECONNECT_DELAY_MIN;
+            session = loop {
+                attempt += 1;
+                if events.send(SessionEvent::Reconnecting(attempt)).is_err() {
+                    return;
+                }
+                tokio::time::sleep(delay).await;
+
+                log::info!("Reconnect attempt {attempt}");
+                match Self::recover_session(session.clone(), target.as_ref()).await {
+                    Some((Some(client), recreated)) => {
+                        // The old session is unusable; close it without waiting for the server
+                        let old_session = std::mem::replace(&mut session, recreated.clone());
+                        tokio::task::spawn_blocking(move || old_session.read().disconnect());
+                        if events
+                            .send(SessionEvent::Recreated(Box::new(client), recreated.clone()))
+                            .is_err()
+                        {
+                            return;
+                        }
+                        break recreated;
+                    }
+                    Some((None, reactivated)) => {
+                        if events
+                            .send(SessionEvent::Reactivated(reactivated.clone()))
+                            .is_err()
+                        {
+                            return;
+                        }
+                        break reactivated;
+                    }
+                    None => {
+                        delay = (delay * 2).min(RECONNECT_DELAY_MAX);
+                        log::warn!(
+                            "Reconnect attempt {attempt} failed, retrying in {}s",
+                            delay.as_secs()
+                        );
+                    }
+                }
+            };
+        }
+    }
+
+    /// Read the server state. Bad results of the Read service itself, such as
+    /// BadTooManyOperations, show the server still answers and count as alive.
+    async fn keep_alive(session: Arc<RwLock<Session>>) -> KeepAlive {
+        tokio::task::spawn_blocking(move || {
+            let session_guard = session.read();
+            if !session_guard.is_connected() {
+                return KeepAlive::Disconnected;
+            }
+            let read_value_id = ReadValueId {
+                node_id: VariableId::Server_ServerStatus_State.into(),
+                attribute_id: AttributeId::Value as u32,
+                index_range: UAString::null(),
+                data_encoding: QualifiedName::null(),
+            };
+            match session_guard.read(&[read_value_id], TimestampsToReturn::Neither, 0.0) {
+                Ok(_) => KeepAlive::Alive,
+                Err(status) if Self::is_session_failure(status) => {
+                    log::warn!("Session keep-alive failed: {status}");
+                    KeepAlive::Failed
+                }
+                Err(status) => {
+                    log::debug!("Session keep-alive answered with {status}");
+                    KeepAlive::Alive
+                }
+            }
+        })
+        .await
+        .unwrap_or(KeepAlive::Failed)
+    }
+
+    /// Whether a failed call means the channel or session is gone rather than a service error
+    fn is_session_failure(status: StatusCode) -> bool {
+        matches!(
+            status,
+            StatusCode::BadTimeout
+                | StatusCode::BadRequestTimeout
+                | StatusCode::BadCommunicationError
+                | StatusCode::BadConnectionClosed
+                | StatusCode::BadNotConnected
+                | StatusCode::BadServerNotConnected
+                | StatusCode::BadDisconnect
+                | StatusCode::BadSecureChannelClosed
+                | StatusCode::BadSecureChannelIdInvalid
+                | StatusCode::BadSessionIdInvalid
+                | StatusCode::BadSessionClosed
+                | StatusCode::BadSessionNotActivated
+        )
+    }
+
+    /// Re-activate the lost session, or re-create it from the connection target. Returns the
+    /// new client when the session was re-created.
+    async fn recover_session(
+        session: Arc<RwLock<Session>>,
+        target: Option<&ConnectionTarget>,
+    ) -> Option<(Option<Client>, Arc<RwLock<Session>>)> {
+        // On re-activation the client library re-creates subscriptions itself under new ids and
+        // without event filters, so sessions with subscriptions are always re-created instead
+        let has_subscriptions = session
+            .read()
+            .subscription_state()
+            .read()
+            .subscription_ids()
+            .is_some_and(|ids| !ids.is_empty());
+        if !has_subscriptions {
+            let reactivate = session.clone();
+            match tokio::task::spawn_blocking(move || reactivate.write().reconnect_and_activate())
+                .await
+            {
+                Ok(Ok(())) => return Some((None, session)),
+                Ok(Err(status)) => log::debug!("Session re-activation failed: {status}"),
+                Err(e) => log::debug!("Session re-activation failed: {e}"),
+            }
+        }
+
+        let target = target?;
+        let mut endpoint = target.endpoint.clone();
+        // Reverse connected servers are reached through a relay that lives for this attempt
+        let relay = match target
+            .reverse
+            .as_ref()
+            .map(ReverseTarget::relay)
+            .transpose()
+        {
+            Ok(relay) => relay,
+            Err(e) => {
+                log::warn!("Failed to relay to the reverse connected server: {e}");
+                return None;
+            }
+        };
+        if let Some(relay) = &relay {
+            endpoint.endpoint_url = UAString::from(relay.url());
+        }
+        match ConnectionManager::connect_to_endpoint(endpoint, &target.config).await {
+            Ok((client, session)) => Some((Some(client), session)),
+            Err(e) => {
+                log::warn!("Failed to re-create session: {e}");
+                None
+            }
+        }
+    }
+
+    /// Read one of the server's operation limits, treating a missing value as no limit
+    fn read_operation_limit(session: &Session, limit_id: VariableId) -> usize {
+        let read_value_id = ReadValueId {
+            node_id: limit_id.into(),
+            attribute_id: AttributeId::Value as u32,
+            index_range: UAString::null(),
+            data_encoding: QualifiedName::null(),
+        };
+        match session.read(&[read_value_id], TimestampsToReturn::Neither, 0.0) {
+            Ok(results) => match results.first().and_then(|r| r.value.as_ref()) {
+                Some(Variant::UInt32(limit)) => {
+                    log::info!("Server operation limit {limit_id:?} is {limit}");
+                    *limit as usize
+                }
+                _ => 0,
+            },
+            Err(e) => {
+                log::debug!("Failed to read operation limit {limit_id:?}: {e}");
+                0
+            }
+        }
+    }
+
+    /// Read `read_values` in as few requests as the server's MaxNodesPerRead allows.
+    /// Results are returned in the same order as `read_values`.
+    fn read_batched(
+        &self,
+        session: &Session,
+        read_values: &[ReadValueId],
+        timestamps_to_return: TimestampsToReturn,
+    ) -> Result<Vec<DataValue>, StatusCode> {
+        let batch_size = match self.max_nodes_per_read {
+            0 => read_values.len().max(1),
+            limit => limit,
+        };
+        let mut results = Vec::with_capacity(read_values.len());
+        for batch in read_values.chunks(batch_size) {
+            results.extend(session.read(batch, timestamps_to_return, 0.0)?);
+        }
+        Ok(results)
+    }
 }
@@ -229,7 +229,7 @@ impl Button {
             ButtonColor::Default => Color::White,
         }
     }
-    fn create_button_text(&self, base_style: Style) -> Line {
+    fn create_button_text(&self, base_style: Style) -> Line<'_> {
         let mut spans = Vec::new();
 
         // Use the text color from base_style and add bold modifier
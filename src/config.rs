@@ -1,15 +1,44 @@
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
+/// Configuration file read from the working directory, next to the `pki` directory
+pub const CONFIG_FILE: &str = "config.json";
+
+#[allow(dead_code)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct OpcUaConfig {
     pub server_url: String,
     pub security_policy: String,
     pub username: Option<String>,
-    pub password: Option<String>,
     pub application_name: String,
     pub application_uri: String,
     pub session_timeout: u32,
     pub keep_alive_interval: u32,
+    pub profiles: Vec<ConnectionProfile>,
+}
+
+/// Named connection settings. Passwords are never stored; they are asked for when connecting.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConnectionProfile {
+    pub name: String,
+    pub server_url: String,
+    pub use_original_url: bool,
+    /// Security policy name as accepted by `--security-policy`, e.g. "Basic256Sha256"
+    pub security_policy: String,
+    /// Security mode name as accepted by `--security-mode`, e.g. "SignAndEncrypt"
+    pub security_mode: String,
+    pub client_certificate: Option<String>,
+    pub client_private_key: Option<String>,
+    pub auto_trust: bool,
+    pub trusted_store: Option<String>,
+    /// "Anonymous", "UserPassword" or "X509Certificate"
+    pub auth_type: String,
+    pub username: Option<String>,
+    pub user_certificate: Option<String>,
+    pub user_private_key: Option<String>,
 }
 
 impl Default for OpcUaConfig {
@@ -18,11 +47,46 @@ impl Default for OpcUaConfig {
             server_url: "opc.tcp://localhost:4840".to_string(),
             security_policy: "None".to_string(),
             username: None,
-            password: None,
             application_name: "OPC UA Rust Client".to_string(),
             application_uri: "urn:OPC-UA-Rust-Client".to_string(),
             session_timeout: 60000,
             keep_alive_interval: 1000,
+            profiles: Vec::new(),
+        }
+    }
+}
+
+impl OpcUaConfig {
+    /// Load `config.json`, falling back to defaults when the file does not exist
+    pub fn load() -> Result<Self> {
+        let path = Path::new(CONFIG_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
         }
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read {}: {}", CONFIG_FILE, e))?;
+        serde_json::from_str(&contents).map_err(|e| anyhow!("Invalid {}: {}", CONFIG_FILE, e))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let contents = serde_json::to_string_pretty(self)?;
+        std::fs::write(CONFIG_FILE, contents + "\n")
+            .map_err(|e| anyhow!("Failed to write {}: {}", CONFIG_FILE, e))
+    }
+
+    pub fn find_profile(&self, name: &str) -> Option<&ConnectionProfile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// Add a profile, replacing an existing profile with the same name
+    pub fn upsert_profile(&mut self, profile: ConnectionProfile) {
+        match self.profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => self.profiles.push(profile),
+        }
+    }
+
+    pub fn remove_profile(&mut self, name: &str) {
+        self.profiles.retain(|profile| profile.name != name);
     }
 }
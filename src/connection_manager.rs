@@ -1,8 +1,13 @@
+use crate::cert_utils::{
+    CertUtils, CertificateInfo, CertificateLocation, OWN_CERT_FILE, OWN_KEY_FILE, PKI_DIR,
+};
+use crate::reverse_connect::ReverseTarget;
 use anyhow::{anyhow, Result};
 use opcua::client::prelude::*;
 use opcua::crypto::SecurityPolicy;
-use opcua::types::{EndpointDescription, MessageSecurityMode, UAString};
+use opcua::types::{ApplicationDescription, EndpointDescription, MessageSecurityMode, UAString};
 use parking_lot::RwLock;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 /// Unified connection manager for all OPC UA connection scenarios
@@ -20,6 +25,46 @@ pub struct ConnectionConfig {
     pub client_key_path: Option<String>,
     pub identity_token: IdentityToken,
     pub use_original_url: bool,
+    pub trust_once_thumbprint: Option<String>, // Server certificate trusted for this connection only
+}
+
+/// Connecting failed with BadCertificateUntrusted; the opcua stack stored the server's
+/// certificate in `pki/rejected`
+#[derive(Debug, Clone)]
+pub struct UntrustedServerCertificate {
+    pub certificate: CertificateInfo,
+    pub endpoint_url: String,
+    pub server_application_uri: String, // ApplicationUri the endpoint announces
+}
+
+impl UntrustedServerCertificate {
+    /// Whether the certificate's application URI is the one the endpoint announces
+    pub fn application_uri_matches(&self) -> bool {
+        self.certificate.application_uri.as_deref() == Some(self.server_application_uri.as_str())
+    }
+}
+
+impl std::fmt::Display for UntrustedServerCertificate {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Server certificate {} is not trusted (BadCertificateUntrusted); move {} to {}/{} or enable auto-trust",
+            self.certificate.subject,
+            self.certificate.path.display(),
+            PKI_DIR,
+            CertificateLocation::Trusted.dir_name()
+        )
+    }
+}
+
+impl std::error::Error for UntrustedServerCertificate {}
+
+/// Endpoint and settings of an established connection, kept to re-create the session after a loss
+#[derive(Debug, Clone)]
+pub struct ConnectionTarget {
+    pub endpoint: EndpointDescription,
+    pub config: ConnectionConfig,
+    pub reverse: Option<ReverseTarget>, // Set when the server connected with Reverse Connect
 }
 
 impl Default for ConnectionConfig {
@@ -34,6 +79,7 @@ impl Default for ConnectionConfig {
             client_key_path: None,
             identity_token: IdentityToken::Anonymous,
             use_original_url: false,
+            trust_once_thumbprint: None,
         }
     }
 }
@@ -69,12 +115,12 @@ impl ConnectionConfig {
     /// Create configuration for secure OPC UA connections with certificates
     /// Automatically extracts application URI from the certificate
     pub fn secure_connection() -> Self {
-        let cert_path = "./pki/own/OpcPlc.der";
-        let key_path = "./pki/private/OpcPlc.pem";
+        let cert_path = format!("./{PKI_DIR}/{OWN_CERT_FILE}");
+        let key_path = format!("./{PKI_DIR}/{OWN_KEY_FILE}");
 
         // Try to extract application URI from certificate, fallback to default
         let application_uri = ConnectionManager::extract_application_uri_from_certificate(
-            cert_path,
+            &cert_path,
         )
         .unwrap_or_else(|e| {
             log::warn!("Failed to extract application URI from certificate: {e}");
@@ -90,10 +136,11 @@ impl ConnectionConfig {
             security_policy: SecurityPolicy::Basic256Sha256,
             security_mode: MessageSecurityMode::SignAndEncrypt,
             auto_trust: true,
-            client_cert_path: Some(cert_path.to_string()),
-            client_key_path: Some(key_path.to_string()),
+            client_cert_path: Some(cert_path),
+            client_key_path: Some(key_path),
             identity_token: IdentityToken::Anonymous,
             use_original_url: false,
+            trust_once_thumbprint: None,
         }
     }
 
@@ -160,6 +207,13 @@ impl ConnectionConfig {
         self.use_original_url = use_original_url;
         self
     }
+
+    /// Trust the server certificate with this thumbprint for this connection without adding it
+    /// to the trusted store
+    pub fn with_trust_once(mut self, thumbprint: Option<String>) -> Self {
+        self.trust_once_thumbprint = thumbprint;
+        self
+    }
 }
 
 impl ConnectionManager {
@@ -204,6 +258,40 @@ impl ConnectionManager {
         .await?
     }
 
+    /// Ask a Local Discovery Server (or any server) for the servers it knows with FindServers.
+    /// FindServersOnNetwork is not offered by the opcua client stack.
+    pub async fn find_servers(
+        discovery_url: &str,
+        config: &ConnectionConfig,
+    ) -> Result<Vec<ApplicationDescription>> {
+        let url = discovery_url.to_string();
+        let config = config.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<ApplicationDescription>> {
+            let mut client = Self::build_discovery_client(&config)?;
+
+            match client.find_servers(url.as_str()) {
+                Ok(servers) => {
+                    log::info!("Found {} servers on {}", servers.len(), url);
+                    for server in &servers {
+                        log::debug!(
+                            "  Server: {} ({:?}) {:?}",
+                            server.application_name.text.as_ref(),
+                            server.application_type,
+                            server.discovery_urls
+                        );
+                    }
+                    Ok(servers)
+                }
+                Err(e) => {
+                    log::error!("Failed to find servers on {url}: {e}");
+                    Err(anyhow!("Failed to find servers: {}", e))
+                }
+            }
+        })
+        .await?
+    }
+
     /// Connect to an OPC UA server using a discovered endpoint
     pub async fn connect_to_endpoint(
         endpoint: EndpointDescription,
@@ -240,10 +328,24 @@ impl ConnectionManager {
 
             log::debug!("Attempting connection with identity token: {:?}", config.identity_token);
 
+            let temporarily_trusted = config
+                .trust_once_thumbprint
+                .as_deref()
+                .and_then(|thumbprint| Self::trust_temporarily(&endpoint, thumbprint));
+
             // Wrap the connection attempt in a panic-catching mechanism
-            match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                client.connect_to_endpoint(endpoint, config.identity_token)
-            })) {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                client.connect_to_endpoint(endpoint.clone(), config.identity_token)
+            }));
+
+            // The certificate is only checked while the session is created
+            if let Some(path) = temporarily_trusted {
+                if let Err(e) = std::fs::remove_file(&path) {
+                    log::warn!("Failed to remove {}: {e}", path.display());
+                }
+            }
+
+            match result {
                 Ok(Ok(session)) => {
                     log::info!("Successfully established OPC UA connection");
                     Ok((client, session))
@@ -252,6 +354,17 @@ impl ConnectionManager {
                     log::error!("Failed to connect to endpoint: {e}");
                     log::debug!("Connection error details: {e:?}");
 
+                    // A certificate rejected before fails with BadSecurityChecksFailed, so both
+                    // ask the user again while it sits in pki/rejected
+                    if e == StatusCode::BadCertificateUntrusted
+                        || e == StatusCode::BadSecurityChecksFailed
+                    {
+                        if let Some(untrusted) = Self::untrusted_server_certificate(&endpoint) {
+                            log::warn!("{untrusted}");
+                            return Err(untrusted.into());
+                        }
+                    }
+
                     // Provide more specific error analysis
                     let error_msg = e.to_string();
                     if error_msg.contains("BadSecurityChecksFailed") {
@@ -283,11 +396,75 @@ impl ConnectionManager {
             .await?
     }
 
+    /// The endpoint's server certificate if the opcua stack put it into `pki/rejected`
+    fn untrusted_server_certificate(
+        endpoint: &EndpointDescription,
+    ) -> Option<UntrustedServerCertificate> {
+        let der = endpoint.server_certificate.value.as_ref()?;
+        let file_name = CertUtils::store_file_name(der).ok()?;
+        let path = Path::new(PKI_DIR)
+            .join(CertificateLocation::Rejected.dir_name())
+            .join(file_name);
+        if !path.exists() {
+            return None;
+        }
+        Some(UntrustedServerCertificate {
+            certificate: CertUtils::certificate_info(CertificateLocation::Rejected, path),
+            endpoint_url: endpoint.endpoint_url.as_ref().to_string(),
+            server_application_uri: endpoint.server.application_uri.as_ref().to_string(),
+        })
+    }
+
+    /// Put the endpoint's server certificate into `pki/trusted` for one connection attempt if it
+    /// has the thumbprint the user accepted, and return the file to remove afterwards
+    fn trust_temporarily(endpoint: &EndpointDescription, thumbprint: &str) -> Option<PathBuf> {
+        let der = endpoint.server_certificate.value.as_ref()?;
+        let cert = openssl::x509::X509::from_der(der).ok()?;
+        if CertUtils::thumbprint(&cert) != thumbprint {
+            log::warn!("The server certificate changed since it was trusted once; not trusting it");
+            return None;
+        }
+        let file_name = CertUtils::store_file_name(der).ok()?;
+        let pki_dir = Path::new(PKI_DIR);
+        // A rejected certificate fails validation even when it is trusted as well
+        let _ = std::fs::remove_file(
+            pki_dir
+                .join(CertificateLocation::Rejected.dir_name())
+                .join(&file_name),
+        );
+        let trusted_dir = pki_dir.join(CertificateLocation::Trusted.dir_name());
+        let path = trusted_dir.join(&file_name);
+        if path.exists() {
+            return None; // Trusted permanently, nothing to undo
+        }
+        std::fs::create_dir_all(&trusted_dir)
+            .and_then(|_| std::fs::write(&path, der))
+            .map_err(|e| log::error!("Failed to write {}: {e}", path.display()))
+            .ok()?;
+        log::info!("Trusting server certificate {file_name} for this connection");
+        Some(path)
+    }
+
     /// Connect to an OPC UA server by URL (discovers endpoints first)
     pub async fn connect_to_server(
         server_url: &str,
         config: &ConnectionConfig,
-    ) -> Result<(Client, Arc<RwLock<Session>>)> {
+    ) -> Result<(Client, Arc<RwLock<Session>>, ConnectionTarget)> {
+        let endpoint = Self::select_endpoint(server_url, config).await?;
+        let (client, session) = Self::connect_to_endpoint(endpoint.clone(), config).await?;
+        let target = ConnectionTarget {
+            endpoint,
+            config: config.clone(),
+            reverse: None,
+        };
+        Ok((client, session, target))
+    }
+
+    /// Discover the endpoints of a server and pick the one matching the configured security
+    pub async fn select_endpoint(
+        server_url: &str,
+        config: &ConnectionConfig,
+    ) -> Result<EndpointDescription> {
         log::info!("Starting connection process to: {server_url}");
         log::debug!("Using config: {config:?}");
 
@@ -332,8 +509,7 @@ impl ConnectionManager {
             selected_endpoint.security_mode
         );
 
-        // Connect to the selected endpoint
-        Self::connect_to_endpoint(selected_endpoint, config).await
+        Ok(selected_endpoint)
     }
 
     /// Build a configured OPC UA client for regular connections
@@ -347,7 +523,7 @@ impl ConnectionManager {
             .application_name(&config.application_name)
             .application_uri(&config.application_uri) // Use config URI, not hardcoded
             .session_retry_limit(1)
-            .pki_dir("pki")
+            .pki_dir(PKI_DIR)
             .session_retry_interval(1000)
             .verify_server_certs(false); // Disable hostname verification for secure connections
 
@@ -398,9 +574,25 @@ impl ConnectionManager {
                     .private_key_path(format!("private/{key_filename}"))
                     .create_sample_keypair(false);
             } else {
-                // Fallback to sample keypair if no certificates provided
-                log::debug!("No client certificates provided, using sample keypair");
-                client_builder = client_builder.create_sample_keypair(true);
+                // Without certificates the client uses the pair from gen-cert when there is one,
+                // or loads or creates the opcua sample keypair in own/cert.der; the application
+                // URI must match the certificate
+                let own_cert = format!("{PKI_DIR}/{OWN_CERT_FILE}");
+                let own_key = format!("{PKI_DIR}/{OWN_KEY_FILE}");
+                match Self::extract_application_uri_from_certificate(&own_cert) {
+                    Ok(application_uri) if std::path::Path::new(&own_key).exists() => {
+                        log::debug!("No client certificates provided, using {own_cert}");
+                        client_builder = client_builder
+                            .application_uri(application_uri)
+                            .certificate_path(OWN_CERT_FILE)
+                            .private_key_path(OWN_KEY_FILE)
+                            .create_sample_keypair(false);
+                    }
+                    _ => {
+                        log::debug!("No client certificates provided, using sample keypair");
+                        client_builder = client_builder.create_sample_keypair(true);
+                    }
+                }
             }
         } else {
             client_builder = client_builder.trust_server_certs(true);
@@ -526,7 +718,7 @@ impl ConnectionManager {
     }
 
     /// Convert URI string to human-readable policy name
-    fn policy_uri_to_name(uri: &str) -> &str {
+    pub fn policy_uri_to_name(uri: &str) -> &str {
         match uri {
             "http://opcfoundation.org/UA/SecurityPolicy#None" => "None",
             "http://opcfoundation.org/UA/SecurityPolicy#Basic128Rsa15" => "Basic128Rsa15",
@@ -612,71 +804,16 @@ impl ConnectionManager {
     /// Extract application URI from an X.509 certificate
     /// This reads the Subject Alternative Name (SAN) extension to find the application URI
     fn extract_application_uri_from_certificate(cert_path: &str) -> Result<String> {
-        use std::fs;
-
         log::debug!("Extracting application URI from certificate: {cert_path}");
 
-        // Read certificate file
-        let cert_data =
-            fs::read(cert_path).map_err(|e| anyhow!("Cannot read certificate file: {}", e))?;
-
-        if cert_data.is_empty() {
-            return Err(anyhow!("Certificate file is empty"));
-        }
-
-        // Parse certificate with OpenSSL
-        let cert = if cert_data.starts_with(b"-----BEGIN CERTIFICATE-----") {
-            // PEM format
-            openssl::x509::X509::from_pem(&cert_data)
-                .map_err(|e| anyhow!("Failed to parse PEM certificate: {}", e))?
-        } else {
-            // DER format
-            openssl::x509::X509::from_der(&cert_data)
-                .map_err(|e| anyhow!("Failed to parse DER certificate: {}", e))?
-        };
-
-        log::debug!("Certificate parsed successfully, extracting Subject Alternative Name");
-
-        // Get Subject Alternative Name extension
-        let subject_alt_names = cert.subject_alt_names();
-
-        if let Some(san_list) = subject_alt_names {
-            for san in san_list {
-                // Check if this is a URI entry
-                if let Some(uri) = san.uri() {
-                    let uri_str = uri.to_string();
-                    log::debug!("Found URI in SAN: {uri_str}");
-
-                    // OPC UA application URIs typically start with "urn:"
-                    // and often contain "opcua" or similar identifiers
-                    if uri_str.starts_with("urn:") {
-                        log::info!("Found application URI in certificate: {uri_str}");
-                        return Ok(uri_str);
-                    }
-                }
-            }
-        }
-
-        // If no URI found in SAN, try to get it from subject CN as fallback
-        let subject = cert.subject_name();
-        for entry in subject.entries() {
-            let obj = entry.object();
-            if obj.nid() == openssl::nid::Nid::COMMONNAME {
-                if let Ok(data) = entry.data().as_utf8() {
-                    let cn = data.to_string();
-                    log::debug!("Found CN in certificate: {cn}");
-
-                    // If CN looks like a URN, use it
-                    if cn.starts_with("urn:") {
-                        log::info!("Using CN as application URI: {cn}");
-                        return Ok(cn);
-                    }
-                }
-            }
-        }
+        let cert = CertUtils::load_x509(std::path::Path::new(cert_path))?;
+        let application_uri = CertUtils::application_uri(&cert).ok_or_else(|| {
+            anyhow!(
+                "No application URI found in certificate Subject Alternative Name or Common Name"
+            )
+        })?;
 
-        Err(anyhow!(
-            "No application URI found in certificate Subject Alternative Name or Common Name"
-        ))
+        log::info!("Found application URI in certificate: {application_uri}");
+        Ok(application_uri)
     }
 }
@@ -0,0 +1,757 @@
+use crate::client::OpcUaClientManager;
+use crate::value_utils::ValueUtils;
+use anyhow::{anyhow, Result};
+use opcua::client::prelude::*;
+use opcua::types::encoding::read_array;
+use std::collections::HashMap;
+use std::io::Cursor;
+
+// Nesting limit guarding against recursive type definitions
+const MAX_DEPTH: usize = 8;
+
+// Array elements listed as individual fields; the rest are summarized
+const MAX_ARRAY_ELEMENTS: usize = 100;
+
+/// A value decoded for display, with nested fields for structures and arrays
+#[derive(Clone, Debug)]
+pub struct DecodedValue {
+    pub text: String,
+    pub fields: Vec<(String, DecodedValue)>,
+}
+
+impl DecodedValue {
+    fn leaf(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Flatten the nested fields depth-first into (depth, name, text) rows
+    pub fn flatten_fields(&self, depth: usize, rows: &mut Vec<(usize, String, String)>) {
+        for (name, field) in &self.fields {
+            rows.push((depth, name.clone(), field.text.clone()));
+            field.flatten_fields(depth + 1, rows);
+        }
+    }
+}
+
+#[derive(Clone)]
+enum TypeDefinition {
+    Structure(StructureDefinition),
+    Enum(EnumDefinition),
+}
+
+#[derive(Clone)]
+struct DataTypeInfo {
+    name: String,
+    definition: Option<TypeDefinition>,
+}
+
+/// DataType lookups of one session, kept between decoders so each DataType is read once
+#[derive(Default)]
+pub struct DataTypeCache {
+    data_types: HashMap<NodeId, DataTypeInfo>,
+    data_types_by_encoding: HashMap<NodeId, Option<NodeId>>,
+    supertypes: HashMap<NodeId, Option<NodeId>>,
+}
+
+/// Decodes ExtensionObject bodies and enumeration values using the DataTypeDefinition
+/// attribute of the server's DataType nodes. Lookups go through the session's cache.
+pub struct DataTypeDecoder<'a> {
+    session: &'a Session,
+    cache: &'a mut DataTypeCache,
+    decoding_options: opcua::types::DecodingOptions,
+}
+
+impl<'a> DataTypeDecoder<'a> {
+    pub fn new(session: &'a Session, cache: &'a mut DataTypeCache) -> Self {
+        Self {
+            session,
+            cache,
+            decoding_options: opcua::types::DecodingOptions::default(),
+        }
+    }
+
+    /// Decode a value for display. `data_type` is the DataType of the node the value belongs
+    /// to and is used to give enumeration values their symbolic names.
+    pub fn decode_variant(&mut self, value: &Variant, data_type: Option<&NodeId>) -> DecodedValue {
+        self.decode_variant_at(value, data_type, 0)
+    }
+
+    /// Symbolic name of an enumeration value, if `data_type` is an enumeration
+    pub fn enum_name(&mut self, data_type: &NodeId, value: i64) -> Option<String> {
+        if Self::builtin_type(data_type).is_some() {
+            return None;
+        }
+        match self.data_type_info(data_type).definition {
+            Some(TypeDefinition::Enum(definition)) => Self::enum_field_name(&definition, value),
+            _ => None,
+        }
+    }
+
+    /// Built-in type values of `data_type` are encoded as, found through its supertypes.
+    /// `None` for abstract types such as BaseDataType or Number, which take any value.
+    pub fn value_type(&mut self, data_type: &NodeId) -> Option<VariantTypeId> {
+        let mut data_type = data_type.clone();
+        for _ in 0..MAX_DEPTH {
+            if let Some(builtin) = Self::builtin_type(&data_type) {
+                return ValueUtils::resolve_scalar_type(&NodeId::new(0, builtin), None);
+            }
+            data_type = self.supertype(&data_type)?;
+        }
+        None
+    }
+
+    fn decode_variant_at(
+        &mut self,
+        value: &Variant,
+        data_type: Option<&NodeId>,
+        depth: usize,
+    ) -> DecodedValue {
+        match value {
+            Variant::Array(array) => {
+                let mut fields: Vec<(String, DecodedValue)> = array
+                    .values
+                    .iter()
+                    .take(MAX_ARRAY_ELEMENTS)
+                    .enumerate()
+                    .map(|(i, element)| {
+                        (
+                            format!("[{i}]"),
+                            self.decode_variant_at(element, data_type, depth + 1),
+                        )
+                    })
+                    .collect();
+                if array.values.len() > MAX_ARRAY_ELEMENTS {
+                    fields.push((
+                        "...".to_string(),
+                        DecodedValue::leaf(format!(
+                            "{} more elements",
+                            array.values.len() - MAX_ARRAY_ELEMENTS
+                        )),
+                    ));
+                }
+                // Matrices show their shape, e.g. Int32[3x4]
+                let shape = match &array.dimensions {
+                    Some(dimensions) if dimensions.len() > 1 => dimensions
+                        .iter()
+                        .map(|d| d.to_string())
+                        .collect::<Vec<_>>()
+                        .join("x"),
+                    _ => array.values.len().to_string(),
+                };
+                DecodedValue {
+                    text: format!("{:?}[{shape}]", array.value_type),
+                    fields,
+                }
+            }
+            Variant::ExtensionObject(extension_object) => {
+                self.decode_extension_object(extension_object, depth)
+            }
+            Variant::Variant(inner) => self.decode_variant_at(inner, None, depth),
+            Variant::Int32(n) => match data_type.and_then(|dt| self.enum_name(dt, *n as i64)) {
+                Some(name) => DecodedValue::leaf(format!("{name} ({n})")),
+                None => DecodedValue::leaf(n.to_string()),
+            },
+            Variant::DataValue(data_value) => match &data_value.value {
+                Some(inner) => self.decode_variant_at(inner, None, depth),
+                None => DecodedValue::leaf("(null)"),
+            },
+            other => DecodedValue::leaf(ValueUtils::format_for_edit(other)),
+        }
+    }
+
+    fn decode_extension_object(
+        &mut self,
+        extension_object: &ExtensionObject,
+        depth: usize,
+    ) -> DecodedValue {
+        if let Some(described) = self.describe_definition(extension_object) {
+            return described;
+        }
+
+        let bytes = match &extension_object.body {
+            ExtensionObjectEncoding::None => return DecodedValue::leaf("(null)"),
+            ExtensionObjectEncoding::XmlElement(xml) => {
+                return DecodedValue::leaf(xml.value().clone().unwrap_or_default())
+            }
+            ExtensionObjectEncoding::ByteString(body) => body.value.clone().unwrap_or_default(),
+        };
+
+        let definition = self
+            .data_type_for_encoding(&extension_object.node_id)
+            .map(|data_type| self.data_type_info(&data_type));
+        match definition {
+            Some(DataTypeInfo {
+                name,
+                definition: Some(TypeDefinition::Structure(definition)),
+            }) => {
+                let mut stream = Cursor::new(bytes);
+                self.decode_structure(&mut stream, &definition, &name, depth)
+                    .unwrap_or_else(|e| DecodedValue::leaf(format!("{name}: {e}")))
+            }
+            _ => DecodedValue::leaf(format!(
+                "ExtensionObject {} ({} bytes)",
+                extension_object.node_id,
+                bytes.len()
+            )),
+        }
+    }
+
+    fn decode_structure(
+        &mut self,
+        stream: &mut Cursor<Vec<u8>>,
+        definition: &StructureDefinition,
+        type_name: &str,
+        depth: usize,
+    ) -> Result<DecodedValue> {
+        if depth > MAX_DEPTH {
+            return Err(anyhow!("structure nested too deeply"));
+        }
+        let fields = definition.fields.clone().unwrap_or_default();
+        let mut decoded = Vec::new();
+
+        match definition.structure_type {
+            StructureType::Structure => {
+                for field in &fields {
+                    decoded.push((
+                        field.name.to_string(),
+                        self.decode_field(stream, field, depth)?,
+                    ));
+                }
+            }
+            StructureType::StructureWithOptionalFields => {
+                // One bit per optional field, in field order
+                let encoding_mask = u32::decode(stream, &self.decoding_options)?;
+                let mut optional_index = 0;
+                for field in &fields {
+                    if field.is_optional {
+                        let present = encoding_mask & (1 << optional_index) != 0;
+                        optional_index += 1;
+                        if !present {
+                            continue;
+                        }
+                    }
+                    decoded.push((
+                        field.name.to_string(),
+                        self.decode_field(stream, field, depth)?,
+                    ));
+                }
+            }
+            StructureType::Union => {
+                // The switch field selects the one encoded field, 0 means null
+                let switch_field = u32::decode(stream, &self.decoding_options)? as usize;
+                if switch_field > 0 {
+                    let field = fields
+                        .get(switch_field - 1)
+                        .ok_or_else(|| anyhow!("invalid union switch field {}", switch_field))?;
+                    decoded.push((
+                        field.name.to_string(),
+                        self.decode_field(stream, field, depth)?,
+                    ));
+                }
+            }
+        }
+
+        Ok(DecodedValue {
+            text: format!("{type_name} ({} fields)", decoded.len()),
+            fields: decoded,
+        })
+    }
+
+    fn decode_field(
+        &mut self,
+        stream: &mut Cursor<Vec<u8>>,
+        field: &StructureField,
+        depth: usize,
+    ) -> Result<DecodedValue> {
+        if field.value_rank < 0 {
+            return self.decode_scalar(stream, &field.data_type, depth);
+        }
+
+        // Multi-dimensional fields are prefixed by their dimensions instead of a length
+        let length = if field.value_rank > 1 {
+            match read_array::<_, i32>(stream, &self.decoding_options)? {
+                Some(dimensions) => dimensions.iter().map(|d| (*d).max(0)).product::<i32>(),
+                None => -1,
+            }
+        } else {
+            i32::decode(stream, &self.decoding_options)?
+        };
+        if length < 0 {
+            return Ok(DecodedValue::leaf("(null)"));
+        }
+
+        // Every element has to be decoded to advance the stream, even the ones not listed
+        let mut elements = Vec::new();
+        for i in 0..length as usize {
+            let element = self.decode_scalar(stream, &field.data_type, depth)?;
+            if i < MAX_ARRAY_ELEMENTS {
+                elements.push((format!("[{i}]"), element));
+            }
+        }
+        if length as usize > MAX_ARRAY_ELEMENTS {
+            elements.push((
+                "...".to_string(),
+                DecodedValue::leaf(format!(
+                    "{} more elements",
+                    length as usize - MAX_ARRAY_ELEMENTS
+                )),
+            ));
+        }
+
+        Ok(DecodedValue {
+            text: format!(
+                "{}[{length}]",
+                OpcUaClientManager::format_data_type(&field.data_type)
+            ),
+            fields: elements,
+        })
+    }
+
+    fn decode_scalar(
+        &mut self,
+        stream: &mut Cursor<Vec<u8>>,
+        data_type: &NodeId,
+        depth: usize,
+    ) -> Result<DecodedValue> {
+        if depth > MAX_DEPTH {
+            return Err(anyhow!("structure nested too deeply"));
+        }
+        if let Some(builtin) = Self::builtin_type(data_type) {
+            let value = self.decode_builtin(stream, builtin)?;
+            return Ok(self.decode_variant_at(&value, None, depth + 1));
+        }
+
+        let info = self.data_type_info(data_type);
+        match info.definition {
+            Some(TypeDefinition::Structure(definition)) => {
+                self.decode_structure(stream, &definition, &info.name, depth + 1)
+            }
+            Some(TypeDefinition::Enum(definition)) => {
+                let value = i32::decode(stream, &self.decoding_options)?;
+                Ok(DecodedValue::leaf(
+                    match Self::enum_field_name(&definition, value as i64) {
+                        Some(name) => format!("{name} ({value})"),
+                        None => value.to_string(),
+                    },
+                ))
+            }
+            // Types without a definition are encoded like their nearest defined supertype
+            None => match self.supertype(data_type) {
+                Some(supertype) => self.decode_scalar(stream, &supertype, depth + 1),
+                None => Err(anyhow!("no definition for DataType {}", data_type)),
+            },
+        }
+    }
+
+    fn decode_builtin(&self, stream: &mut Cursor<Vec<u8>>, builtin: u32) -> Result<Variant> {
+        let options = &self.decoding_options;
+        Ok(match builtin {
+            1 => Variant::from(bool::decode(stream, options)?),
+            2 => Variant::from(i8::decode(stream, options)?),
+            3 => Variant::from(u8::decode(stream, options)?),
+            4 => Variant::from(i16::decode(stream, options)?),
+            5 => Variant::from(u16::decode(stream, options)?),
+            6 | 29 => Variant::from(i32::decode(stream, options)?),
+            7 => Variant::from(u32::decode(stream, options)?),
+            8 => Variant::from(i64::decode(stream, options)?),
+            9 => Variant::from(u64::decode(stream, options)?),
+            10 => Variant::from(f32::decode(stream, options)?),
+            11 => Variant::from(f64::decode(stream, options)?),
+            12 => Variant::from(UAString::decode(stream, options)?),
+            13 => Variant::from(DateTime::decode(stream, options)?),
+            14 => Variant::from(Guid::decode(stream, options)?),
+            15 => Variant::from(ByteString::decode(stream, options)?),
+            16 => Variant::XmlElement(UAString::decode(stream, options)?),
+            17 => Variant::from(NodeId::decode(stream, options)?),
+            18 => Variant::from(ExpandedNodeId::decode(stream, options)?),
+            19 => Variant::from(StatusCode::decode(stream, options)?),
+            20 => Variant::from(QualifiedName::decode(stream, options)?),
+            21 => Variant::from(LocalizedText::decode(stream, options)?),
+            22 => Variant::from(ExtensionObject::decode(stream, options)?),
+            23 => Variant::from(DataValue::decode(stream, options)?),
+            25 => Variant::from(DiagnosticInfo::decode(stream, options)?),
+            // BaseDataType, Number, Integer and UInteger are encoded as Variants
+            _ => Variant::Variant(Box::new(Variant::decode(stream, options)?)),
+        })
+    }
+
+    /// Built-in type id that a DataType is encoded as, for built-in types and their
+    /// well-known simple subtypes
+    fn builtin_type(data_type: &NodeId) -> Option<u32> {
+        match (&data_type.identifier, data_type.namespace) {
+            (Identifier::Numeric(id), 0) => match *id {
+                1..=29 => Some(*id),
+                290 => Some(11),           // Duration
+                294 => Some(13),           // UtcTime
+                291 | 295 => Some(12),     // NumericRange, LocaleId
+                288 | 289 => Some(7),      // IntegerId, Counter
+                311 => Some(15),           // ApplicationInstanceCertificate
+                12877..=12881 => Some(12), // Normalized/Decimal/Duration/Time/Date strings
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn enum_field_name(definition: &EnumDefinition, value: i64) -> Option<String> {
+        definition
+            .fields
+            .iter()
+            .flatten()
+            .find(|field| field.value == value)
+            .map(|field| {
+                if field.name.is_null() || field.name.is_empty() {
+                    field.display_name.text.to_string()
+                } else {
+                    field.name.to_string()
+                }
+            })
+    }
+
+    /// Show StructureDefinition and EnumDefinition values (the DataTypeDefinition attribute)
+    /// as field lists
+    fn describe_definition(&self, extension_object: &ExtensionObject) -> Option<DecodedValue> {
+        match extension_object.node_id.as_object_id().ok()? {
+            ObjectId::StructureDefinition_Encoding_DefaultBinary => {
+                let definition = extension_object
+                    .decode_inner::<StructureDefinition>(&self.decoding_options)
+                    .ok()?;
+                let fields: Vec<(String, DecodedValue)> = definition
+                    .fields
+                    .iter()
+                    .flatten()
+                    .map(|field| {
+                        let array_suffix = if field.value_rank >= 0 { "[]" } else { "" };
+                        let optional = if field.is_optional { " (optional)" } else { "" };
+                        (
+                            field.name.to_string(),
+                            DecodedValue::leaf(format!(
+                                "{}{array_suffix}{optional}",
+                                OpcUaClientManager::format_data_type(&field.data_type)
+                            )),
+                        )
+                    })
+                    .collect();
+                Some(DecodedValue {
+                    text: format!("{:?} ({} fields)", definition.structure_type, fields.len()),
+                    fields,
+                })
+            }
+            ObjectId::EnumDefinition_Encoding_DefaultBinary => {
+                let definition = extension_object
+                    .decode_inner::<EnumDefinition>(&self.decoding_options)
+                    .ok()?;
+                let fields: Vec<(String, DecodedValue)> = definition
+                    .fields
+                    .iter()
+                    .flatten()
+                    .map(|field| {
+                        (
+                            field.name.to_string(),
+                            DecodedValue::leaf(field.value.to_string()),
+                        )
+                    })
+                    .collect();
+                Some(DecodedValue {
+                    text: format!("Enumeration ({} values)", fields.len()),
+                    fields,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn data_type_info(&mut self, data_type: &NodeId) -> DataTypeInfo {
+        if let Some(info) = self.cache.data_types.get(data_type) {
+            return info.clone();
+        }
+
+        let read_values: Vec<ReadValueId> =
+            [AttributeId::DataTypeDefinition, AttributeId::BrowseName]
+                .iter()
+                .map(|attr_id| ReadValueId {
+                    node_id: data_type.clone(),
+                    attribute_id: *attr_id as u32,
+                    index_range: UAString::null(),
+                    data_encoding: QualifiedName::null(),
+                })
+                .collect();
+
+        let results = self
+            .session
+            .read(&read_values, TimestampsToReturn::Neither, 0.0)
+            .map_err(|e| log::warn!("Failed to read DataTypeDefinition of {data_type}: {e}"))
+            .unwrap_or_default();
+
+        let definition = match results.first().and_then(|result| result.value.as_ref()) {
+            Some(Variant::ExtensionObject(extension_object)) => {
+                match extension_object.node_id.as_object_id() {
+                    Ok(ObjectId::StructureDefinition_Encoding_DefaultBinary) => extension_object
+                        .decode_inner::<StructureDefinition>(&self.decoding_options)
+                        .ok()
+                        .map(TypeDefinition::Structure),
+                    Ok(ObjectId::EnumDefinition_Encoding_DefaultBinary) => extension_object
+                        .decode_inner::<EnumDefinition>(&self.decoding_options)
+                        .ok()
+                        .map(TypeDefinition::Enum),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        let name = match results.get(1).and_then(|result| result.value.as_ref()) {
+            Some(Variant::QualifiedName(browse_name)) => browse_name.name.to_string(),
+            _ => data_type.to_string(),
+        };
+
+        let info = DataTypeInfo { name, definition };
+        self.cache
+            .data_types
+            .insert(data_type.clone(), info.clone());
+        info
+    }
+
+    fn data_type_for_encoding(&mut self, encoding_id: &NodeId) -> Option<NodeId> {
+        if let Some(data_type) = self.cache.data_types_by_encoding.get(encoding_id) {
+            return data_type.clone();
+        }
+        let data_type = self.browse_single(encoding_id, ReferenceTypeId::HasEncoding);
+        self.cache
+            .data_types_by_encoding
+            .insert(encoding_id.clone(), data_type.clone());
+        data_type
+    }
+
+    fn supertype(&mut self, data_type: &NodeId) -> Option<NodeId> {
+        if let Some(supertype) = self.cache.supertypes.get(data_type) {
+            return supertype.clone();
+        }
+        let supertype = self.browse_single(data_type, ReferenceTypeId::HasSubtype);
+        self.cache
+            .supertypes
+            .insert(data_type.clone(), supertype.clone());
+        supertype
+    }
+
+    /// Follow an inverse reference to the single DataType node on the other end
+    fn browse_single(&self, node_id: &NodeId, reference_type: ReferenceTypeId) -> Option<NodeId> {
+        let browse_description = BrowseDescription {
+            node_id: node_id.clone(),
+            browse_direction: BrowseDirection::Inverse,
+            reference_type_id: reference_type.into(),
+            include_subtypes: false,
+            node_class_mask: NodeClassMask::DATA_TYPE.bits(),
+            result_mask: 0x3F,
+        };
+        self.session
+            .browse(&[browse_description])
+            .map_err(|e| log::warn!("Failed to browse {reference_type:?} of {node_id}: {e}"))
+            .ok()
+            .flatten()
+            .and_then(|results| results.into_iter().next())
+            .and_then(|result| result.references)
+            .and_then(|references| references.into_iter().next())
+            .map(|reference| reference.node_id.node_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::RwLock;
+    use std::sync::Arc;
+
+    /// A session that is never connected; the tests only decode types the cache already knows
+    fn offline_session() -> Arc<RwLock<Session>> {
+        let pki_dir = std::env::temp_dir().join("opcua-client-decoder-test");
+        let mut client = ClientBuilder::new()
+            .application_name("decoder test")
+            .application_uri("urn:decoder-test")
+            .pki_dir(pki_dir)
+            .create_sample_keypair(false)
+            .session_retry_limit(0)
+            .client()
+            .unwrap();
+        let endpoint: EndpointDescription = (
+            "opc.tcp://127.0.0.1:4840/",
+            SecurityPolicy::None.to_str(),
+            MessageSecurityMode::None,
+            UserTokenPolicy::anonymous(),
+        )
+            .into();
+        client.new_session_from_info(endpoint).unwrap()
+    }
+
+    fn field(name: &str, data_type: DataTypeId, value_rank: i32, optional: bool) -> StructureField {
+        StructureField {
+            name: UAString::from(name),
+            description: LocalizedText::null(),
+            data_type: data_type.into(),
+            value_rank,
+            array_dimensions: None,
+            max_string_length: 0,
+            is_optional: optional,
+        }
+    }
+
+    fn definition(
+        structure_type: StructureType,
+        fields: Vec<StructureField>,
+    ) -> StructureDefinition {
+        StructureDefinition {
+            default_encoding_id: NodeId::null(),
+            base_data_type: DataTypeId::Structure.into(),
+            structure_type,
+            fields: Some(fields),
+        }
+    }
+
+    fn decode(
+        cache: &mut DataTypeCache,
+        definition: &StructureDefinition,
+        bytes: Vec<u8>,
+    ) -> Result<DecodedValue> {
+        let session = offline_session();
+        let session_guard = session.read();
+        let mut decoder = DataTypeDecoder::new(&session_guard, cache);
+        decoder.decode_structure(&mut Cursor::new(bytes), definition, "Test", 0)
+    }
+
+    fn texts(value: &DecodedValue) -> Vec<(&str, &str)> {
+        value
+            .fields
+            .iter()
+            .map(|(name, field)| (name.as_str(), field.text.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn structure_fields_are_decoded_in_order() {
+        let definition = definition(
+            StructureType::Structure,
+            vec![
+                field("Speed", DataTypeId::Int32, -1, false),
+                field("Name", DataTypeId::String, -1, false),
+                field("Setpoints", DataTypeId::Double, 1, false),
+            ],
+        );
+        let bytes = [
+            &1500i32.to_le_bytes()[..],
+            &4i32.to_le_bytes(),
+            b"Pump",
+            &2i32.to_le_bytes(),
+            &1.5f64.to_le_bytes(),
+            &2.5f64.to_le_bytes(),
+        ]
+        .concat();
+
+        let decoded = decode(&mut DataTypeCache::default(), &definition, bytes).unwrap();
+        assert_eq!(decoded.text, "Test (3 fields)");
+        assert_eq!(
+            texts(&decoded),
+            vec![
+                ("Speed", "1500"),
+                ("Name", "Pump"),
+                ("Setpoints", "Double[2]")
+            ]
+        );
+        assert_eq!(
+            texts(&decoded.fields[2].1),
+            vec![("[0]", "1.5"), ("[1]", "2.5")]
+        );
+    }
+
+    #[test]
+    fn optional_fields_follow_the_encoding_mask() {
+        let definition = definition(
+            StructureType::StructureWithOptionalFields,
+            vec![
+                field("Id", DataTypeId::UInt16, -1, false),
+                field("Low", DataTypeId::Int32, -1, true),
+                field("High", DataTypeId::Int32, -1, true),
+            ],
+        );
+        // Only the second optional field (High) is present
+        let bytes = [
+            &0b10u32.to_le_bytes()[..],
+            &7u16.to_le_bytes(),
+            &90i32.to_le_bytes(),
+        ]
+        .concat();
+
+        let decoded = decode(&mut DataTypeCache::default(), &definition, bytes).unwrap();
+        assert_eq!(texts(&decoded), vec![("Id", "7"), ("High", "90")]);
+    }
+
+    #[test]
+    fn union_decodes_the_selected_field() {
+        let definition = definition(
+            StructureType::Union,
+            vec![
+                field("Count", DataTypeId::Int32, -1, false),
+                field("Ratio", DataTypeId::Double, -1, false),
+            ],
+        );
+        let bytes = [&2u32.to_le_bytes()[..], &0.25f64.to_le_bytes()].concat();
+        let decoded = decode(&mut DataTypeCache::default(), &definition, bytes).unwrap();
+        assert_eq!(texts(&decoded), vec![("Ratio", "0.25")]);
+
+        let null = decode(
+            &mut DataTypeCache::default(),
+            &definition,
+            0u32.to_le_bytes().to_vec(),
+        )
+        .unwrap();
+        assert!(null.fields.is_empty());
+
+        let invalid = decode(
+            &mut DataTypeCache::default(),
+            &definition,
+            3u32.to_le_bytes().to_vec(),
+        );
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn nested_structures_use_cached_definitions() {
+        let inner_type = NodeId::new(1, 3001);
+        let mut cache = DataTypeCache::default();
+        cache.data_types.insert(
+            inner_type.clone(),
+            DataTypeInfo {
+                name: "Range".to_string(),
+                definition: Some(TypeDefinition::Structure(definition(
+                    StructureType::Structure,
+                    vec![
+                        field("Low", DataTypeId::Float, -1, false),
+                        field("High", DataTypeId::Float, -1, false),
+                    ],
+                ))),
+            },
+        );
+        let mut limits = field("Limits", DataTypeId::Structure, -1, false);
+        limits.data_type = inner_type;
+        let definition = definition(StructureType::Structure, vec![limits]);
+        let bytes = [0.5f32.to_le_bytes(), 9.5f32.to_le_bytes()].concat();
+
+        let decoded = decode(&mut cache, &definition, bytes).unwrap();
+        assert_eq!(texts(&decoded), vec![("Limits", "Range (2 fields)")]);
+        assert_eq!(
+            texts(&decoded.fields[0].1),
+            vec![("Low", "0.5"), ("High", "9.5")]
+        );
+    }
+
+    #[test]
+    fn truncated_body_is_an_error() {
+        let definition = definition(
+            StructureType::Structure,
+            vec![field("Speed", DataTypeId::Int64, -1, false)],
+        );
+        let result = decode(&mut DataTypeCache::default(), &definition, vec![1, 2, 3]);
+        assert!(result.is_err());
+    }
+}
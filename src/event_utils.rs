@@ -0,0 +1,278 @@
+use crate::value_utils::ValueUtils;
+use anyhow::{anyhow, Result};
+use opcua::types::{
+    AttributeId, ContentFilter, ContentFilterBuilder, EventFilter, ObjectTypeId, Operand,
+    SimpleAttributeOperand, UAString, Variant,
+};
+
+/// Fields selected by default when subscribing to events
+pub const DEFAULT_EVENT_SELECT: &str = "EventType, SourceName, Severity, Message, Time";
+
+/// Utility functions for building event filters from user input
+pub struct EventUtils;
+
+impl EventUtils {
+    /// Split a comma separated select clause into BaseEventType browse paths
+    pub fn parse_select_clause(input: &str) -> Result<Vec<String>> {
+        let fields: Vec<String> = input
+            .split(',')
+            .map(|field| field.trim().to_string())
+            .filter(|field| !field.is_empty())
+            .collect();
+        if fields.is_empty() {
+            return Err(anyhow!("Select at least one event field"));
+        }
+        Ok(fields)
+    }
+
+    /// Build an EventFilter selecting `fields` followed by the EventId and ConditionId,
+    /// which are needed to call Acknowledge/Confirm/AddComment on conditions.
+    pub fn build_event_filter(fields: &[String], where_clause: &str) -> Result<EventFilter> {
+        let mut select_clauses: Vec<SimpleAttributeOperand> = fields
+            .iter()
+            .map(|field| {
+                SimpleAttributeOperand::new(
+                    ObjectTypeId::BaseEventType,
+                    field,
+                    AttributeId::Value,
+                    UAString::null(),
+                )
+            })
+            .collect();
+        select_clauses.push(SimpleAttributeOperand::new(
+            ObjectTypeId::BaseEventType,
+            "EventId",
+            AttributeId::Value,
+            UAString::null(),
+        ));
+        // The ConditionId is the NodeId attribute of the ConditionType itself (empty browse path)
+        select_clauses.push(SimpleAttributeOperand {
+            type_definition_id: ObjectTypeId::ConditionType.into(),
+            browse_path: None,
+            attribute_id: AttributeId::NodeId as u32,
+            index_range: UAString::null(),
+        });
+
+        Ok(EventFilter {
+            select_clauses: Some(select_clauses),
+            where_clause: Self::parse_where_clause(where_clause)?,
+        })
+    }
+
+    /// Parse a where clause of comparisons joined by `and`, e.g. `Severity >= 500 and SourceName == 'Pump1'`.
+    /// Supported operators are ==, >, >=, <, <= and like.
+    pub fn parse_where_clause(input: &str) -> Result<ContentFilter> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(ContentFilter { elements: None });
+        }
+
+        let conditions = Self::split_conditions(input);
+        let count = conditions.len();
+
+        // And elements come first (0..count-1), each joining one comparison with the rest;
+        // comparison i is stored at index count - 1 + i.
+        let mut builder = ContentFilterBuilder::new();
+        for i in 0..count.saturating_sub(1) {
+            let comparison = Operand::element((count - 1 + i) as u32);
+            let rest = if i + 1 < count - 1 {
+                Operand::element((i + 1) as u32)
+            } else {
+                Operand::element((2 * count - 2) as u32)
+            };
+            builder = builder.and(comparison, rest);
+        }
+        for condition in conditions {
+            builder = Self::add_comparison(builder, condition)?;
+        }
+        Ok(builder.build())
+    }
+
+    /// Format an event field for display
+    pub fn format_event_field(value: &Variant) -> String {
+        match value {
+            Variant::DateTime(time) => time.as_chrono().format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            Variant::LocalizedText(text) => text.text.value().clone().unwrap_or_default(),
+            Variant::String(text) => text.value().clone().unwrap_or_default(),
+            Variant::Empty => String::new(),
+            other => other.to_string(),
+        }
+    }
+
+    /// Split on " and " outside quoted literals, so `Message == 'up and down'` stays whole
+    fn split_conditions(input: &str) -> Vec<&str> {
+        const SEPARATOR: &str = " and ";
+
+        let lower = input.to_ascii_lowercase();
+        let mut conditions = Vec::new();
+        let mut start = 0;
+        let mut quote = None;
+        for (index, c) in input.char_indices() {
+            match quote {
+                Some(open) if c == open => quote = None,
+                Some(_) => {}
+                None if c == '\'' || c == '"' => quote = Some(c),
+                None if index >= start && lower[index..].starts_with(SEPARATOR) => {
+                    conditions.push(input[start..index].trim());
+                    start = index + SEPARATOR.len();
+                }
+                None => {}
+            }
+        }
+        conditions.push(input[start..].trim());
+        conditions
+    }
+
+    fn add_comparison(
+        builder: ContentFilterBuilder,
+        condition: &str,
+    ) -> Result<ContentFilterBuilder> {
+        // Longer operators are listed first so ">=" wins over ">" at the same position
+        const OPERATORS: [&str; 7] = [">=", "<=", "==", " like ", ">", "<", "="];
+
+        let lower = condition.to_ascii_lowercase();
+        let (position, operator) = OPERATORS
+            .iter()
+            .filter_map(|operator| lower.find(operator).map(|position| (position, *operator)))
+            .min_by_key(|(position, _)| *position)
+            .ok_or_else(|| anyhow!("No comparison operator in '{}'", condition))?;
+
+        let field = condition[..position].trim();
+        let literal = condition[position + operator.len()..].trim();
+        if field.is_empty() || literal.is_empty() {
+            return Err(anyhow!("Incomplete condition '{}'", condition));
+        }
+
+        let field = Operand::simple_attribute(
+            ObjectTypeId::BaseEventType,
+            field,
+            AttributeId::Value,
+            UAString::null(),
+        );
+        let literal = Operand::literal(ValueUtils::infer_scalar(literal));
+
+        Ok(match operator {
+            ">=" => builder.gte(field, literal),
+            "<=" => builder.lte(field, literal),
+            ">" => builder.gt(field, literal),
+            "<" => builder.lt(field, literal),
+            " like " => builder.like(field, literal),
+            _ => builder.eq(field, literal),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    /// One line per filter element, e.g. `And(#1, #2)` or `Equals(SourceName, String("Pump"))`
+    fn describe(filter: &ContentFilter) -> Vec<String> {
+        filter
+            .elements
+            .iter()
+            .flatten()
+            .map(|element| {
+                let operands: Vec<String> = element
+                    .filter_operands
+                    .iter()
+                    .flatten()
+                    .map(|operand| match Operand::try_from(operand) {
+                        Ok(Operand::ElementOperand(element)) => format!("#{}", element.index),
+                        Ok(Operand::SimpleAttributeOperand(attribute)) => attribute
+                            .browse_path
+                            .iter()
+                            .flatten()
+                            .map(|name| name.name.value().clone().unwrap_or_default())
+                            .collect::<Vec<_>>()
+                            .join("/"),
+                        Ok(Operand::LiteralOperand(literal)) => format!("{:?}", literal.value),
+                        _ => "?".to_string(),
+                    })
+                    .collect();
+                format!("{:?}({})", element.filter_operator, operands.join(", "))
+            })
+            .collect()
+    }
+
+    fn where_clause(input: &str) -> Vec<String> {
+        describe(&EventUtils::parse_where_clause(input).unwrap())
+    }
+
+    #[test]
+    fn empty_where_clause_has_no_elements() {
+        assert!(EventUtils::parse_where_clause("  ")
+            .unwrap()
+            .elements
+            .is_none());
+    }
+
+    #[test]
+    fn single_condition_is_one_comparison() {
+        assert_eq!(
+            where_clause("Severity > 500"),
+            vec!["GreaterThan(Severity, Int32(500))"]
+        );
+    }
+
+    #[test]
+    fn two_conditions_are_joined_by_one_and() {
+        assert_eq!(
+            where_clause("Severity >= 500 AND SourceName == 'Pump'"),
+            vec![
+                "And(#1, #2)",
+                "GreaterThanOrEqual(Severity, Int32(500))",
+                r#"Equals(SourceName, String(UAString { value: Some("Pump") }))"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn three_conditions_chain_the_and_elements() {
+        assert_eq!(
+            where_clause("Severity < 10 and Severity <= 20 and Message like 'Pump%'"),
+            vec![
+                "And(#2, #1)",
+                "And(#3, #4)",
+                "LessThan(Severity, Int32(10))",
+                "LessThanOrEqual(Severity, Int32(20))",
+                r#"Like(Message, String(UAString { value: Some("Pump%") }))"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn and_inside_quotes_does_not_split() {
+        assert_eq!(
+            EventUtils::split_conditions(r#"Message == 'up and down' and Severity > 1"#),
+            vec!["Message == 'up and down'", "Severity > 1"]
+        );
+        assert_eq!(
+            EventUtils::split_conditions(r#"Message == "left AND right""#),
+            vec![r#"Message == "left AND right""#]
+        );
+    }
+
+    #[test]
+    fn longer_operators_win_over_their_prefix() {
+        assert_eq!(
+            where_clause("Severity>=500"),
+            vec!["GreaterThanOrEqual(Severity, Int32(500))"]
+        );
+        assert_eq!(
+            where_clause("Severity>500"),
+            vec!["GreaterThan(Severity, Int32(500))"]
+        );
+        assert_eq!(
+            where_clause("Severity<=500"),
+            vec!["LessThanOrEqual(Severity, Int32(500))"]
+        );
+    }
+
+    #[test]
+    fn conditions_without_operator_or_operands_are_rejected() {
+        assert!(EventUtils::parse_where_clause("Severity 500").is_err());
+        assert!(EventUtils::parse_where_clause("Severity >=").is_err());
+    }
+}
@@ -0,0 +1,96 @@
+use anyhow::Result;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::iter::Peekable;
+use std::path::{Path, PathBuf};
+
+/// Utility functions for writing exported data to disk
+pub struct ExportUtils;
+
+impl ExportUtils {
+    /// Quote a CSV field when it contains a separator, quote or line break
+    pub fn csv_field(value: &str) -> String {
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+
+    /// Write a header and rows as a CSV file
+    pub fn write_csv(path: &Path, header: &[&str], rows: &[Vec<String>]) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        let header_line: Vec<String> = header.iter().map(|h| Self::csv_field(h)).collect();
+        writeln!(writer, "{}", header_line.join(","))?;
+
+        for row in rows {
+            let line: Vec<String> = row.iter().map(|field| Self::csv_field(field)).collect();
+            writeln!(writer, "{}", line.join(","))?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Write a value as a pretty-printed JSON file
+    pub fn write_json(path: &Path, value: &serde_json::Value) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut writer, value)?;
+        writeln!(writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Turn depth-first `(depth, object)` rows back into a tree of JSON objects, each with its
+    /// descendants under "children"
+    pub fn nest_json(
+        rows: impl IntoIterator<Item = (usize, serde_json::Value)>,
+    ) -> Vec<serde_json::Value> {
+        Self::nest_level(&mut rows.into_iter().peekable(), 0)
+    }
+
+    fn nest_level<I: Iterator<Item = (usize, serde_json::Value)>>(
+        rows: &mut Peekable<I>,
+        depth: usize,
+    ) -> Vec<serde_json::Value> {
+        let mut nodes = Vec::new();
+        while let Some((_, mut node)) = rows.next_if(|(row_depth, _)| *row_depth >= depth) {
+            node["children"] = serde_json::Value::Array(Self::nest_level(rows, depth + 1));
+            nodes.push(node);
+        }
+        nodes
+    }
+
+    /// Build a file name in the working directory from a prefix, a node name and the current time
+    pub fn timestamped_file_name(prefix: &str, node_name: &str, extension: &str) -> PathBuf {
+        let safe_name: String = node_name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        PathBuf::from(format!("{prefix}_{safe_name}_{timestamp}.{extension}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn depth_first_rows_nest_under_the_row_before_them() {
+        let rows = [(0, "a"), (1, "a1"), (2, "a1x"), (1, "a2"), (0, "b")]
+            .map(|(depth, name)| (depth, json!({ "name": name })));
+        assert_eq!(
+            ExportUtils::nest_json(rows),
+            vec![
+                json!({ "name": "a", "children": [
+                    { "name": "a1", "children": [{ "name": "a1x", "children": [] }] },
+                    { "name": "a2", "children": [] },
+                ] }),
+                json!({ "name": "b", "children": [] }),
+            ]
+        );
+    }
+}
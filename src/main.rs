@@ -6,17 +6,28 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+mod cert_utils;
+mod cli;
 mod client;
 mod components;
 mod config;
 mod connection_manager;
+mod data_type_decoder;
+mod event_utils;
+mod export_utils;
 mod logging;
 mod node_utils;
+mod nodeset_utils;
+mod reverse_connect;
 mod screens;
+mod snapshot_utils;
 mod ui;
 mod ui_utils;
+mod value_utils;
 
 use client::OpcUaClientManager;
+use config::{ConnectionProfile, OpcUaConfig, CONFIG_FILE};
+use reverse_connect::ReverseConnectListener;
 use ui::App;
 
 #[derive(Parser, Debug)]
@@ -24,62 +35,123 @@ use ui::App;
 #[command(about = "OPC UA TUI Client with command line support")]
 #[command(version)]
 pub struct Args {
+    /// Run a single command without the TUI
+    #[command(subcommand)]
+    command: Option<cli::Command>,
+
+    /// Connect with a named profile from config.json; other options override its settings
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
     /// OPC UA server URL (e.g., opc.tcp://localhost:4840)
-    #[arg(long)]
+    #[arg(long, global = true)]
     server_url: Option<String>,
 
-    /// Security policy (None, Basic128Rsa15, Basic256, Basic256Sha256, Aes128Sha256RsaOaep, Aes256Sha256RsaPss)
-    #[arg(long, default_value = "None")]
-    security_policy: String,
+    /// Security policy (None, Basic128Rsa15, Basic256, Basic256Sha256, Aes128Sha256RsaOaep, Aes256Sha256RsaPss) [default: None]
+    #[arg(long, global = true)]
+    security_policy: Option<String>,
 
-    /// Security mode (None, Sign, SignAndEncrypt)
-    #[arg(long, default_value = "None")]
-    security_mode: String,
+    /// Security mode (None, Sign, SignAndEncrypt) [default: None]
+    #[arg(long, global = true)]
+    security_mode: Option<String>,
 
     /// Path to client certificate file
-    #[arg(long)]
+    #[arg(long, global = true)]
     client_certificate: Option<String>,
 
     /// Path to client private key file
-    #[arg(long)]
+    #[arg(long, global = true)]
     client_private_key: Option<String>,
 
     /// Auto-trust server certificate
-    #[arg(long)]
+    #[arg(long, global = true)]
     auto_trust: bool,
 
     /// Path to trusted certificate store (required if auto_trust is false)
-    #[arg(long)]
+    #[arg(long, global = true)]
     trusted_store: Option<String>,
 
     /// Username for authentication
-    #[arg(long)]
+    #[arg(long, global = true)]
     user_name: Option<String>,
 
     /// Password for authentication
-    #[arg(long)]
+    #[arg(long, global = true)]
     password: Option<String>,
 
     /// Path to user certificate file for X.509 authentication
-    #[arg(long)]
+    #[arg(long, global = true)]
     user_certificate: Option<String>,
 
     /// Path to user private key file for X.509 authentication
-    #[arg(long)]
+    #[arg(long, global = true)]
     user_private_key: Option<String>,
 
     /// Use original URL instead of server-provided endpoint URLs
-    #[arg(long)]
+    #[arg(long, global = true)]
     use_original_url: bool,
 
+    /// Listen for Reverse Connect from servers on this address (e.g., 0.0.0.0:4844); list them with Ctrl+R on the connect screen
+    #[arg(long, global = true)]
+    reverse_connect_listen: Option<String>,
+
     /// Log level (Error, Warn, Info, Debug, Trace)
-    #[arg(long, default_value = "Info")]
+    #[arg(long, default_value = "Info", global = true)]
     log_level: String,
+
+    /// Output format of commands
+    #[arg(long, value_enum, default_value = "table", global = true)]
+    output: cli::OutputFormat,
+}
+
+impl Args {
+    fn security_policy(&self) -> &str {
+        self.security_policy.as_deref().unwrap_or("None")
+    }
+
+    fn security_mode(&self) -> &str {
+        self.security_mode.as_deref().unwrap_or("None")
+    }
+
+    /// Fill the options not given on the command line from a connection profile
+    fn apply_profile(&mut self, profile: &ConnectionProfile) {
+        fn fill(arg: &mut Option<String>, value: &Option<String>) {
+            if arg.is_none() {
+                arg.clone_from(value);
+            }
+        }
+
+        fill(&mut self.server_url, &Some(profile.server_url.clone()));
+        fill(
+            &mut self.security_policy,
+            &Some(profile.security_policy.clone()),
+        );
+        fill(
+            &mut self.security_mode,
+            &Some(profile.security_mode.clone()),
+        );
+        fill(&mut self.client_certificate, &profile.client_certificate);
+        fill(&mut self.client_private_key, &profile.client_private_key);
+        fill(&mut self.trusted_store, &profile.trusted_store);
+        self.auto_trust |= profile.auto_trust;
+        self.use_original_url |= profile.use_original_url;
+
+        // Only take the profile's credentials when none were given on the command line
+        let has_credentials = self.user_name.is_some() || self.user_certificate.is_some();
+        match profile.auth_type.as_str() {
+            "UserPassword" if !has_credentials => fill(&mut self.user_name, &profile.username),
+            "X509Certificate" if !has_credentials => {
+                fill(&mut self.user_certificate, &profile.user_certificate);
+                fill(&mut self.user_private_key, &profile.user_private_key);
+            }
+            _ => {}
+        }
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let args = Args::parse();
+    let mut args = Args::parse();
 
     // Parse log level from command line argument
     let log_level = match args.log_level.to_lowercase().as_str() {
@@ -98,13 +170,51 @@ async fn main() -> Result<()> {
     // Initialize our custom dual logger with the specified level
     logging::init_logger(log_level);
 
+    if let Some(name) = args.profile.clone() {
+        let profile = OpcUaConfig::load().and_then(|config| {
+            config.find_profile(&name).cloned().ok_or_else(|| {
+                anyhow::anyhow!("Connection profile '{}' not found in {}", name, CONFIG_FILE)
+            })
+        });
+        match profile {
+            Ok(profile) => {
+                log::info!("Using connection profile '{name}'");
+                args.apply_profile(&profile);
+                if profile.auth_type == "UserPassword" && args.password.is_none() {
+                    log::error!(
+                        "Profile '{name}' uses username/password authentication; passwords are not stored, pass --password"
+                    );
+                    logging::flush_console_logs();
+                    std::process::exit(1);
+                }
+            }
+            Err(e) => {
+                log::error!("{e}");
+                logging::flush_console_logs();
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if args.reverse_connect_listen.is_some()
+        && (args.command.is_some() || args.server_url.is_some())
+    {
+        log::warn!("--reverse-connect-listen only applies to the connect screen, ignoring it");
+    }
+
+    if let Some(command) = &args.command {
+        let exit_code = cli::run(command, &args).await;
+        logging::flush_console_logs();
+        std::process::exit(exit_code);
+    }
+
     let client_manager = Arc::new(RwLock::new(OpcUaClientManager::new())); // Check if we should connect directly via command line parameters
     if let Some(ref server_url) = args.server_url {
         // Use log macros for CLI connection (will be buffered)
         log::info!("Starting OPC UA Client with command line connection...");
         log::info!("Server URL: {server_url}");
-        log::info!("Security Policy: {}", args.security_policy);
-        log::info!("Security Mode: {}", args.security_mode);
+        log::info!("Security Policy: {}", args.security_policy());
+        log::info!("Security Mode: {}", args.security_mode());
 
         if args.use_original_url {
             log::info!("Using original URL override");
@@ -134,10 +244,23 @@ async fn main() -> Result<()> {
             }
         }
     } else {
+        // Start listening before the TUI takes over so a bad address is reported on the console
+        let reverse_connect = match &args.reverse_connect_listen {
+            Some(address) => match ReverseConnectListener::start(address).await {
+                Ok(listener) => Some(listener),
+                Err(e) => {
+                    log::error!("{e}");
+                    logging::flush_console_logs();
+                    std::process::exit(1);
+                }
+            },
+            None => None,
+        };
+
         // Normal TUI mode - switch to TUI logging immediately
         logging::switch_to_tui_logging();
 
-        let mut app = App::new(client_manager);
+        let mut app = App::new(client_manager, reverse_connect);
         app.run().await?;
     }
 
@@ -154,7 +277,7 @@ async fn connect_via_command_line(
     log::info!("Parsing connection parameters...");
 
     // Validate security configuration
-    if args.security_policy != "None" || args.security_mode != "None" {
+    if args.security_policy() != "None" || args.security_mode() != "None" {
         if args.client_certificate.is_none() || args.client_private_key.is_none() {
             return Err(anyhow::anyhow!(
                 "Client certificate and private key are required for non-None security"
@@ -216,16 +339,16 @@ async fn connect_with_cli_params(
     client_manager.server_url = server_url.to_string();
 
     // Convert our local enums to opcua crate enums
-    let opcua_security_policy = convert_security_policy(&args.security_policy)?;
-    let opcua_security_mode = convert_security_mode(&args.security_mode)?;
+    let opcua_security_policy = convert_security_policy(args.security_policy())?;
+    let opcua_security_mode = convert_security_mode(args.security_mode())?;
 
     // Create identity token based on authentication parameters
     let identity_token = create_identity_token(args)?;
 
     log::info!(
         "Building client with security policy: {} and mode: {}",
-        args.security_policy,
-        args.security_mode
+        args.security_policy(),
+        args.security_mode()
     );
 
     // Create unified connection configuration
@@ -263,11 +386,9 @@ async fn connect_with_cli_params(
     let connection_result = ConnectionManager::connect_to_server(server_url, &config).await;
 
     match connection_result {
-        Ok((client, session)) => {
+        Ok((client, session, target)) => {
             // Store the connection in the client manager
-            client_manager.client = Some(client);
-            client_manager.session = Some(session);
-            client_manager.connection_status = crate::client::ConnectionStatus::Connected;
+            client_manager.set_connection(client, session, server_url.to_string(), Some(target));
             Ok(())
         }
         Err(e) => {
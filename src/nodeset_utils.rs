@@ -0,0 +1,497 @@
+use crate::client::{OpcUaClientManager, OpcUaExportAttributes, OpcUaReference};
+use opcua::types::{LocalizedText, NodeClass, NodeId, QualifiedName, Variant};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+
+// XML encoding NodeId of the Argument structure
+const ARGUMENT_XML_ENCODING: &str = "i=297";
+
+/// A node of an exported subtree with everything its NodeSet2 element needs
+pub struct NodeSetNode {
+    pub node_id: NodeId,
+    pub node_class: NodeClass,
+    pub display_name: String,
+    pub attributes: OpcUaExportAttributes,
+    pub references: Vec<OpcUaReference>,
+}
+
+/// Utility functions for writing nodes as a UA NodeSet2 XML document
+pub struct NodeSetUtils;
+
+impl NodeSetUtils {
+    /// Build a NodeSet2 document. NodeIds keep the server's namespace indexes, so every entry of
+    /// the NamespaceArray after the UA namespace is listed in NamespaceUris in server order.
+    pub fn build_nodeset(namespace_array: &[String], nodes: &[NodeSetNode]) -> String {
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<UANodeSet xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+             xmlns:uax=\"http://opcfoundation.org/UA/2008/02/Types.xsd\" \
+             xmlns=\"http://opcfoundation.org/UA/2011/03/UANodeSet.xsd\" LastModified=\"{}\">",
+            chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ")
+        );
+
+        if namespace_array.len() > 1 {
+            xml.push_str("  <NamespaceUris>\n");
+            for uri in &namespace_array[1..] {
+                let _ = writeln!(xml, "    <Uri>{}</Uri>", Self::escape(uri));
+            }
+            xml.push_str("  </NamespaceUris>\n");
+        }
+
+        // Standard ReferenceTypes are written by name
+        let aliases: BTreeMap<&str, String> = nodes
+            .iter()
+            .flat_map(|node| &node.references)
+            .filter(|reference| {
+                reference.reference_type_id.namespace == 0
+                    && reference.reference_type != reference.reference_type_id.to_string()
+            })
+            .map(|reference| {
+                (
+                    reference.reference_type.as_str(),
+                    reference.reference_type_id.to_string(),
+                )
+            })
+            .collect();
+        if !aliases.is_empty() {
+            xml.push_str("  <Aliases>\n");
+            for (alias, node_id) in &aliases {
+                let _ = writeln!(
+                    xml,
+                    "    <Alias Alias=\"{}\">{}</Alias>",
+                    Self::escape(alias),
+                    node_id
+                );
+            }
+            xml.push_str("  </Aliases>\n");
+        }
+
+        let exported: HashSet<&NodeId> = nodes.iter().map(|node| &node.node_id).collect();
+        // A NodeId may only appear once in a NodeSet
+        let mut written: HashSet<&NodeId> = HashSet::new();
+        for node in nodes {
+            if written.insert(&node.node_id) {
+                Self::write_node(&mut xml, node, &aliases, &exported);
+            }
+        }
+
+        xml.push_str("</UANodeSet>\n");
+        xml
+    }
+
+    fn write_node(
+        xml: &mut String,
+        node: &NodeSetNode,
+        aliases: &BTreeMap<&str, String>,
+        exported: &HashSet<&NodeId>,
+    ) {
+        let element = match node.node_class {
+            NodeClass::Object => "UAObject",
+            NodeClass::Variable => "UAVariable",
+            NodeClass::Method => "UAMethod",
+            NodeClass::ObjectType => "UAObjectType",
+            NodeClass::VariableType => "UAVariableType",
+            NodeClass::ReferenceType => "UAReferenceType",
+            NodeClass::DataType => "UADataType",
+            NodeClass::View => "UAView",
+            NodeClass::Unspecified => {
+                log::warn!("nodeset: skipping {} with unknown NodeClass", node.node_id);
+                return;
+            }
+        };
+        let attributes = &node.attributes;
+
+        let browse_name = attributes
+            .browse_name
+            .as_ref()
+            .map(Self::format_browse_name)
+            .unwrap_or_else(|| node.display_name.clone());
+        let mut element_attributes = vec![
+            ("NodeId", node.node_id.to_string()),
+            ("BrowseName", browse_name),
+        ];
+        if let Some(data_type) = &attributes.data_type {
+            element_attributes.push(("DataType", data_type.to_string()));
+        }
+        if let Some(value_rank) = attributes.value_rank.filter(|rank| *rank != -1) {
+            element_attributes.push(("ValueRank", value_rank.to_string()));
+        }
+        if let Some(dimensions) = attributes
+            .array_dimensions
+            .as_ref()
+            .filter(|dimensions| !dimensions.is_empty())
+        {
+            let dimensions: Vec<String> = dimensions.iter().map(u32::to_string).collect();
+            element_attributes.push(("ArrayDimensions", dimensions.join(",")));
+        }
+        if let Some(access_level) = attributes.access_level.filter(|level| *level != 1) {
+            element_attributes.push(("AccessLevel", access_level.to_string()));
+        }
+        if let Some(user_access_level) = attributes.user_access_level.filter(|level| *level != 1) {
+            element_attributes.push(("UserAccessLevel", user_access_level.to_string()));
+        }
+        if let Some(interval) = attributes
+            .minimum_sampling_interval
+            .filter(|interval| *interval != 0.0)
+        {
+            element_attributes.push(("MinimumSamplingInterval", interval.to_string()));
+        }
+        if attributes.historizing == Some(true) {
+            element_attributes.push(("Historizing", "true".to_string()));
+        }
+        if let Some(notifier) = attributes.event_notifier.filter(|notifier| *notifier != 0) {
+            element_attributes.push(("EventNotifier", notifier.to_string()));
+        }
+        if attributes.executable == Some(false) {
+            element_attributes.push(("Executable", "false".to_string()));
+        }
+        if attributes.is_abstract == Some(true) {
+            element_attributes.push(("IsAbstract", "true".to_string()));
+        }
+        if attributes.symmetric == Some(true) {
+            element_attributes.push(("Symmetric", "true".to_string()));
+        }
+
+        let _ = write!(xml, "  <{element}");
+        for (name, value) in &element_attributes {
+            let _ = write!(xml, " {name}=\"{}\"", Self::escape(value));
+        }
+        xml.push_str(">\n");
+
+        let _ = writeln!(
+            xml,
+            "    <DisplayName>{}</DisplayName>",
+            Self::escape(&node.display_name)
+        );
+        if let Some(description) = attributes
+            .description
+            .as_ref()
+            .and_then(Self::localized_text)
+        {
+            let _ = writeln!(
+                xml,
+                "    <Description>{}</Description>",
+                Self::escape(&description)
+            );
+        }
+        // Forward references are enough between exported nodes; inverse ones are only kept
+        // when they point out of the subtree, such as to the parent of the selected node
+        let references: Vec<&OpcUaReference> = node
+            .references
+            .iter()
+            .filter(|reference| reference.is_forward || !exported.contains(&reference.target_id))
+            .collect();
+        if !references.is_empty() {
+            xml.push_str("    <References>\n");
+            for reference in references {
+                let reference_type = if aliases.contains_key(reference.reference_type.as_str()) {
+                    reference.reference_type.clone()
+                } else {
+                    reference.reference_type_id.to_string()
+                };
+                let direction = if reference.is_forward {
+                    ""
+                } else {
+                    " IsForward=\"false\""
+                };
+                let _ = writeln!(
+                    xml,
+                    "      <Reference ReferenceType=\"{}\"{direction}>{}</Reference>",
+                    Self::escape(&reference_type),
+                    Self::escape(&reference.target_id.to_string())
+                );
+            }
+            xml.push_str("    </References>\n");
+        }
+
+        // The schema puts InverseName after References
+        if let Some(inverse_name) = attributes
+            .inverse_name
+            .as_ref()
+            .and_then(Self::localized_text)
+        {
+            let _ = writeln!(
+                xml,
+                "    <InverseName>{}</InverseName>",
+                Self::escape(&inverse_name)
+            );
+        }
+
+        if let Some(value) = &attributes.value {
+            match Self::value_xml(value) {
+                Some(value_xml) => {
+                    let _ = writeln!(xml, "    <Value>{value_xml}</Value>");
+                }
+                None if !matches!(value, Variant::Empty) => {
+                    log::debug!(
+                        "nodeset: value of {} has no XML encoding, left out",
+                        node.node_id
+                    );
+                }
+                None => {}
+            }
+        }
+
+        let _ = writeln!(xml, "  </{element}>");
+    }
+
+    /// Encode a value in the UA XML encoding, or `None` for types without one here
+    fn value_xml(value: &Variant) -> Option<String> {
+        match value {
+            Variant::Empty => None,
+            Variant::Array(array) => {
+                // Matrices have no ListOf encoding
+                if array
+                    .dimensions
+                    .as_ref()
+                    .is_some_and(|dimensions| dimensions.len() > 1)
+                {
+                    return None;
+                }
+                let type_name = format!("{:?}", array.value_type);
+                let elements = array
+                    .values
+                    .iter()
+                    .map(Self::scalar_xml)
+                    .collect::<Option<Vec<String>>>()?;
+                Some(format!(
+                    "<uax:ListOf{type_name}>{}</uax:ListOf{type_name}>",
+                    elements.concat()
+                ))
+            }
+            scalar => Self::scalar_xml(scalar),
+        }
+    }
+
+    fn scalar_xml(value: &Variant) -> Option<String> {
+        let (type_name, content) = match value {
+            Variant::Boolean(v) => ("Boolean", v.to_string()),
+            Variant::SByte(v) => ("SByte", v.to_string()),
+            Variant::Byte(v) => ("Byte", v.to_string()),
+            Variant::Int16(v) => ("Int16", v.to_string()),
+            Variant::UInt16(v) => ("UInt16", v.to_string()),
+            Variant::Int32(v) => ("Int32", v.to_string()),
+            Variant::UInt32(v) => ("UInt32", v.to_string()),
+            Variant::Int64(v) => ("Int64", v.to_string()),
+            Variant::UInt64(v) => ("UInt64", v.to_string()),
+            Variant::Float(v) => ("Float", Self::format_float(*v as f64)),
+            Variant::Double(v) => ("Double", Self::format_float(*v)),
+            Variant::String(v) => (
+                "String",
+                Self::escape(v.value().as_deref().unwrap_or_default()),
+            ),
+            Variant::DateTime(v) => (
+                "DateTime",
+                v.as_chrono().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+            ),
+            Variant::Guid(v) => ("Guid", format!("<uax:String>{v}</uax:String>")),
+            Variant::ByteString(v) => ("ByteString", v.as_base64()),
+            Variant::StatusCode(v) => ("StatusCode", format!("<uax:Code>{}</uax:Code>", v.bits())),
+            Variant::NodeId(v) => (
+                "NodeId",
+                format!(
+                    "<uax:Identifier>{}</uax:Identifier>",
+                    Self::escape(&v.to_string())
+                ),
+            ),
+            Variant::QualifiedName(v) => (
+                "QualifiedName",
+                format!(
+                    "<uax:NamespaceIndex>{}</uax:NamespaceIndex><uax:Name>{}</uax:Name>",
+                    v.namespace_index,
+                    Self::escape(v.name.value().as_deref().unwrap_or_default())
+                ),
+            ),
+            Variant::LocalizedText(v) => (
+                "LocalizedText",
+                format!(
+                    "<uax:Locale>{}</uax:Locale><uax:Text>{}</uax:Text>",
+                    Self::escape(v.locale.value().as_deref().unwrap_or_default()),
+                    Self::escape(v.text.value().as_deref().unwrap_or_default())
+                ),
+            ),
+            // Method arguments are the only structures a simulator needs to call Methods
+            Variant::ExtensionObject(_) => {
+                let argument = OpcUaClientManager::decode_arguments(Some(value)).pop()?;
+                let dimensions: String = argument
+                    .array_dimensions
+                    .iter()
+                    .flatten()
+                    .map(|length| format!("<uax:UInt32>{length}</uax:UInt32>"))
+                    .collect();
+                let body = format!(
+                    "<uax:Argument><uax:Name>{}</uax:Name>\
+                     <uax:DataType><uax:Identifier>{}</uax:Identifier></uax:DataType>\
+                     <uax:ValueRank>{}</uax:ValueRank>\
+                     <uax:ArrayDimensions>{dimensions}</uax:ArrayDimensions>\
+                     <uax:Description><uax:Text>{}</uax:Text></uax:Description></uax:Argument>",
+                    Self::escape(argument.name.value().as_deref().unwrap_or_default()),
+                    Self::escape(&argument.data_type.to_string()),
+                    argument.value_rank,
+                    Self::escape(
+                        argument
+                            .description
+                            .text
+                            .value()
+                            .as_deref()
+                            .unwrap_or_default()
+                    )
+                );
+                (
+                    "ExtensionObject",
+                    format!(
+                        "<uax:TypeId><uax:Identifier>{ARGUMENT_XML_ENCODING}</uax:Identifier></uax:TypeId><uax:Body>{body}</uax:Body>"
+                    ),
+                )
+            }
+            _ => return None,
+        };
+        Some(format!("<uax:{type_name}>{content}</uax:{type_name}>"))
+    }
+
+    /// Infinity and NaN use the XML Schema spelling
+    fn format_float(value: f64) -> String {
+        if value.is_nan() {
+            "NaN".to_string()
+        } else if value.is_infinite() {
+            if value > 0.0 { "INF" } else { "-INF" }.to_string()
+        } else {
+            value.to_string()
+        }
+    }
+
+    /// BrowseName as "<namespace index>:<name>", without prefix in the UA namespace
+    fn format_browse_name(name: &QualifiedName) -> String {
+        let text = name.name.value().clone().unwrap_or_default();
+        if name.namespace_index == 0 {
+            text
+        } else {
+            format!("{}:{}", name.namespace_index, text)
+        }
+    }
+
+    fn localized_text(text: &LocalizedText) -> Option<String> {
+        text.text.value().clone().filter(|text| !text.is_empty())
+    }
+
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&apos;")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn node_id(text: &str) -> NodeId {
+        NodeId::from_str(text).unwrap()
+    }
+
+    fn node(id: &str, display_name: &str, references: Vec<OpcUaReference>) -> NodeSetNode {
+        NodeSetNode {
+            node_id: node_id(id),
+            node_class: NodeClass::Object,
+            display_name: display_name.to_string(),
+            attributes: OpcUaExportAttributes::default(),
+            references,
+        }
+    }
+
+    fn reference(name: &str, type_id: &str, is_forward: bool, target: &str) -> OpcUaReference {
+        OpcUaReference {
+            reference_type: name.to_string(),
+            reference_type_id: node_id(type_id),
+            is_forward,
+            target_id: node_id(target),
+            browse_name: String::new(),
+            node_class: NodeClass::Object,
+        }
+    }
+
+    #[test]
+    fn namespace_uris_leave_out_the_ua_namespace() {
+        let namespaces = [
+            "http://opcfoundation.org/UA/".to_string(),
+            "urn:plant?a=1&b=2".to_string(),
+        ];
+        let xml = NodeSetUtils::build_nodeset(&namespaces, &[]);
+        assert!(xml.contains("<Uri>urn:plant?a=1&amp;b=2</Uri>"));
+        assert!(!xml.contains("<Uri>http://opcfoundation.org/UA/</Uri>"));
+    }
+
+    #[test]
+    fn only_standard_reference_types_get_aliases() {
+        let nodes = [node(
+            "ns=1;s=Pump",
+            "Pump",
+            vec![
+                reference("HasComponent", "i=47", true, "ns=1;s=Motor"),
+                reference("FeedsInto", "ns=1;i=4001", true, "ns=1;s=Tank"),
+                reference("i=9999", "i=9999", true, "ns=1;s=Valve"),
+            ],
+        )];
+        let xml = NodeSetUtils::build_nodeset(&[], &nodes);
+        assert!(xml.contains("<Alias Alias=\"HasComponent\">i=47</Alias>"));
+        assert!(!xml.contains("Alias=\"FeedsInto\""));
+        assert!(!xml.contains("Alias=\"i=9999\""));
+        assert!(xml.contains("<Reference ReferenceType=\"HasComponent\">ns=1;s=Motor</Reference>"));
+        assert!(xml.contains("<Reference ReferenceType=\"ns=1;i=4001\">ns=1;s=Tank</Reference>"));
+        assert!(xml.contains("<Reference ReferenceType=\"i=9999\">ns=1;s=Valve</Reference>"));
+    }
+
+    #[test]
+    fn inverse_references_are_kept_only_out_of_the_subtree() {
+        let nodes = [
+            node(
+                "ns=1;s=Pump",
+                "Pump",
+                vec![
+                    reference("Organizes", "i=35", false, "i=85"),
+                    reference("HasComponent", "i=47", true, "ns=1;s=Motor"),
+                ],
+            ),
+            node(
+                "ns=1;s=Motor",
+                "Motor",
+                vec![reference("HasComponent", "i=47", false, "ns=1;s=Pump")],
+            ),
+        ];
+        let xml = NodeSetUtils::build_nodeset(&[], &nodes);
+        assert!(xml.contains(
+            "<Reference ReferenceType=\"Organizes\" IsForward=\"false\">i=85</Reference>"
+        ));
+        assert!(xml.contains("<Reference ReferenceType=\"HasComponent\">ns=1;s=Motor</Reference>"));
+        assert!(!xml.contains("IsForward=\"false\">ns=1;s=Pump"));
+    }
+
+    #[test]
+    fn text_is_escaped() {
+        let mut pump = node("ns=1;s=A<B>", "Tank \"A\" & 'B' <1>", Vec::new());
+        pump.attributes.description = Some(LocalizedText::new("", "x < y"));
+        let xml = NodeSetUtils::build_nodeset(&[], &[pump]);
+        assert!(xml.contains("NodeId=\"ns=1;s=A&lt;B&gt;\""));
+        assert!(xml.contains(
+            "<DisplayName>Tank &quot;A&quot; &amp; &apos;B&apos; &lt;1&gt;</DisplayName>"
+        ));
+        assert!(xml.contains("<Description>x &lt; y</Description>"));
+    }
+
+    #[test]
+    fn each_node_is_written_once() {
+        let nodes = [
+            node("ns=1;s=Pump", "Pump", Vec::new()),
+            node("ns=1;s=Motor", "Motor", Vec::new()),
+            node("ns=1;s=Pump", "Pump", Vec::new()),
+        ];
+        let xml = NodeSetUtils::build_nodeset(&[], &nodes);
+        assert_eq!(xml.matches("NodeId=\"ns=1;s=Pump\"").count(), 1);
+        assert_eq!(xml.matches("NodeId=\"ns=1;s=Motor\"").count(), 1);
+    }
+}
@@ -0,0 +1,390 @@
+//! Reverse Connect (OPC UA Part 6, 7.1.3): servers that can only open outbound connections
+//! connect to the client and announce themselves with a ReverseHello message. The opcua client
+//! stack only dials out, so each connect attempt gets a relay on 127.0.0.1 that hands the
+//! connections the client opens to the sockets the chosen server opened.
+
+use anyhow::{anyhow, Result};
+use parking_lot::Mutex;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+const MAX_REVERSE_HELLO_SIZE: usize = 16 * 1024;
+const MAX_HELLO_SIZE: usize = 16 * 1024;
+const REVERSE_HELLO_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long a relayed connection waits for the server to open another socket
+const SOCKET_WAIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A server with open reverse connections, as listed on the connect screen
+#[derive(Clone, Debug)]
+pub struct ReverseServer {
+    pub server_uri: String,
+    pub endpoint_url: String,
+    pub peer: SocketAddr, // Address of the most recent socket
+    pub open_sockets: usize,
+}
+
+/// A socket a server opened, waiting for the client to send Hello
+#[derive(Debug)]
+struct ReverseSocket {
+    server_uri: String,
+    endpoint_url: String,
+    peer: SocketAddr,
+    socket: TcpStream,
+}
+
+#[derive(Debug, Default)]
+struct ListenerState {
+    sockets: Vec<ReverseSocket>, // Oldest first
+}
+
+/// Accepts reverse connections in the background; cheap to clone
+#[derive(Clone, Debug)]
+pub struct ReverseConnectListener {
+    address: SocketAddr,
+    state: Arc<Mutex<ListenerState>>,
+}
+
+impl ReverseConnectListener {
+    /// Listen on `address`, e.g. "0.0.0.0:4844", until the application exits
+    pub async fn start(address: &str) -> Result<Self> {
+        let listener = TcpListener::bind(address)
+            .await
+            .map_err(|e| anyhow!("Cannot listen on {address}: {e}"))?;
+        let listener_state = Self {
+            address: listener.local_addr()?,
+            state: Arc::default(),
+        };
+        log::info!(
+            "reverse connect: listening for ReverseHello on {}",
+            listener_state.address
+        );
+
+        let state = listener_state.state.clone();
+        tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((socket, peer)) => {
+                        tokio::spawn(Self::accept_server(state.clone(), socket, peer));
+                    }
+                    Err(e) => {
+                        log::warn!("reverse connect: accept failed: {e}");
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                    }
+                }
+            }
+        });
+        Ok(listener_state)
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    async fn accept_server(
+        state: Arc<Mutex<ListenerState>>,
+        mut socket: TcpStream,
+        peer: SocketAddr,
+    ) {
+        match tokio::time::timeout(REVERSE_HELLO_TIMEOUT, Self::read_reverse_hello(&mut socket))
+            .await
+        {
+            Ok(Ok((server_uri, endpoint_url))) => {
+                log::info!("reverse connect: {server_uri} ({endpoint_url}) connected from {peer}");
+                state.lock().sockets.push(ReverseSocket {
+                    server_uri,
+                    endpoint_url,
+                    peer,
+                    socket,
+                });
+            }
+            Ok(Err(e)) => log::warn!("reverse connect: {peer}: {e}"),
+            Err(_) => log::warn!("reverse connect: {peer} sent no ReverseHello"),
+        }
+    }
+
+    /// Read the ReverseHello message: "RHEF", the message size and the ServerUri and EndpointUrl
+    async fn read_reverse_hello(socket: &mut TcpStream) -> Result<(String, String)> {
+        let mut header = [0u8; 8];
+        socket.read_exact(&mut header).await?;
+        if &header[..4] != b"RHEF" {
+            return Err(anyhow!("not a ReverseHello message"));
+        }
+        let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+        if !(header.len()..=MAX_REVERSE_HELLO_SIZE).contains(&size) {
+            return Err(anyhow!("invalid ReverseHello size {size}"));
+        }
+
+        let mut body = vec![0u8; size - header.len()];
+        socket.read_exact(&mut body).await?;
+        let mut body = body.as_slice();
+        let server_uri = Self::read_string(&mut body)?;
+        let endpoint_url = Self::read_string(&mut body)?;
+        Ok((server_uri, endpoint_url))
+    }
+
+    /// Decode an OPC UA String: Int32 length (-1 for null) followed by UTF-8 bytes
+    fn read_string(data: &mut &[u8]) -> Result<String> {
+        let Some((length, rest)) = data.split_first_chunk::<4>() else {
+            return Err(anyhow!("truncated ReverseHello"));
+        };
+        let length = i32::from_le_bytes(*length).max(0) as usize;
+        if rest.len() < length {
+            return Err(anyhow!("truncated ReverseHello"));
+        }
+        let text = String::from_utf8_lossy(&rest[..length]).into_owned();
+        *data = &rest[length..];
+        Ok(text)
+    }
+
+    /// Servers with open sockets, in the order they first connected
+    pub fn servers(&self) -> Vec<ReverseServer> {
+        let mut state = self.state.lock();
+        state.sockets.retain(|socket| Self::is_open(&socket.socket));
+
+        let mut servers: Vec<ReverseServer> = Vec::new();
+        for socket in &state.sockets {
+            match servers.iter_mut().find(|server| {
+                server.server_uri == socket.server_uri && server.endpoint_url == socket.endpoint_url
+            }) {
+                Some(server) => {
+                    server.open_sockets += 1;
+                    server.peer = socket.peer;
+                }
+                None => servers.push(ReverseServer {
+                    server_uri: socket.server_uri.clone(),
+                    endpoint_url: socket.endpoint_url.clone(),
+                    peer: socket.peer,
+                    open_sockets: 1,
+                }),
+            }
+        }
+        servers
+    }
+
+    /// A waiting socket has nothing to read; end of stream or data means it cannot be used
+    fn is_open(socket: &TcpStream) -> bool {
+        let mut buffer = [0u8; 1];
+        matches!(socket.try_read(&mut buffer), Err(e) if e.kind() == std::io::ErrorKind::WouldBlock)
+    }
+
+    /// Start a relay to `server` on 127.0.0.1, serving the path of its endpoint URL. It only
+    /// accepts connections while the returned handle lives, so hold it for one connect attempt.
+    pub fn relay(&self, server: &ReverseServer) -> Result<ReverseRelay> {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+        listener.set_nonblocking(true)?;
+        let listener = TcpListener::from_std(listener)?;
+        let port = listener.local_addr()?.port();
+        log::debug!(
+            "reverse connect: relaying 127.0.0.1:{port} to {}",
+            server.server_uri
+        );
+
+        let state = self.state.clone();
+        let key = (server.server_uri.clone(), server.endpoint_url.clone());
+        let task = tokio::spawn(async move {
+            while let Ok((client, _)) = listener.accept().await {
+                tokio::spawn(Self::relay_connection(state.clone(), key.clone(), client));
+            }
+        });
+
+        let path = server
+            .endpoint_url
+            .split_once("://")
+            .and_then(|(_, rest)| rest.find('/').map(|index| &rest[index..]))
+            .unwrap_or("");
+        Ok(ReverseRelay {
+            url: format!("opc.tcp://127.0.0.1:{port}{path}"),
+            target: ReverseTarget {
+                listener: self.clone(),
+                server: server.clone(),
+            },
+            task,
+        })
+    }
+
+    /// Copy between a client connection and the newest socket of the server
+    async fn relay_connection(
+        state: Arc<Mutex<ListenerState>>,
+        key: (String, String),
+        mut client: TcpStream,
+    ) {
+        let deadline = Instant::now() + SOCKET_WAIT_TIMEOUT;
+        let mut server = loop {
+            if let Some(socket) = Self::take_socket(&state, &key) {
+                break socket;
+            }
+            if Instant::now() >= deadline {
+                log::warn!("reverse connect: {} did not connect again", key.0);
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        };
+
+        if let Err(e) = Self::forward_hello(&mut client, &mut server, &key.1).await {
+            log::warn!("reverse connect: {}: {e}", key.0);
+            return;
+        }
+        match tokio::io::copy_bidirectional(&mut client, &mut server).await {
+            Ok((sent, received)) => {
+                log::debug!(
+                    "reverse connect: {} closed ({sent} bytes sent, {received} received)",
+                    key.0
+                )
+            }
+            Err(e) => log::debug!("reverse connect: {} closed: {e}", key.0),
+        }
+    }
+
+    /// Pass the client's Hello on with the EndpointUrl the server announced instead of the
+    /// relay URL, as Part 6 requires for reverse connections
+    async fn forward_hello(
+        client: &mut TcpStream,
+        server: &mut TcpStream,
+        endpoint_url: &str,
+    ) -> Result<()> {
+        let mut header = [0u8; 8];
+        client.read_exact(&mut header).await?;
+        if &header[..4] != b"HELF" {
+            return Err(anyhow!("client did not start with a Hello message"));
+        }
+        let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+        if !(header.len()..=MAX_HELLO_SIZE).contains(&size) {
+            return Err(anyhow!("invalid Hello size {size}"));
+        }
+        let mut body = vec![0u8; size - header.len()];
+        client.read_exact(&mut body).await?;
+
+        // ProtocolVersion, ReceiveBufferSize, SendBufferSize, MaxMessageSize and MaxChunkCount
+        // precede the EndpointUrl
+        let Some((limits, mut rest)) = body.split_first_chunk::<20>() else {
+            return Err(anyhow!("truncated Hello"));
+        };
+        Self::read_string(&mut rest)?;
+
+        let mut hello = Vec::with_capacity(size + endpoint_url.len());
+        hello.extend_from_slice(b"HELF");
+        hello.extend_from_slice(&[0; 4]); // Size, set below
+        hello.extend_from_slice(limits);
+        hello.extend_from_slice(&(endpoint_url.len() as i32).to_le_bytes());
+        hello.extend_from_slice(endpoint_url.as_bytes());
+        let hello_size = hello.len() as u32;
+        hello[4..8].copy_from_slice(&hello_size.to_le_bytes());
+        server.write_all(&hello).await?;
+        Ok(())
+    }
+
+    fn take_socket(state: &Mutex<ListenerState>, key: &(String, String)) -> Option<TcpStream> {
+        let mut state = state.lock();
+        state.sockets.retain(|socket| Self::is_open(&socket.socket));
+        let index = state
+            .sockets
+            .iter()
+            .rposition(|socket| socket.server_uri == key.0 && socket.endpoint_url == key.1)?;
+        Some(state.sockets.remove(index).socket)
+    }
+}
+
+/// A relay on 127.0.0.1 to the sockets of one reverse connected server, closed when dropped.
+/// Connections already relayed stay open.
+pub struct ReverseRelay {
+    url: String,
+    target: ReverseTarget,
+    task: JoinHandle<()>,
+}
+
+impl ReverseRelay {
+    /// URL to connect to instead of the server's endpoint URL
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn target(&self) -> &ReverseTarget {
+        &self.target
+    }
+}
+
+impl Drop for ReverseRelay {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// A reverse connected server and the listener holding its sockets, kept to relay to the
+/// server again when the session is re-created
+#[derive(Clone, Debug)]
+pub struct ReverseTarget {
+    pub listener: ReverseConnectListener,
+    pub server: ReverseServer,
+}
+
+impl ReverseTarget {
+    pub fn relay(&self) -> Result<ReverseRelay> {
+        self.listener.relay(&self.server)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn socket_pair() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let connect = TcpStream::connect(listener.local_addr().unwrap());
+        let (connected, accepted) = tokio::join!(connect, listener.accept());
+        (connected.unwrap(), accepted.unwrap().0)
+    }
+
+    fn hello(endpoint_url: &str) -> Vec<u8> {
+        let mut message = b"HELF".to_vec();
+        message.extend_from_slice(&[0; 4]);
+        for limit in [0u32, 65535, 65535, 0, 0] {
+            message.extend_from_slice(&limit.to_le_bytes());
+        }
+        message.extend_from_slice(&(endpoint_url.len() as i32).to_le_bytes());
+        message.extend_from_slice(endpoint_url.as_bytes());
+        let size = message.len() as u32;
+        message[4..8].copy_from_slice(&size.to_le_bytes());
+        message
+    }
+
+    #[tokio::test]
+    async fn hello_gets_the_announced_endpoint_url() {
+        let (mut client, mut relay_client) = socket_pair().await;
+        let (mut relay_server, mut server) = socket_pair().await;
+
+        client
+            .write_all(&hello("opc.tcp://127.0.0.1:40000/UA/Plant"))
+            .await
+            .unwrap();
+        ReverseConnectListener::forward_hello(
+            &mut relay_client,
+            &mut relay_server,
+            "opc.tcp://plant-server:4840/UA/Plant",
+        )
+        .await
+        .unwrap();
+
+        let expected = hello("opc.tcp://plant-server:4840/UA/Plant");
+        let mut received = vec![0u8; expected.len()];
+        server.read_exact(&mut received).await.unwrap();
+        assert_eq!(received, expected);
+    }
+
+    #[tokio::test]
+    async fn other_messages_are_not_forwarded() {
+        let (mut client, mut relay_client) = socket_pair().await;
+        let (mut relay_server, _server) = socket_pair().await;
+
+        client
+            .write_all(b"MSGF\x10\0\0\0\0\0\0\0\0\0\0\0")
+            .await
+            .unwrap();
+        let result =
+            ReverseConnectListener::forward_hello(&mut relay_client, &mut relay_server, "x").await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,176 @@
+use super::types::{ArrayViewState, NodeType};
+use crate::client::ConnectionStatus;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+
+impl super::BrowseScreen {
+    /// Open the array viewer for the selected Variable unless it is known to be scalar
+    pub async fn open_array_view(&mut self) {
+        let Some(node) = self.tree_nodes.get(self.selected_node_index) else {
+            return;
+        };
+        if !matches!(node.node_type, NodeType::Variable) {
+            return;
+        }
+        let Some(node_id) = node.opcua_node_id.clone() else {
+            log::warn!("array: no real NodeId available for selected node");
+            return;
+        };
+
+        // ValueRank -1 means Scalar; -2 (Any) and -3 (ScalarOrOneDimension) may hold arrays
+        let scalar = self
+            .selected_attributes
+            .iter()
+            .any(|attr| attr.name == "ValueRank" && attr.value == "-1");
+        if scalar {
+            log::info!("array: node '{}' has a scalar value", node.name);
+            return;
+        }
+
+        self.array_view = Some(ArrayViewState {
+            node_id,
+            node_name: node.name.clone(),
+            range_input: Input::default(),
+            range_focused: false,
+            value_type: String::new(),
+            dimensions: Vec::new(),
+            offsets: Vec::new(),
+            elements: Vec::new(),
+            selected_row: 0,
+            page_size: 20,
+            status_message: None,
+        });
+        self.array_view_open = true;
+
+        self.read_array_elements().await;
+    }
+
+    pub async fn handle_array_input(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Result<Option<ConnectionStatus>> {
+        let Some(state) = &mut self.array_view else {
+            self.array_view_open = false;
+            return Ok(None);
+        };
+
+        let last_row = state.elements.len().saturating_sub(1);
+        match key {
+            KeyCode::Esc => {
+                self.array_view_open = false;
+                self.array_view = None;
+            }
+            KeyCode::Tab | KeyCode::BackTab => {
+                state.range_focused = !state.range_focused;
+            }
+            KeyCode::Enter => {
+                self.read_array_elements().await;
+            }
+            _ if state.range_focused => {
+                state
+                    .range_input
+                    .handle_event(&crossterm::event::Event::Key(
+                        crossterm::event::KeyEvent::new(key, modifiers),
+                    ));
+            }
+            KeyCode::Up => state.selected_row = state.selected_row.saturating_sub(1),
+            KeyCode::Down => state.selected_row = (state.selected_row + 1).min(last_row),
+            KeyCode::PageUp | KeyCode::Left => {
+                state.selected_row = state.selected_row.saturating_sub(state.page_size);
+            }
+            KeyCode::PageDown | KeyCode::Right => {
+                state.selected_row = (state.selected_row + state.page_size).min(last_row);
+            }
+            KeyCode::Home => state.selected_row = 0,
+            KeyCode::End => state.selected_row = last_row,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    /// Read the whole array, or the slice given in the index range field
+    async fn read_array_elements(&mut self) {
+        let Some(state) = &self.array_view else {
+            return;
+        };
+        let node_id = state.node_id.clone();
+        let index_range = state.range_input.value().trim().to_string();
+
+        let result = {
+            let client_guard = self.client.read().await;
+            client_guard.read_array_value(&node_id, &index_range).await
+        };
+
+        let Some(state) = &mut self.array_view else {
+            return;
+        };
+        match result {
+            Ok(array) => {
+                let shape = array
+                    .dimensions
+                    .iter()
+                    .map(|d| d.to_string())
+                    .collect::<Vec<_>>()
+                    .join("x");
+                let range = if index_range.is_empty() {
+                    String::new()
+                } else {
+                    format!(" from range {index_range}")
+                };
+                state.status_message = Some((
+                    format!(
+                        "{} elements ({shape}){range} - {}",
+                        array.elements.len(),
+                        array.status
+                    ),
+                    true,
+                ));
+                state.value_type = array.value_type;
+                state.offsets = Self::index_range_offsets(&index_range, array.dimensions.len());
+                state.dimensions = array.dimensions;
+                state.elements = array.elements;
+                state.selected_row = 0;
+            }
+            Err(e) => {
+                log::warn!("array: {e}");
+                state.status_message = Some((e.to_string(), false));
+            }
+        }
+    }
+
+    /// First index of each dimension in an index range such as "2:5,0:3"
+    fn index_range_offsets(index_range: &str, dimension_count: usize) -> Vec<u32> {
+        let mut offsets: Vec<u32> = index_range
+            .split(',')
+            .map(|part| {
+                part.split(':')
+                    .next()
+                    .and_then(|start| start.trim().parse().ok())
+                    .unwrap_or(0)
+            })
+            .collect();
+        offsets.resize(dimension_count, 0);
+        offsets
+    }
+
+    /// Index label of the element at `position` in the loaded slice, e.g. "[12]" or "[1][3]"
+    pub(super) fn array_index_label(state: &ArrayViewState, position: usize) -> String {
+        let mut remaining = position;
+        let mut indices = vec![0usize; state.dimensions.len()];
+        // Row-major order: the last index varies fastest
+        for (i, dimension) in state.dimensions.iter().enumerate().rev() {
+            let dimension = (*dimension as usize).max(1);
+            indices[i] = remaining % dimension;
+            remaining /= dimension;
+        }
+
+        indices
+            .iter()
+            .zip(state.offsets.iter())
+            .map(|(index, offset)| format!("[{}]", index + *offset as usize))
+            .collect()
+    }
+}
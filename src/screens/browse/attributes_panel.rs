@@ -0,0 +1,106 @@
+use super::types::AttributesTab;
+use crate::client::ConnectionStatus;
+use anyhow::Result;
+use crossterm::event::KeyCode;
+
+impl super::BrowseScreen {
+    /// Indices of the attribute rows that are not hidden inside a collapsed structure or array
+    pub(super) fn visible_attribute_indices(&self) -> Vec<usize> {
+        let mut visible = Vec::new();
+        let mut collapsed_depth: Option<usize> = None;
+
+        for (i, attr) in self.selected_attributes.iter().enumerate() {
+            if let Some(depth) = collapsed_depth {
+                if attr.depth > depth {
+                    continue;
+                }
+                collapsed_depth = None;
+            }
+            visible.push(i);
+            if self.attribute_has_fields(i) && !self.expanded_attributes.contains(&attr.path) {
+                collapsed_depth = Some(attr.depth);
+            }
+        }
+        visible
+    }
+
+    /// Whether the attribute row at `index` has decoded fields below it
+    pub(super) fn attribute_has_fields(&self, index: usize) -> bool {
+        match (
+            self.selected_attributes.get(index),
+            self.selected_attributes.get(index + 1),
+        ) {
+            (Some(attr), Some(next)) => next.depth > attr.depth,
+            _ => false,
+        }
+    }
+
+    /// Continue to the watch list or event panel, otherwise back to the tree
+    pub(super) fn focus_after_attributes_panel(&mut self) {
+        self.attributes_focused = false;
+        if !self.watch_items.is_empty() {
+            self.watch_focused = true;
+        } else {
+            self.events_focused = self.event_monitor.is_some();
+        }
+    }
+
+    /// Key handling while the attributes panel has focus
+    pub async fn handle_attributes_panel_input(
+        &mut self,
+        key: KeyCode,
+    ) -> Result<Option<ConnectionStatus>> {
+        if self.attributes_tab == AttributesTab::References {
+            return self.handle_references_panel_input(key).await;
+        }
+
+        let visible = self.visible_attribute_indices();
+        let selected = visible.get(self.attribute_selected_index).copied();
+
+        match key {
+            KeyCode::Tab => self.focus_after_attributes_panel(),
+            KeyCode::Char('t') => self.toggle_attributes_tab().await,
+            KeyCode::Up => {
+                self.attribute_selected_index = self.attribute_selected_index.saturating_sub(1);
+            }
+            KeyCode::Down if self.attribute_selected_index + 1 < visible.len() => {
+                self.attribute_selected_index += 1;
+            }
+            KeyCode::PageUp => {
+                self.attribute_selected_index = self.attribute_selected_index.saturating_sub(10);
+            }
+            KeyCode::PageDown => {
+                self.attribute_selected_index =
+                    (self.attribute_selected_index + 10).min(visible.len().saturating_sub(1));
+            }
+            KeyCode::Right | KeyCode::Enter => {
+                if let Some(index) = selected.filter(|i| self.attribute_has_fields(*i)) {
+                    let path = self.selected_attributes[index].path.clone();
+                    self.expanded_attributes.insert(path);
+                }
+            }
+            KeyCode::Left => {
+                let Some(index) = selected else {
+                    return Ok(None);
+                };
+                let attr = &self.selected_attributes[index];
+                if self.expanded_attributes.contains(&attr.path) {
+                    let path = attr.path.clone();
+                    self.expanded_attributes.remove(&path);
+                } else if attr.depth > 0 {
+                    // Jump to the parent field, like collapsing in the tree view
+                    let depth = attr.depth;
+                    if let Some(parent) = visible
+                        .iter()
+                        .take(self.attribute_selected_index)
+                        .rposition(|i| self.selected_attributes[*i].depth < depth)
+                    {
+                        self.attribute_selected_index = parent;
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,311 @@
+use super::types::{EventFilterField, EventMonitor, EventRow};
+use crate::client::{ConditionAction, ConnectionStatus, OpcUaEvent};
+use crate::event_utils::EventUtils;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyModifiers};
+use opcua::types::Variant;
+use tokio::sync::mpsc;
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+
+// Client handle of the single event monitored item
+pub(super) const EVENT_CLIENT_HANDLE: u32 = 1;
+
+// Oldest events are dropped beyond this many
+const MAX_EVENTS: usize = 500;
+
+impl super::BrowseScreen {
+    /// Open the event subscription dialog for the selected node if it is an event notifier
+    pub fn open_event_dialog(&mut self) {
+        let Some(node) = self.tree_nodes.get(self.selected_node_index) else {
+            return;
+        };
+        let Some(node_id) = node.opcua_node_id.clone() else {
+            log::warn!("events: no real NodeId available for selected node");
+            return;
+        };
+
+        // Bit 0 of the EventNotifier attribute is SubscribeToEvents
+        let subscribable = self
+            .selected_attributes
+            .iter()
+            .find(|attr| attr.name == "EventNotifier")
+            .and_then(|attr| attr.value.parse::<u8>().ok())
+            .is_some_and(|notifier| notifier & 0x01 != 0);
+        if !subscribable {
+            log::info!("events: node '{}' is not an event notifier", node.name);
+            return;
+        }
+
+        self.event_dialog_target = Some((node_id, node.name.clone()));
+        self.event_dialog_focus = EventFilterField::Select;
+        self.event_dialog_error = None;
+        self.event_dialog_open = true;
+    }
+
+    pub async fn handle_event_dialog_input(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Result<Option<ConnectionStatus>> {
+        match key {
+            KeyCode::Esc => {
+                self.event_dialog_open = false;
+            }
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                self.event_dialog_focus = match self.event_dialog_focus {
+                    EventFilterField::Select => EventFilterField::Where,
+                    EventFilterField::Where => EventFilterField::Select,
+                };
+            }
+            KeyCode::Enter => {
+                self.start_event_monitor().await;
+            }
+            _ => {
+                let input = match self.event_dialog_focus {
+                    EventFilterField::Select => &mut self.event_select_input,
+                    EventFilterField::Where => &mut self.event_where_input,
+                };
+                input.handle_event(&crossterm::event::Event::Key(
+                    crossterm::event::KeyEvent::new(key, modifiers),
+                ));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Replace any running event monitor with one using the dialog's select and where clauses
+    async fn start_event_monitor(&mut self) {
+        let Some((notifier_id, notifier_name)) = self.event_dialog_target.clone() else {
+            self.event_dialog_open = false;
+            return;
+        };
+
+        let built =
+            EventUtils::parse_select_clause(self.event_select_input.value()).and_then(|columns| {
+                EventUtils::build_event_filter(&columns, self.event_where_input.value())
+                    .map(|filter| (columns, filter))
+            });
+        let (columns, filter) = match built {
+            Ok(built) => built,
+            Err(e) => {
+                self.event_dialog_error = Some(e.to_string());
+                return;
+            }
+        };
+
+        self.stop_event_monitor().await;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let result = {
+            let mut client_guard = self.client.write().await;
+            client_guard
+                .create_event_subscription(&notifier_id, EVENT_CLIENT_HANDLE, &filter, tx)
+                .await
+        };
+
+        match result {
+            Ok(_) => {
+                self.event_monitor = Some(EventMonitor {
+                    notifier_id,
+                    notifier_name,
+                    columns,
+                    filter,
+                    events: Vec::new(),
+                    selected_index: 0,
+                });
+                self.event_message_rx = Some(rx);
+                self.event_dialog_open = false;
+            }
+            Err(e) => {
+                log::error!("events: {e}");
+                self.event_dialog_error = Some(e.to_string());
+            }
+        }
+    }
+
+    async fn stop_event_monitor(&mut self) {
+        if self.event_monitor.is_none() {
+            return;
+        }
+
+        let result = {
+            let mut client_guard = self.client.write().await;
+            client_guard.delete_event_subscription().await
+        };
+        if let Err(e) = result {
+            log::warn!("events: {e}");
+        }
+
+        self.event_monitor = None;
+        self.event_message_rx = None;
+        self.events_focused = false;
+    }
+
+    /// Apply events received since the last tick
+    pub fn process_event_notifications(&mut self) {
+        let (Some(rx), Some(monitor)) = (&mut self.event_message_rx, &mut self.event_monitor)
+        else {
+            return;
+        };
+
+        while let Ok(event) = rx.try_recv() {
+            if event.client_handle != EVENT_CLIENT_HANDLE {
+                continue;
+            }
+            monitor
+                .events
+                .insert(0, Self::format_event_row(monitor.columns.len(), &event));
+            monitor.events.truncate(MAX_EVENTS);
+
+            // Keep the selection on the same event while the user is working with the list
+            if self.events_focused {
+                monitor.selected_index = (monitor.selected_index + 1).min(monitor.events.len() - 1);
+            }
+        }
+    }
+
+    fn format_event_row(column_count: usize, event: &OpcUaEvent) -> EventRow {
+        let fields = (0..column_count)
+            .map(|i| {
+                event
+                    .fields
+                    .get(i)
+                    .map(EventUtils::format_event_field)
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        // EventId and ConditionId are appended after the user's columns
+        let event_id = match event.fields.get(column_count) {
+            Some(Variant::ByteString(event_id)) if !event_id.is_null() => Some(event_id.clone()),
+            _ => None,
+        };
+        let condition_id = match event.fields.get(column_count + 1) {
+            Some(Variant::NodeId(condition_id)) if !condition_id.is_null() => {
+                Some((**condition_id).clone())
+            }
+            _ => None,
+        };
+
+        EventRow {
+            fields,
+            event_id,
+            condition_id,
+        }
+    }
+
+    /// Key handling while the event panel has focus
+    pub async fn handle_events_panel_input(
+        &mut self,
+        key: KeyCode,
+    ) -> Result<Option<ConnectionStatus>> {
+        let Some(monitor) = &mut self.event_monitor else {
+            self.events_focused = false;
+            return Ok(None);
+        };
+
+        match key {
+            KeyCode::Tab => {
+                self.events_focused = false;
+            }
+            KeyCode::Up => {
+                monitor.selected_index = monitor.selected_index.saturating_sub(1);
+            }
+            KeyCode::Down if monitor.selected_index + 1 < monitor.events.len() => {
+                monitor.selected_index += 1;
+            }
+            KeyCode::Char('a') => self.open_condition_dialog(ConditionAction::Acknowledge),
+            KeyCode::Char('c') => self.open_condition_dialog(ConditionAction::Confirm),
+            KeyCode::Char('m') => self.open_condition_dialog(ConditionAction::AddComment),
+            KeyCode::Char('x') => {
+                monitor.events.clear();
+                monitor.selected_index = 0;
+            }
+            KeyCode::Delete => {
+                self.stop_event_monitor().await;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn open_condition_dialog(&mut self, action: ConditionAction) {
+        let Some(row) = self
+            .event_monitor
+            .as_ref()
+            .and_then(|monitor| monitor.events.get(monitor.selected_index))
+        else {
+            return;
+        };
+        if row.condition_id.is_none() || row.event_id.is_none() {
+            log::info!("events: the selected event is not a condition");
+            return;
+        }
+
+        self.condition_action = action;
+        self.condition_comment_input = Input::default();
+        self.condition_status = None;
+        self.condition_dialog_open = true;
+    }
+
+    pub async fn handle_condition_input(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Result<Option<ConnectionStatus>> {
+        match key {
+            KeyCode::Esc => {
+                self.condition_dialog_open = false;
+            }
+            KeyCode::Enter => {
+                self.execute_condition_action().await;
+            }
+            _ => {
+                self.condition_comment_input
+                    .handle_event(&crossterm::event::Event::Key(
+                        crossterm::event::KeyEvent::new(key, modifiers),
+                    ));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn execute_condition_action(&mut self) {
+        let Some((condition_id, event_id)) = self
+            .event_monitor
+            .as_ref()
+            .and_then(|monitor| monitor.events.get(monitor.selected_index))
+            .and_then(|row| Some((row.condition_id.clone()?, row.event_id.clone()?)))
+        else {
+            self.condition_dialog_open = false;
+            return;
+        };
+        let action = self.condition_action;
+
+        let result = {
+            let client_guard = self.client.read().await;
+            client_guard
+                .call_condition_method(
+                    &condition_id,
+                    action,
+                    event_id,
+                    self.condition_comment_input.value(),
+                )
+                .await
+        };
+
+        match result {
+            Ok(status) if status.is_good() => {
+                log::info!("events: {} on {condition_id} succeeded", action.label());
+                self.condition_dialog_open = false;
+            }
+            Ok(status) => {
+                self.condition_status = Some(format!("{} failed: {status}", action.label()));
+            }
+            Err(e) => {
+                self.condition_status = Some(format!("{} failed: {e}", action.label()));
+            }
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use super::types::{HistoryField, HistoryRow, HistoryViewState, NodeType};
+use crate::client::{ConnectionStatus, HistoryQuery, HistoryReadMode};
+use crate::export_utils::ExportUtils;
+use crate::value_utils::ValueUtils;
+use anyhow::{anyhow, Result};
+use crossterm::event::{KeyCode, KeyModifiers};
+use opcua::types::{DataValue, DateTime};
+use std::str::FromStr;
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+
+// Number of raw values requested per HistoryRead page
+const HISTORY_PAGE_SIZE: u32 = 200;
+
+impl super::BrowseScreen {
+    /// Open the history viewer for the selected Variable if it is historizing
+    pub fn open_history_view(&mut self) {
+        let Some(node) = self.tree_nodes.get(self.selected_node_index) else {
+            return;
+        };
+        if !matches!(node.node_type, NodeType::Variable) {
+            return;
+        }
+        let Some(node_id) = node.opcua_node_id.clone() else {
+            log::warn!("history: no real NodeId available for selected node");
+            return;
+        };
+
+        // The attributes panel already holds the Historizing flag of the selection
+        let historizing = self
+            .selected_attributes
+            .iter()
+            .any(|attr| attr.name == "Historizing" && attr.value == "true");
+        if !historizing {
+            log::info!("history: node '{}' is not historizing", node.name);
+            return;
+        }
+
+        let now = chrono::Utc::now();
+        let start = now - chrono::Duration::hours(1);
+        let time_format = "%Y-%m-%dT%H:%M:%SZ";
+
+        self.history_view = Some(HistoryViewState {
+            node_id,
+            node_name: node.name.clone(),
+            mode: HistoryReadMode::Raw,
+            start_input: Input::new(start.format(time_format).to_string()),
+            end_input: Input::new(now.format(time_format).to_string()),
+            interval_input: Input::new("60000".to_string()),
+            focus: HistoryField::StartTime,
+            rows: Vec::new(),
+            selected_row: 0,
+            query: None,
+            continuation_point: None,
+            status_message: None,
+        });
+        self.history_view_open = true;
+    }
+
+    pub async fn close_history_view(&mut self) {
+        self.release_history_continuation_point().await;
+        self.history_view_open = false;
+        self.history_view = None;
+    }
+
+    pub async fn handle_history_input(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Result<Option<ConnectionStatus>> {
+        let Some(state) = &mut self.history_view else {
+            self.history_view_open = false;
+            return Ok(None);
+        };
+
+        match (key, &state.focus) {
+            (KeyCode::Esc, _) => {
+                self.close_history_view().await;
+            }
+            (KeyCode::Tab, _) => {
+                state.focus = match state.focus {
+                    HistoryField::StartTime => HistoryField::EndTime,
+                    HistoryField::EndTime => HistoryField::Mode,
+                    HistoryField::Mode => HistoryField::Interval,
+                    HistoryField::Interval => HistoryField::Table,
+                    HistoryField::Table => HistoryField::StartTime,
+                };
+            }
+            (KeyCode::BackTab, _) => {
+                state.focus = match state.focus {
+                    HistoryField::StartTime => HistoryField::Table,
+                    HistoryField::EndTime => HistoryField::StartTime,
+                    HistoryField::Mode => HistoryField::EndTime,
+                    HistoryField::Interval => HistoryField::Mode,
+                    HistoryField::Table => HistoryField::Interval,
+                };
+            }
+            (KeyCode::Enter, _) => {
+                self.run_history_query().await;
+            }
+            (KeyCode::Left | KeyCode::Right, HistoryField::Mode) => {
+                let modes = HistoryReadMode::ALL;
+                let current = modes.iter().position(|m| *m == state.mode).unwrap_or(0);
+                let next = if key == KeyCode::Right {
+                    (current + 1) % modes.len()
+                } else {
+                    (current + modes.len() - 1) % modes.len()
+                };
+                state.mode = modes[next];
+            }
+            (_, HistoryField::Table) => match key {
+                KeyCode::Up => state.selected_row = state.selected_row.saturating_sub(1),
+                KeyCode::Down => {
+                    state.selected_row =
+                        (state.selected_row + 1).min(state.rows.len().saturating_sub(1));
+                }
+                KeyCode::PageUp => state.selected_row = state.selected_row.saturating_sub(10),
+                KeyCode::PageDown => {
+                    state.selected_row =
+                        (state.selected_row + 10).min(state.rows.len().saturating_sub(1));
+                }
+                KeyCode::Home => state.selected_row = 0,
+                KeyCode::End => state.selected_row = state.rows.len().saturating_sub(1),
+                KeyCode::Char('n') => self.load_next_history_page().await,
+                KeyCode::Char('e') => self.export_history_csv(),
+                _ => {}
+            },
+            (_, HistoryField::Mode) => {}
+            (_, field) => {
+                let input = match field {
+                    HistoryField::StartTime => &mut state.start_input,
+                    HistoryField::EndTime => &mut state.end_input,
+                    _ => &mut state.interval_input,
+                };
+                input.handle_event(&crossterm::event::Event::Key(
+                    crossterm::event::KeyEvent::new(key, modifiers),
+                ));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Start a new history read from the time range and mode in the form
+    async fn run_history_query(&mut self) {
+        let query = match self.history_view.as_ref().map(Self::build_history_query) {
+            Some(Ok(query)) => query,
+            Some(Err(e)) => {
+                self.set_history_status(e.to_string(), false);
+                return;
+            }
+            None => return,
+        };
+
+        // A new query abandons any page the server still holds for the previous one
+        self.release_history_continuation_point().await;
+
+        let Some(state) = &mut self.history_view else {
+            return;
+        };
+        state.rows.clear();
+        state.selected_row = 0;
+        state.query = Some(query);
+        state.continuation_point = None;
+
+        self.load_next_history_page().await;
+    }
+
+    async fn load_next_history_page(&mut self) {
+        let Some(state) = &self.history_view else {
+            return;
+        };
+        let Some(query) = state.query.clone() else {
+            return;
+        };
+        // Only the first page is read without a continuation point
+        if !state.rows.is_empty() && state.continuation_point.is_none() {
+            self.set_history_status("No more values".to_string(), true);
+            return;
+        }
+        let node_id = state.node_id.clone();
+        let continuation_point = state.continuation_point.clone();
+
+        let result = {
+            let client_guard = self.client.read().await;
+            client_guard
+                .history_read(&node_id, &query, continuation_point)
+                .await
+        };
+
+        let Some(state) = &mut self.history_view else {
+            return;
+        };
+        match result {
+            Ok(page) => {
+                state
+                    .rows
+                    .extend(page.values.iter().map(Self::format_history_row));
+                state.continuation_point = page.continuation_point;
+                let more = if state.continuation_point.is_some() {
+                    " - press n for more"
+                } else {
+                    ""
+                };
+                let message = format!("{} values loaded{more}", state.rows.len());
+                self.set_history_status(message, true);
+            }
+            Err(e) => {
+                state.continuation_point = None;
+                self.set_history_status(e.to_string(), false);
+            }
+        }
+    }
+
+    async fn release_history_continuation_point(&mut self) {
+        let Some(state) = &mut self.history_view else {
+            return;
+        };
+        let (Some(query), Some(continuation_point)) =
+            (state.query.clone(), state.continuation_point.take())
+        else {
+            return;
+        };
+        let node_id = state.node_id.clone();
+
+        let client_guard = self.client.read().await;
+        if let Err(e) = client_guard
+            .release_history_continuation_point(&node_id, &query, continuation_point)
+            .await
+        {
+            log::warn!("history: failed to release continuation point: {e}");
+        }
+    }
+
+    fn export_history_csv(&mut self) {
+        let Some(state) = &self.history_view else {
+            return;
+        };
+        if state.rows.is_empty() {
+            self.set_history_status("Nothing to export".to_string(), false);
+            return;
+        }
+
+        let path = ExportUtils::timestamped_file_name("history", &state.node_name, "csv");
+        let rows: Vec<Vec<String>> = state
+            .rows
+            .iter()
+            .map(|row| {
+                vec![
+                    row.source_timestamp.clone(),
+                    row.server_timestamp.clone(),
+                    row.value.clone(),
+                    row.status.clone(),
+                ]
+            })
+            .collect();
+
+        match ExportUtils::write_csv(
+            &path,
+            &["SourceTimestamp", "ServerTimestamp", "Value", "Status"],
+            &rows,
+        ) {
+            Ok(()) => {
+                log::info!(
+                    "history: exported {} rows to {}",
+                    rows.len(),
+                    path.display()
+                );
+                self.set_history_status(format!("Exported to {}", path.display()), true);
+            }
+            Err(e) => {
+                log::error!("history: CSV export failed: {e}");
+                self.set_history_status(format!("Export failed: {e}"), false);
+            }
+        }
+    }
+
+    fn build_history_query(state: &HistoryViewState) -> Result<HistoryQuery> {
+        let start_time = DateTime::from_str(state.start_input.value().trim())
+            .map_err(|_| anyhow!("Start time must be RFC 3339, e.g. 2024-01-31T08:00:00Z"))?;
+        let end_time = DateTime::from_str(state.end_input.value().trim())
+            .map_err(|_| anyhow!("End time must be RFC 3339, e.g. 2024-01-31T09:00:00Z"))?;
+
+        let processing_interval = if state.mode == HistoryReadMode::Raw {
+            0.0
+        } else {
+            state
+                .interval_input
+                .value()
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|interval| *interval > 0.0)
+                .ok_or_else(|| anyhow!("Interval must be a positive number of milliseconds"))?
+        };
+
+        Ok(HistoryQuery {
+            mode: state.mode,
+            start_time,
+            end_time,
+            processing_interval,
+            values_per_page: HISTORY_PAGE_SIZE,
+        })
+    }
+
+    fn format_history_row(data_value: &DataValue) -> HistoryRow {
+        let format_timestamp = |timestamp: &Option<DateTime>| {
+            timestamp
+                .as_ref()
+                .map(|t| t.to_string())
+                .unwrap_or_default()
+        };
+
+        HistoryRow {
+            source_timestamp: format_timestamp(&data_value.source_timestamp),
+            server_timestamp: format_timestamp(&data_value.server_timestamp),
+            value: data_value
+                .value
+                .as_ref()
+                .map(ValueUtils::format_for_edit)
+                .unwrap_or_else(|| "(null)".to_string()),
+            status: data_value.status().to_string(),
+            is_value_good: data_value.is_valid(),
+        }
+    }
+
+    fn set_history_status(&mut self, message: String, is_good: bool) {
+        if let Some(state) = &mut self.history_view {
+            state.status_message = Some((message, is_good));
+        }
+    }
+}
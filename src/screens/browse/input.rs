@@ -20,6 +20,56 @@ impl super::BrowseScreen {
             return self.handle_search_input(key, modifiers).await;
         }
 
+        // Handle value write dialog input
+        if self.write_dialog_open {
+            return self.handle_write_input(key, modifiers).await;
+        }
+
+        // Handle watch item settings dialog input
+        if self.watch_settings_open {
+            return self.handle_watch_settings_input(key, modifiers).await;
+        }
+
+        // Handle method call dialog input
+        if self.method_dialog_open {
+            return self.handle_method_input(key, modifiers).await;
+        }
+
+        // Handle history viewer input
+        if self.history_view_open {
+            return self.handle_history_input(key, modifiers).await;
+        }
+
+        // Handle array viewer input
+        if self.array_view_open {
+            return self.handle_array_input(key, modifiers).await;
+        }
+
+        // Handle event subscription dialog input
+        if self.event_dialog_open {
+            return self.handle_event_dialog_input(key, modifiers).await;
+        }
+
+        // Handle condition comment dialog input
+        if self.condition_dialog_open {
+            return self.handle_condition_input(key, modifiers).await;
+        }
+
+        // Handle subtree export dialog input
+        if self.export_dialog_open {
+            return self.handle_export_dialog_input(key, modifiers).await;
+        }
+
+        // Handle snapshot compare dialog input
+        if self.diff_dialog_open {
+            return self.handle_diff_dialog_input(key, modifiers).await;
+        }
+
+        // Handle snapshot diff view input
+        if self.diff_view_open {
+            return self.handle_diff_view_input(key, modifiers).await;
+        }
+
         match key {
             KeyCode::F(3) => {
                 // F3: Continue search from current position or open search dialog
@@ -47,17 +97,42 @@ impl super::BrowseScreen {
                     // Cancel search progress
                     self.cancel_search();
                     Ok(None)
+                } else if self.export_progress_open {
+                    // Cancel the running export
+                    self.cancel_export();
+                    Ok(None)
+                } else if self.diff_progress_open {
+                    // Cancel capturing the live server for a compare
+                    self.cancel_diff();
+                    Ok(None)
                 } else if self.search_dialog_open {
                     // Close search dialog first
                     self.close_search_dialog();
                     Ok(None)
+                } else if self.attributes_focused {
+                    // Return focus from the attributes panel to the tree
+                    self.attributes_focused = false;
+                    Ok(None)
+                } else if self.watch_focused {
+                    // Return focus from the watch list to the tree
+                    self.watch_focused = false;
+                    Ok(None)
+                } else if self.events_focused {
+                    // Return focus from the event panel to the tree
+                    self.events_focused = false;
+                    Ok(None)
                 } else {
                     // Disconnect and return to connect screen
                     Ok(Some(ConnectionStatus::Disconnected))
                 }
             }
             // Disable navigation keys when any dialog is open (except F3, Ctrl+F, Esc, q)
-            _ if self.search_dialog_open || self.search_progress_open || self.log_viewer_open => {
+            _ if self.search_dialog_open
+                || self.search_progress_open
+                || self.export_progress_open
+                || self.diff_progress_open
+                || self.log_viewer_open =>
+            {
                 // Allow some keys in log viewer for navigation
                 if self.log_viewer_open {
                     match key {
@@ -104,6 +179,17 @@ impl super::BrowseScreen {
                     Ok(None)
                 }
             }
+            // Attribute row navigation and field expansion while the panel has focus
+            _ if self.attributes_focused => self.handle_attributes_panel_input(key).await,
+            // Watch list navigation while it has focus
+            _ if self.watch_focused => self.handle_watch_list_input(key).await,
+            // Event panel navigation while it has focus
+            _ if self.events_focused => self.handle_events_panel_input(key).await,
+            KeyCode::Tab => {
+                // Move focus to the attributes panel, the first panel after the tree
+                self.attributes_focused = true;
+                Ok(None)
+            }
             KeyCode::Up => {
                 if self.selected_node_index > 0 {
                     self.selected_node_index -= 1;
@@ -124,6 +210,18 @@ impl super::BrowseScreen {
                 }
                 Ok(None)
             }
+            KeyCode::Enter
+                if self
+                    .tree_nodes
+                    .get(self.selected_node_index)
+                    .is_some_and(|node| {
+                        matches!(node.node_type, super::types::NodeType::Method)
+                    }) =>
+            {
+                // Enter on a Method opens the call dialog
+                self.open_method_dialog().await;
+                Ok(None)
+            }
             KeyCode::Right | KeyCode::Enter => {
                 // Expand node if it supports expansion (based on node type) and has children
                 if self.selected_node_index < self.tree_nodes.len() {
@@ -206,6 +304,51 @@ impl super::BrowseScreen {
                 }
                 Ok(None)
             }
+            KeyCode::Char('w') => {
+                // Open the value write dialog for the selected Variable
+                self.open_write_dialog().await;
+                Ok(None)
+            }
+            KeyCode::Char('p') => {
+                // Pin/unpin the selected Variable in the watch list
+                self.toggle_watch_for_selected().await;
+                Ok(None)
+            }
+            KeyCode::Char('h') => {
+                // Open the history viewer for the selected historizing Variable
+                self.open_history_view();
+                Ok(None)
+            }
+            KeyCode::Char('a') => {
+                // Open the array element viewer for the selected Variable
+                self.open_array_view().await;
+                Ok(None)
+            }
+            KeyCode::Char('o') => {
+                // Switch the tree root between Root, Objects, Types and Views
+                self.cycle_browse_root().await;
+                Ok(None)
+            }
+            KeyCode::Char('t') => {
+                // Switch the right panel between attributes and references
+                self.toggle_attributes_tab().await;
+                Ok(None)
+            }
+            KeyCode::Char('e') => {
+                // Subscribe to events of the selected event notifier
+                self.open_event_dialog();
+                Ok(None)
+            }
+            KeyCode::Char('x') => {
+                // Export the subtree of the selected node to JSON, CSV, NodeSet2 or a snapshot
+                self.open_export_dialog();
+                Ok(None)
+            }
+            KeyCode::Char('d') => {
+                // Compare a snapshot with another snapshot or the live server
+                self.open_diff_dialog();
+                Ok(None)
+            }
             KeyCode::Char('r') => {
                 // Refresh/reload real OPC UA data
                 if let Err(e) = self.load_real_tree().await {
@@ -227,8 +370,21 @@ impl super::BrowseScreen {
         dialog_area: Option<Rect>,
         progress_area: Option<Rect>,
     ) -> Result<Option<ConnectionStatus>> {
-        // Disable mouse input when log viewer is open
-        if self.log_viewer_open {
+        // Disable mouse input when log viewer or write dialog is open
+        if self.log_viewer_open
+            || self.write_dialog_open
+            || self.watch_settings_open
+            || self.method_dialog_open
+            || self.history_view_open
+            || self.array_view_open
+            || self.event_dialog_open
+            || self.condition_dialog_open
+            || self.export_dialog_open
+            || self.export_progress_open
+            || self.diff_dialog_open
+            || self.diff_progress_open
+            || self.diff_view_open
+        {
             return Ok(None);
         }
 
@@ -533,31 +689,31 @@ impl super::BrowseScreen {
 
         if has_connection && !self.tree_nodes.is_empty() {
             // Use background recursive search
-            let start_node_id = if let Some(current_node) =
-                self.tree_nodes.get(self.selected_node_index)
-            {
-                // If the current node has an OPC UA node ID, use it
-                if let Some(ref opcua_node_id) = current_node.opcua_node_id {
-                    log::info!(
-                        "search: starting from selected node '{}' ({})",
-                        current_node.name,
-                        opcua_node_id
-                    );
-                    opcua_node_id.clone()
+            let start_node_id =
+                if let Some(current_node) = self.tree_nodes.get(self.selected_node_index) {
+                    // If the current node has an OPC UA node ID, use it
+                    if let Some(ref opcua_node_id) = current_node.opcua_node_id {
+                        log::info!(
+                            "search: starting from selected node '{}' ({})",
+                            current_node.name,
+                            opcua_node_id
+                        );
+                        opcua_node_id.clone()
+                    } else {
+                        log::warn!("search: selected node has no OPC UA node ID, using tree root");
+                        self.browse_root.node_id()
+                    }
                 } else {
-                    log::warn!("search: selected node has no OPC UA node ID, using ObjectsFolder");
-                    opcua::types::ObjectId::ObjectsFolder.into()
-                }
-            } else {
-                log::info!("search: no selected node, starting from ObjectsFolder");
-                opcua::types::ObjectId::ObjectsFolder.into()
-            };
+                    log::info!("search: no selected node, starting from tree root");
+                    self.browse_root.node_id()
+                };
 
             // Start background search
             let options = super::recursive_search::RecursiveSearchOptions {
                 query,
                 include_values: self.search_include_values,
                 start_node_id,
+                root_node_id: self.browse_root.node_id(),
             };
 
             self.start_background_search(options)?;
@@ -690,6 +846,40 @@ impl super::BrowseScreen {
             .iter()
             .position(|node| node.node_id == target_node_id)
     }
+
+    /// Index of `node_id` in the tree, loading further pages of its parent's folder (or of
+    /// the top level when `parent_node_id` is None) until it appears
+    async fn find_node_index_loading_pages(
+        &mut self,
+        node_id: &opcua::types::NodeId,
+        parent_node_id: Option<&opcua::types::NodeId>,
+    ) -> Option<usize> {
+        let node_id_text = node_id.to_string();
+        loop {
+            if let Some(index) = self.find_node_index_by_id(&node_id_text) {
+                return Some(index);
+            }
+
+            let (first_child, child_level) = match parent_node_id {
+                Some(parent_node_id) => {
+                    let parent_index = self
+                        .tree_nodes
+                        .iter()
+                        .position(|node| node.opcua_node_id.as_ref() == Some(parent_node_id))?;
+                    (parent_index + 1, self.tree_nodes[parent_index].level + 1)
+                }
+                None => (0, 0),
+            };
+            let load_more_index = first_child
+                + self.tree_nodes[first_child..]
+                    .iter()
+                    .take_while(|node| node.level >= child_level)
+                    .position(|node| node.level == child_level && node.is_load_more())?;
+            if !self.load_more_children(load_more_index).await {
+                return None;
+            }
+        }
+    }
     pub async fn expand_to_find_node(&mut self, target_node_id: &str) -> Result<()> {
         log::info!("search: navigating to search result {target_node_id}");
 
@@ -723,25 +913,33 @@ impl super::BrowseScreen {
         if let Some(path_to_target) = self.find_path_to_node(&target_opcua_node_id).await? {
             log::info!("search: found path to target node: {path_to_target:?}");
 
-            // Filter out the Objects node (i=85) since it's not displayed in the tree
-            let objects_node_id = opcua::types::NodeId::new(0, 85u32);
+            // Filter out the tree root since it's not displayed in the tree
+            let root_node_id = self.browse_root.node_id();
             let filtered_path: Vec<_> = path_to_target
                 .into_iter()
-                .filter(|node_id| *node_id != objects_node_id)
+                .filter(|node_id| *node_id != root_node_id)
                 .collect();
 
-            log::info!("search: filtered path (excluding Objects node): {filtered_path:?}");
+            log::info!("search: filtered path (excluding tree root): {filtered_path:?}");
 
             // Expand nodes along the filtered path to make the target visible
+            let mut parent_node_id: Option<opcua::types::NodeId> = None;
             for ancestor_node_id in filtered_path {
+                // The ancestor may sit on a page of its folder that is not loaded yet
+                self.find_node_index_loading_pages(&ancestor_node_id, parent_node_id.as_ref())
+                    .await;
                 if let Err(e) = self.expand_node_by_opcua_id(&ancestor_node_id).await {
                     log::error!("search: failed to expand ancestor node {ancestor_node_id}: {e}");
                     // Continue trying to expand other ancestors
                 }
+                parent_node_id = Some(ancestor_node_id);
             }
 
             // Now try to find the target node in the expanded tree
-            if let Some(target_index) = self.find_node_index_by_id(target_node_id) {
+            if let Some(target_index) = self
+                .find_node_index_loading_pages(&target_opcua_node_id, parent_node_id.as_ref())
+                .await
+            {
                 log::info!("search: target node now visible at index {target_index}");
                 self.selected_node_index = target_index;
                 self.update_scroll();
@@ -772,16 +970,16 @@ impl super::BrowseScreen {
             return Err(anyhow::anyhow!("OPC UA client is not connected"));
         }
 
-        // Start from the Objects folder (standard starting point)
-        let objects_node_id = opcua::types::NodeId::new(0, 85u32); // Objects folder
+        // Start from the folder the tree is rooted at
+        let root_node_id = self.browse_root.node_id();
 
         // Use breadth-first search to find the path
         let mut queue = VecDeque::new();
         let mut visited = HashSet::new();
         let mut parent_map: HashMap<opcua::types::NodeId, opcua::types::NodeId> = HashMap::new();
 
-        queue.push_back(objects_node_id.clone());
-        visited.insert(objects_node_id.clone());
+        queue.push_back(root_node_id.clone());
+        visited.insert(root_node_id.clone());
 
         while let Some(current_node_id) = queue.pop_front() {
             // Check if we found the target
@@ -1081,8 +1279,10 @@ impl super::BrowseScreen {
                 node_type: node_type.clone(),
                 level: 0, // Not relevant for this check
                 has_children: result.has_children,
+                children_checked: false,
                 is_expanded: false,
                 parent_path: String::new(),
+                continuation_point: None,
             };
 
             // Only include nodes that would actually be displayed in the tree
@@ -1290,30 +1490,30 @@ impl super::BrowseScreen {
 
         if has_connection && !self.tree_nodes.is_empty() {
             // Get the start node for continuing the search
-            let start_node_id = if let Some(current_node) =
-                self.tree_nodes.get(self.selected_node_index)
-            {
-                if let Some(ref opcua_node_id) = current_node.opcua_node_id {
-                    log::info!(
-                        "search: continuing from selected node '{}' ({})",
-                        current_node.name,
-                        opcua_node_id
-                    );
-                    opcua_node_id.clone()
+            let start_node_id =
+                if let Some(current_node) = self.tree_nodes.get(self.selected_node_index) {
+                    if let Some(ref opcua_node_id) = current_node.opcua_node_id {
+                        log::info!(
+                            "search: continuing from selected node '{}' ({})",
+                            current_node.name,
+                            opcua_node_id
+                        );
+                        opcua_node_id.clone()
+                    } else {
+                        log::warn!("search: selected node has no OPC UA node ID, using tree root");
+                        self.browse_root.node_id()
+                    }
                 } else {
-                    log::warn!("search: selected node has no OPC UA node ID, using ObjectsFolder");
-                    opcua::types::ObjectId::ObjectsFolder.into()
-                }
-            } else {
-                log::info!("search: no selected node, starting from ObjectsFolder");
-                opcua::types::ObjectId::ObjectsFolder.into()
-            };
+                    log::info!("search: no selected node, starting from tree root");
+                    self.browse_root.node_id()
+                };
 
             // Start background search from the current position
             let options = super::recursive_search::RecursiveSearchOptions {
                 query,
                 include_values: self.search_include_values,
                 start_node_id,
+                root_node_id: self.browse_root.node_id(),
             };
 
             self.start_background_search(options)?;
@@ -1342,12 +1542,12 @@ impl super::BrowseScreen {
                     );
                     opcua_node_id.clone()
                 } else {
-                    log::warn!("search: selected node has no OPC UA node ID, using ObjectsFolder");
-                    opcua::types::ObjectId::ObjectsFolder.into()
+                    log::warn!("search: selected node has no OPC UA node ID, using tree root");
+                    self.browse_root.node_id()
                 }
             } else {
-                log::info!("search: no selected node, starting from ObjectsFolder");
-                opcua::types::ObjectId::ObjectsFolder.into()
+                log::info!("search: no selected node, starting from tree root");
+                self.browse_root.node_id()
             };
 
         // First, search in the children of the current node (if it has expandable children)
@@ -1376,7 +1576,7 @@ impl super::BrowseScreen {
         log::info!("search: searching remaining tree after node {current_node_id}");
 
         // Start from the root and perform a full DFS, but skip nodes until we're past the current node
-        let root_node_id = opcua::types::ObjectId::ObjectsFolder.into();
+        let root_node_id = self.browse_root.node_id();
 
         // Get all top-level children of the root
         let root_children = self.get_sorted_children(&root_node_id).await?;
@@ -1462,7 +1662,7 @@ impl super::BrowseScreen {
         log::info!("search: wrapping search to beginning of tree");
 
         // Start from the root of the tree
-        let root_node_id = opcua::types::ObjectId::ObjectsFolder.into();
+        let root_node_id = self.browse_root.node_id();
 
         // Update progress message to show wrapping
         self.search_progress_message = format!("Wrapping search for '{query}'...");
@@ -0,0 +1,183 @@
+use super::types::{MethodCallOutcome, MethodCallState, NodeType};
+use crate::client::ConnectionStatus;
+use crate::value_utils::ValueUtils;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyModifiers};
+use opcua::types::CallMethodResult;
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+
+impl super::BrowseScreen {
+    /// Open the method call dialog for the selected Method node
+    pub async fn open_method_dialog(&mut self) {
+        let Some(node) = self.tree_nodes.get(self.selected_node_index) else {
+            return;
+        };
+        if !matches!(node.node_type, NodeType::Method) {
+            return;
+        }
+        let Some(method_id) = node.opcua_node_id.clone() else {
+            log::warn!("method: no real NodeId available for selected node");
+            return;
+        };
+        let method_name = node.name.clone();
+
+        // Prefer the object the method was reached from in the tree
+        let tree_parent = (0..self.selected_node_index)
+            .rev()
+            .map(|i| &self.tree_nodes[i])
+            .find(|parent| parent.level + 1 == node.level)
+            .filter(|parent| matches!(parent.node_type, NodeType::Object))
+            .and_then(|parent| parent.opcua_node_id.clone());
+
+        let client_guard = self.client.read().await;
+        let object_id = match tree_parent {
+            Some(object_id) => object_id,
+            None => match client_guard.find_method_owner(&method_id).await {
+                Ok(object_id) => object_id,
+                Err(e) => {
+                    log::error!("method: {e}");
+                    return;
+                }
+            },
+        };
+
+        let arguments = match client_guard.read_method_arguments(&method_id).await {
+            Ok(arguments) => arguments,
+            Err(e) => {
+                log::error!("method: failed to read arguments of {method_id}: {e}");
+                return;
+            }
+        };
+        drop(client_guard);
+
+        let inputs = arguments
+            .input_arguments
+            .iter()
+            .map(|_| Input::default())
+            .collect();
+        self.method_call = Some(MethodCallState {
+            object_id,
+            method_id,
+            method_name,
+            input_arguments: arguments.input_arguments,
+            output_arguments: arguments.output_arguments,
+            inputs,
+            focused_input: 0,
+            outcome: None,
+        });
+        self.method_dialog_open = true;
+    }
+
+    pub fn close_method_dialog(&mut self) {
+        self.method_dialog_open = false;
+        self.method_call = None;
+    }
+
+    pub async fn handle_method_input(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Result<Option<ConnectionStatus>> {
+        let Some(state) = &mut self.method_call else {
+            self.method_dialog_open = false;
+            return Ok(None);
+        };
+
+        match key {
+            KeyCode::Esc => {
+                self.close_method_dialog();
+            }
+            KeyCode::Enter => {
+                self.execute_method_call().await;
+            }
+            KeyCode::Tab | KeyCode::Down if !state.inputs.is_empty() => {
+                state.focused_input = (state.focused_input + 1) % state.inputs.len();
+            }
+            KeyCode::BackTab | KeyCode::Up if !state.inputs.is_empty() => {
+                state.focused_input =
+                    (state.focused_input + state.inputs.len() - 1) % state.inputs.len();
+            }
+            _ => {
+                if let Some(input) = state.inputs.get_mut(state.focused_input) {
+                    input.handle_event(&crossterm::event::Event::Key(
+                        crossterm::event::KeyEvent::new(key, modifiers),
+                    ));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    async fn execute_method_call(&mut self) {
+        let Some(state) = &self.method_call else {
+            return;
+        };
+
+        let client_guard = self.client.read().await;
+        let inputs: Vec<&str> = state.inputs.iter().map(Input::value).collect();
+        let input_values = match client_guard
+            .parse_method_inputs(&state.input_arguments, &inputs)
+            .await
+        {
+            Ok(values) => values,
+            Err(e) => {
+                if let Some(state) = &mut self.method_call {
+                    state.outcome = Some(MethodCallOutcome {
+                        status: format!("Invalid input: {e}"),
+                        is_good: false,
+                        lines: Vec::new(),
+                    });
+                }
+                return;
+            }
+        };
+        let result = client_guard
+            .call_method(&state.object_id, &state.method_id, input_values)
+            .await;
+
+        if let Some(state) = &mut self.method_call {
+            state.outcome = Some(match result {
+                Ok(result) => Self::format_call_result(state, &result),
+                Err(e) => MethodCallOutcome {
+                    status: format!("Call failed: {e}"),
+                    is_good: false,
+                    lines: Vec::new(),
+                },
+            });
+        }
+    }
+
+    fn format_call_result(state: &MethodCallState, result: &CallMethodResult) -> MethodCallOutcome {
+        let mut lines = Vec::new();
+
+        let outputs = result.output_arguments.clone().unwrap_or_default();
+        for (i, value) in outputs.iter().enumerate() {
+            let name = state
+                .output_arguments
+                .get(i)
+                .map(|argument| argument.name.as_ref().to_string())
+                .unwrap_or_else(|| format!("Output {i}"));
+            lines.push((name, ValueUtils::format_for_edit(value)));
+        }
+
+        // Only list per-argument results when the server reports a problem with an input
+        let input_results = result.input_argument_results.clone().unwrap_or_default();
+        if input_results.iter().any(|status| !status.is_good()) {
+            for (i, status) in input_results.iter().enumerate() {
+                let name = state
+                    .input_arguments
+                    .get(i)
+                    .map(|argument| argument.name.as_ref().to_string())
+                    .unwrap_or_else(|| format!("Input {i}"));
+                lines.push((format!("{name} (input)"), status.to_string()));
+            }
+        }
+
+        MethodCallOutcome {
+            status: result.status_code.to_string(),
+            is_good: result.status_code.is_good(),
+            lines,
+        }
+    }
+}
@@ -1,8 +1,28 @@
+mod array_view;
+mod attributes_panel;
+mod event_monitor;
+mod history_view;
 mod input;
+mod method_call;
 mod navigation;
 mod real_data;
 mod recursive_search;
+mod references_panel;
 mod render;
+mod render_array;
+mod render_diff;
+mod render_events;
+mod render_export;
+mod render_history;
+mod render_method;
+mod render_references;
+mod render_watch;
+mod render_write;
+mod session_recovery;
+mod snapshot_diff;
+mod subtree_export;
 pub mod types;
+mod value_write;
+mod watch_list;
 
 pub use types::BrowseScreen;
@@ -73,7 +73,8 @@ impl super::BrowseScreen {
         }
 
         // Remove children from visual tree (but keep their expansion state for restoration)
-        self.tree_nodes.drain(index + 1..end_index);
+        let removed: Vec<TreeNode> = self.tree_nodes.drain(index + 1..end_index).collect();
+        self.discard_tree_nodes(removed);
 
         // Ensure selected index is valid
         if self.selected_node_index >= self.tree_nodes.len() {
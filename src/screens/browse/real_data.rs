@@ -1,25 +1,28 @@
-use super::types::{NodeAttribute, NodeType, TreeNode};
+use super::types::{AttributesTab, NodeAttribute, NodeType, TreeNode};
+use crate::client::{BrowseRoot, OpcUaBrowsePage};
 use anyhow::Result;
-use opcua::types::{NodeClass, NodeId};
+use opcua::types::{ByteString, NodeClass, NodeId};
+use std::collections::HashSet;
 
 impl super::BrowseScreen {
     pub async fn load_real_tree(&mut self) -> Result<()> {
         self.is_loading = true;
 
         // Clear existing nodes
-        self.tree_nodes.clear();
+        let old_nodes = std::mem::take(&mut self.tree_nodes);
+        self.discard_tree_nodes(old_nodes);
         self.selected_node_index = 0;
         self.expanded_nodes.clear();
 
         // Add timeout to the entire loading process
         let load_future = async {
-            // Get the root node (Objects folder)
+            // Get the root node of the selected root folder
             let client_guard = self.client.read().await;
             if !client_guard.is_connected() {
                 return Ok(Vec::new());
             }
 
-            let root_node_id = client_guard.get_root_node().await?;
+            let root_node_id = client_guard.get_root_node(self.browse_root).await?;
             drop(client_guard);
 
             // Load the root level nodes
@@ -42,6 +45,30 @@ impl super::BrowseScreen {
         self.is_loading = false;
         Ok(())
     }
+    /// Switch the tree to the next root folder (Root, Objects, Types, Views) and reload it
+    pub async fn cycle_browse_root(&mut self) {
+        let roots = BrowseRoot::ALL;
+        let current = roots
+            .iter()
+            .position(|root| *root == self.browse_root)
+            .unwrap_or(0);
+        self.browse_root = roots[(current + 1) % roots.len()];
+        log::info!(
+            "browse: switching tree root to {}",
+            self.browse_root.label()
+        );
+
+        if let Err(e) = self.load_real_tree().await {
+            log::error!("browse: failed to load real OPC UA data: {e}");
+        }
+        self.scroll_offset = 0;
+        if let Err(e) = self.update_selected_attributes_async().await {
+            log::error!("browse: failed to update attributes: {e}");
+        }
+    }
+
+    /// Children of a node as tree rows. Only the first page the server returns is loaded; a
+    /// "load more" row at the end holds the continuation point for the rest.
     pub async fn get_real_children(
         &self,
         parent_node_id: &NodeId,
@@ -53,10 +80,189 @@ impl super::BrowseScreen {
             return Ok(Vec::new());
         }
 
-        let opcua_nodes = client_guard.browse_node(parent_node_id).await?;
+        let page = client_guard.browse_node_page(parent_node_id).await?;
         drop(client_guard);
+        Ok(Self::tree_nodes_from_page(page, level, parent_path, 0))
+    }
+
+    /// Replace the "load more" row at `index` with the next page of its folder. When BrowseNext
+    /// fails, e.g. because the server recycled the continuation point, the folder is browsed
+    /// again from the start and the rows already loaded are skipped. Returns false when that
+    /// failed too; the row then stays as a retry row.
+    pub async fn load_more_children(&mut self, index: usize) -> bool {
+        let Some(continuation_point) = self
+            .tree_nodes
+            .get_mut(index)
+            .and_then(|node| node.continuation_point.take())
+        else {
+            return false;
+        };
+        let level = self.tree_nodes[index].level;
+        let parent_path = self.tree_nodes[index].parent_path.clone();
+        let loaded = self.loaded_sibling_count(index);
+
+        let mut result = Err(anyhow::anyhow!("no continuation point"));
+        if !continuation_point.is_null() {
+            let client_guard = self.client.read().await;
+            result = client_guard.browse_next_page(continuation_point).await;
+        }
+        if let Err(e) = &result {
+            log::warn!("browse: failed to load more children ({e}), browsing the folder again");
+            result = self.browse_remaining_children(index).await;
+        }
+
+        let loaded_more = match result {
+            Ok(page) => {
+                let nodes = Self::tree_nodes_from_page(page, level, &parent_path, loaded);
+                self.tree_nodes.splice(index..index + 1, nodes);
+                true
+            }
+            Err(e) => {
+                log::error!("browse: failed to load more children: {e}");
+                let row = &mut self.tree_nodes[index];
+                row.name = format!("… {loaded} loaded, loading more failed - select to retry");
+                row.continuation_point = Some(ByteString::null());
+                false
+            }
+        };
+        if self.selected_node_index >= self.tree_nodes.len() {
+            self.selected_node_index = self.tree_nodes.len().saturating_sub(1);
+        }
+        loaded_more
+    }
+
+    /// Browse the folder of the "load more" row at `index` from the first page, up to the
+    /// first page with rows that are not in the tree yet, and keep only those rows
+    async fn browse_remaining_children(&self, index: usize) -> Result<OpcUaBrowsePage> {
+        let level = self.tree_nodes[index].level;
+        let parent_node_id = match level.checked_sub(1) {
+            Some(parent_level) => self.tree_nodes[..index]
+                .iter()
+                .rev()
+                .find(|node| node.level == parent_level)
+                .and_then(|node| node.opcua_node_id.clone())
+                .ok_or_else(|| anyhow::anyhow!("parent of the folder not found"))?,
+            None => {
+                let client_guard = self.client.read().await;
+                client_guard.get_root_node(self.browse_root).await?
+            }
+        };
+        let parent_path = &self.tree_nodes[index].parent_path;
+        let loaded: HashSet<&NodeId> = self
+            .tree_nodes
+            .iter()
+            .filter(|node| node.level == level && &node.parent_path == parent_path)
+            .filter_map(|node| node.opcua_node_id.as_ref())
+            .collect();
+
+        let client_guard = self.client.read().await;
+        let mut page = client_guard.browse_node_page(&parent_node_id).await?;
+        loop {
+            page.nodes.retain(|node| !loaded.contains(&node.node_id));
+            let Some(continuation_point) = page.continuation_point.take() else {
+                return Ok(page);
+            };
+            if !page.nodes.is_empty() {
+                page.continuation_point = Some(continuation_point);
+                return Ok(page);
+            }
+            page = client_guard.browse_next_page(continuation_point).await?;
+        }
+    }
+
+    /// Confirm `has_children` of the rendered rows whose expand arrow is still guessed
+    pub async fn check_visible_children(&mut self) {
+        let end = self
+            .tree_nodes
+            .len()
+            .min(self.scroll_offset + self.current_visible_height);
+        let mut indices = Vec::new();
+        let mut node_ids = Vec::new();
+        for index in self.scroll_offset.min(end)..end {
+            let node = &mut self.tree_nodes[index];
+            if node.children_checked {
+                continue;
+            }
+            node.children_checked = true;
+            if let (true, Some(node_id)) =
+                (node.should_show_expand_indicator(), &node.opcua_node_id)
+            {
+                indices.push(index);
+                node_ids.push(node_id.clone());
+            }
+        }
+        if node_ids.is_empty() {
+            return;
+        }
+
+        let result = {
+            let client_guard = self.client.read().await;
+            client_guard.detect_children(&node_ids).await
+        };
+        match result {
+            Ok(has_children) => {
+                for (index, has_children) in indices.into_iter().zip(has_children) {
+                    if let Some(has_children) = has_children {
+                        self.tree_nodes[index].has_children = has_children;
+                    }
+                }
+            }
+            Err(e) => log::debug!("browse: failed to check for child nodes: {e}"),
+        }
+    }
+
+    /// Number of loaded rows in the folder of the row at `index`, excluding its "load more" row
+    pub fn loaded_sibling_count(&self, index: usize) -> usize {
+        let Some(node) = self.tree_nodes.get(index) else {
+            return 0;
+        };
+        self.tree_nodes
+            .iter()
+            .filter(|sibling| {
+                sibling.level == node.level
+                    && sibling.parent_path == node.parent_path
+                    && !sibling.is_load_more()
+            })
+            .count()
+    }
+
+    /// Release continuation points of "load more" rows that were removed from the tree
+    pub async fn release_stale_continuation_points(&mut self) {
+        if self.stale_continuation_points.is_empty() {
+            return;
+        }
+        let continuation_points = std::mem::take(&mut self.stale_continuation_points);
+
+        let client_guard = self.client.read().await;
+        for continuation_point in continuation_points {
+            if let Err(e) = client_guard
+                .release_browse_continuation_point(continuation_point)
+                .await
+            {
+                log::debug!("browse: failed to release continuation point: {e}");
+            }
+        }
+    }
+
+    /// Remember the continuation points of "load more" rows in `nodes` for release
+    pub fn discard_tree_nodes(&mut self, nodes: impl IntoIterator<Item = TreeNode>) {
+        self.stale_continuation_points.extend(
+            nodes
+                .into_iter()
+                .filter_map(|node| node.continuation_point)
+                // Retry rows have no continuation point on the server
+                .filter(|continuation_point| !continuation_point.is_null()),
+        );
+    }
+
+    fn tree_nodes_from_page(
+        page: OpcUaBrowsePage,
+        level: usize,
+        parent_path: &str,
+        loaded_before: usize,
+    ) -> Vec<TreeNode> {
         let mut tree_nodes = Vec::new();
-        for opcua_node in opcua_nodes {
+        for opcua_node in page.nodes {
             let node_type = match opcua_node.node_class {
                 NodeClass::Object => NodeType::Object,
                 NodeClass::Variable => NodeType::Variable,
@@ -82,10 +288,12 @@ impl super::BrowseScreen {
                 node_type,
                 level,
                 has_children: opcua_node.has_children,
+                children_checked: false,
                 is_expanded: false,
                 parent_path: parent_path.to_string(),
+                continuation_point: None,
             });
-        } // Sort nodes by type priority, then by name
+        } // Sort nodes by type priority, then by name (each page is sorted on its own)
         tree_nodes.sort_by(|a, b| {
             let type_order_a = a.node_type.get_sort_priority();
             let type_order_b = b.node_type.get_sort_priority();
@@ -98,7 +306,23 @@ impl super::BrowseScreen {
                 other => other,
             }
         });
-        Ok(tree_nodes)
+
+        if let Some(continuation_point) = page.continuation_point {
+            let loaded = loaded_before + tree_nodes.len();
+            tree_nodes.push(TreeNode {
+                name: format!("… {loaded} loaded, select to load more"),
+                node_id: String::new(),
+                opcua_node_id: None,
+                node_type: NodeType::Object,
+                level,
+                has_children: false,
+                children_checked: true,
+                is_expanded: false,
+                parent_path: parent_path.to_string(),
+                continuation_point: Some(continuation_point),
+            });
+        }
+        tree_nodes
     }
 
     // Improved expand method for real OPC UA data
@@ -171,14 +395,29 @@ impl super::BrowseScreen {
 
             match client_guard.read_node_attributes(&opcua_node_id).await {
                 Ok(opcua_attributes) => {
+                    // Decoded fields follow their parent depth-first, so a stack of names gives each row's path
+                    let mut parents: Vec<String> = Vec::new();
                     self.selected_attributes = opcua_attributes
                         .into_iter()
-                        .map(|attr| NodeAttribute {
-                            name: attr.name,
-                            value: attr.value,
-                            is_value_good: attr.is_value_good,
+                        .map(|attr| {
+                            parents.truncate(attr.depth);
+                            let path = if parents.is_empty() {
+                                attr.name.clone()
+                            } else {
+                                format!("{}/{}", parents.join("/"), attr.name)
+                            };
+                            parents.push(attr.name.clone());
+                            NodeAttribute {
+                                name: attr.name,
+                                value: attr.value,
+                                is_value_good: attr.is_value_good,
+                                depth: attr.depth,
+                                path,
+                            }
                         })
                         .collect();
+                    self.attribute_selected_index = 0;
+                    self.attribute_scroll_offset = 0;
                 }
                 Err(e) => {
                     log::error!("browse: failed to read node attributes: {e}");
@@ -197,6 +436,15 @@ impl super::BrowseScreen {
             return Ok(());
         }
 
+        // Selecting the "load more" row of a folder fetches its next page in place
+        if self.tree_nodes[self.selected_node_index].is_load_more() {
+            self.load_more_children(self.selected_node_index).await;
+            if self.selected_node_index >= self.tree_nodes.len() {
+                self.selected_attributes.clear();
+                return Ok(());
+            }
+        }
+
         let has_real_node_id = self.tree_nodes[self.selected_node_index]
             .opcua_node_id
             .is_some();
@@ -204,6 +452,9 @@ impl super::BrowseScreen {
         if has_real_node_id {
             // Use real OPC UA data
             self.update_real_attributes().await?;
+            if self.attributes_tab == AttributesTab::References {
+                self.update_references().await;
+            }
         } else {
             // No real NodeId available
             log::warn!("browse: no real NodeId available for selected node");
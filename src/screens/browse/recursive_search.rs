@@ -1,5 +1,5 @@
 use super::types::{BrowseScreen, SearchCommand, SearchMessage};
-use crate::client::OpcUaClientManager;
+use crate::client::{OpcUaClientManager, OpcUaSearchAttributes};
 use anyhow::Result;
 use opcua::types::NodeId;
 use std::sync::Arc;
@@ -9,6 +9,7 @@ pub struct RecursiveSearchOptions {
     pub query: String,
     pub include_values: bool,
     pub start_node_id: NodeId,
+    pub root_node_id: NodeId, // Folder the tree is rooted at; the search does not go above it
 }
 
 pub struct SearchContext<'a> {
@@ -16,6 +17,7 @@ pub struct SearchContext<'a> {
     pub search_by_value: bool,
     pub client: &'a Arc<RwLock<OpcUaClientManager>>,
     pub tree_nodes: &'a [super::types::TreeNode],
+    pub root_node_id: &'a NodeId,
     pub message_tx: &'a mpsc::UnboundedSender<SearchMessage>,
     pub command_rx: &'a mut mpsc::UnboundedReceiver<SearchCommand>,
     pub cancelled: &'a mut bool,
@@ -215,6 +217,7 @@ impl BrowseScreen {
             search_by_value: options.include_values,
             client: &client,
             tree_nodes: &tree_nodes,
+            root_node_id: &options.root_node_id,
             message_tx: &message_tx,
             command_rx,
             cancelled: &mut cancelled,
@@ -256,31 +259,26 @@ impl BrowseScreen {
             children.len()
         );
 
-        for child in children.iter() {
-            // Check for cancellation
-            if let Ok(SearchCommand::Cancel) = context.command_rx.try_recv() {
-                *context.cancelled = true;
-                return Ok(None);
-            }
-
-            if let Some(found) = Self::search_in_node_recursive(
-                &child.opcua_node_id,
-                context.query,
-                context.search_by_value,
-                context.client,
-                context.message_tx,
-                context.command_rx,
-                context.cancelled,
-            )
-            .await?
-            {
-                log::info!("search: found match in descendants '{found}'");
-                return Ok(Some(found));
-            }
-
-            if *context.cancelled {
-                return Ok(None);
-            }
+        let child_ids: Vec<NodeId> = children
+            .into_iter()
+            .map(|child| child.opcua_node_id)
+            .collect();
+        if let Some(found) = Self::search_in_nodes(
+            &child_ids,
+            context.query,
+            context.search_by_value,
+            context.client,
+            context.message_tx,
+            context.command_rx,
+            context.cancelled,
+        )
+        .await?
+        {
+            log::info!("search: found match in descendants '{found}'");
+            return Ok(Some(found));
+        }
+        if *context.cancelled {
+            return Ok(None);
         }
 
         log::debug!("search: step 1 complete - no match found in descendants");
@@ -299,7 +297,7 @@ impl BrowseScreen {
             // Find parent of current node
             let parent_node_id = match Self::find_parent_node_id_in_tree(
                 &current_node_id,
-                context.client,
+                context.root_node_id,
                 context.tree_nodes,
             )
             .await?
@@ -335,33 +333,26 @@ impl BrowseScreen {
                         remaining_root_siblings.len()
                     );
 
-                    for sibling in remaining_root_siblings.iter() {
-                        // Check for cancellation
-                        if let Ok(SearchCommand::Cancel) = context.command_rx.try_recv() {
-                            *context.cancelled = true;
-                            return Ok(None);
-                        }
-
-                        if let Some(sibling_opcua_id) = &sibling.opcua_node_id {
-                            if let Some(found) = Self::search_in_node_recursive(
-                                sibling_opcua_id,
-                                context.query,
-                                context.search_by_value,
-                                context.client,
-                                context.message_tx,
-                                context.command_rx,
-                                context.cancelled,
-                            )
-                            .await?
-                            {
-                                log::info!("search: found match in root sibling subtree '{found}'");
-                                return Ok(Some(found));
-                            }
-
-                            if *context.cancelled {
-                                return Ok(None);
-                            }
-                        }
+                    let sibling_ids: Vec<NodeId> = remaining_root_siblings
+                        .iter()
+                        .filter_map(|sibling| sibling.opcua_node_id.clone())
+                        .collect();
+                    if let Some(found) = Self::search_in_nodes(
+                        &sibling_ids,
+                        context.query,
+                        context.search_by_value,
+                        context.client,
+                        context.message_tx,
+                        context.command_rx,
+                        context.cancelled,
+                    )
+                    .await?
+                    {
+                        log::info!("search: found match in root sibling subtree '{found}'");
+                        return Ok(Some(found));
+                    }
+                    if *context.cancelled {
+                        return Ok(None);
                     }
 
                     // Finished searching all root siblings, we're done
@@ -370,10 +361,9 @@ impl BrowseScreen {
                 }
             };
 
-            // Don't go ABOVE the Objects folder ("i=85") - but we can search its siblings
-            let objects_node_id = opcua::types::NodeId::new(0, 85u32);
-            if parent_node_id == objects_node_id {
-                log::debug!("search: reached Objects folder boundary, stopping upward traversal");
+            // Don't go ABOVE the tree root - but we can search its siblings
+            if parent_node_id == *context.root_node_id {
+                log::debug!("search: reached tree root boundary, stopping upward traversal");
                 break;
             }
 
@@ -402,31 +392,26 @@ impl BrowseScreen {
                 remaining_siblings.len()
             );
 
-            for sibling in remaining_siblings.iter() {
-                // Check for cancellation
-                if let Ok(SearchCommand::Cancel) = context.command_rx.try_recv() {
-                    *context.cancelled = true;
-                    return Ok(None);
-                }
-
-                if let Some(found) = Self::search_in_node_recursive(
-                    &sibling.opcua_node_id,
-                    context.query,
-                    context.search_by_value,
-                    context.client,
-                    context.message_tx,
-                    context.command_rx,
-                    context.cancelled,
-                )
-                .await?
-                {
-                    log::info!("search: found match in sibling subtree '{found}'");
-                    return Ok(Some(found));
-                }
-
-                if *context.cancelled {
-                    return Ok(None);
-                }
+            let sibling_ids: Vec<NodeId> = remaining_siblings
+                .iter()
+                .map(|sibling| sibling.opcua_node_id.clone())
+                .collect();
+            if let Some(found) = Self::search_in_nodes(
+                &sibling_ids,
+                context.query,
+                context.search_by_value,
+                context.client,
+                context.message_tx,
+                context.command_rx,
+                context.cancelled,
+            )
+            .await?
+            {
+                log::info!("search: found match in sibling subtree '{found}'");
+                return Ok(Some(found));
+            }
+            if *context.cancelled {
+                return Ok(None);
             }
 
             // Nothing on this level; climb one level up and loop
@@ -438,9 +423,11 @@ impl BrowseScreen {
         Ok(None)
     }
 
-    /// Returns the first matching descendant of `node`, or `None` (iterative depth-first using stack)
-    async fn search_in_node_recursive(
-        start_node_id: &NodeId,
+    /// Returns the first match in the subtrees of `start_node_ids`, searched in order, or `None`
+    /// (iterative depth-first using stack). The attributes of each node's children are read
+    /// together in one batched request before the children are visited.
+    async fn search_in_nodes(
+        start_node_ids: &[NodeId],
         query: &str,
         search_by_value: bool,
         client: &Arc<RwLock<OpcUaClientManager>>,
@@ -449,9 +436,11 @@ impl BrowseScreen {
         cancelled: &mut bool,
     ) -> Result<Option<String>> {
         // Use iterative approach with a stack to avoid async recursion issues
-        let mut stack = vec![start_node_id.clone()];
+        let mut stack: Vec<(NodeId, OpcUaSearchAttributes)> =
+            Self::read_search_attributes(start_node_ids, search_by_value, client).await;
+        stack.reverse();
 
-        while let Some(current_node_id) = stack.pop() {
+        while let Some((current_node_id, attributes)) = stack.pop() {
             // Check for cancellation
             if let Ok(SearchCommand::Cancel) = command_rx.try_recv() {
                 *cancelled = true;
@@ -461,7 +450,7 @@ impl BrowseScreen {
             log::debug!("search: searching in node '{current_node_id}'");
 
             // Check if this node matches
-            if Self::is_match(&current_node_id, query, search_by_value, client, message_tx).await {
+            if Self::is_match(&current_node_id, &attributes, query, message_tx) {
                 log::info!("🎯 MATCH FOUND: {current_node_id} matches query '{query}'");
                 return Ok(Some(current_node_id.to_string()));
             } else {
@@ -469,18 +458,7 @@ impl BrowseScreen {
             }
 
             // Check if this node is a Method - if so, skip its children (Input/Output arguments)
-            let client_guard = client.read().await;
-            let should_skip_children = if let Ok((_, _, _, node_class)) = client_guard
-                .read_node_search_attributes(&current_node_id, false)
-                .await
-            {
-                matches!(node_class, opcua::types::NodeClass::Method)
-            } else {
-                false // If we can't read the node class, assume it's not a Method
-            };
-            drop(client_guard);
-
-            if should_skip_children {
+            if matches!(attributes.node_class, opcua::types::NodeClass::Method) {
                 log::debug!("search: skipping children of Method node '{current_node_id}'");
                 continue; // Skip to next node in stack without adding children
             }
@@ -493,15 +471,41 @@ impl BrowseScreen {
                 children.len()
             );
 
-            for child in children.into_iter().rev() {
-                stack.push(child.opcua_node_id);
-            }
+            let child_ids: Vec<NodeId> = children
+                .into_iter()
+                .map(|child| child.opcua_node_id)
+                .collect();
+            let children = Self::read_search_attributes(&child_ids, search_by_value, client).await;
+            stack.extend(children.into_iter().rev());
         }
 
-        log::info!("🚫 No match found in subtree starting from {start_node_id}");
+        log::info!("🚫 No match found in {} subtrees", start_node_ids.len());
         Ok(None)
     }
 
+    /// Read the search attributes of `node_ids` in one batched request
+    async fn read_search_attributes(
+        node_ids: &[NodeId],
+        search_by_value: bool,
+        client: &Arc<RwLock<OpcUaClientManager>>,
+    ) -> Vec<(NodeId, OpcUaSearchAttributes)> {
+        if node_ids.is_empty() {
+            return Vec::new();
+        }
+
+        let client_guard = client.read().await;
+        match client_guard
+            .read_nodes_search_attributes(node_ids, search_by_value)
+            .await
+        {
+            Ok(attributes) => node_ids.iter().cloned().zip(attributes).collect(),
+            Err(e) => {
+                log::debug!("search: failed to read search attributes: {e}");
+                Vec::new()
+            }
+        }
+    }
+
     /// Get visible children of a node, sorted in tree display order
     async fn get_visible_children_sorted(
         node_id: &NodeId,
@@ -568,7 +572,7 @@ impl BrowseScreen {
     /// Find the parent node ID by looking it up in the loaded tree structure
     async fn find_parent_node_id_in_tree(
         target_node_id: &NodeId,
-        _client: &Arc<RwLock<OpcUaClientManager>>,
+        root_node_id: &NodeId,
         tree_nodes: &[super::types::TreeNode],
     ) -> Result<Option<NodeId>> {
         // Since we're searching within the tree view, the parent should be visible in the tree
@@ -614,66 +618,57 @@ impl BrowseScreen {
         // Fall back to the simplified heuristic
         log::debug!("Node {target_node_id} not found in loaded tree, using fallback logic");
 
-        match target_node_id {
-            // Objects folder has no parent we search beyond
-            node_id if *node_id == opcua::types::NodeId::new(0, 85u32) => Ok(None),
-            // For other nodes, assume their parent is the Objects folder for simplicity
-            _ => Ok(Some(opcua::types::NodeId::new(0, 85u32))), // Objects folder
+        if target_node_id == root_node_id {
+            // The tree root has no parent we search beyond
+            Ok(None)
+        } else {
+            // For other nodes, assume their parent is the tree root for simplicity
+            Ok(Some(root_node_id.clone()))
         }
     }
 
     /// Text comparison helper (case-insensitive)
-    async fn is_match(
+    fn is_match(
         node_id: &NodeId,
+        attributes: &OpcUaSearchAttributes,
         query: &str,
-        search_by_value: bool,
-        client: &Arc<RwLock<OpcUaClientManager>>,
         message_tx: &mpsc::UnboundedSender<SearchMessage>,
     ) -> bool {
-        let client_guard = client.read().await;
         let query_lower = query.to_ascii_lowercase();
 
-        // Use the lightweight method to read BrowseName, DisplayName, NodeClass, and optionally Value
-        if let Ok((browse_name, display_name, value_attr, _node_class)) = client_guard
-            .read_node_search_attributes(node_id, search_by_value)
-            .await
-        {
-            // Send progress message with the current node being searched (DisplayName + NodeId)
-            let progress_text = format!("{display_name} [{node_id}]");
-            let _ = message_tx.send(SearchMessage::Progress {
-                current_node: progress_text,
-            });
+        // Send progress message with the current node being searched (DisplayName + NodeId)
+        let progress_text = format!("{} [{node_id}]", attributes.display_name);
+        let _ = message_tx.send(SearchMessage::Progress {
+            current_node: progress_text,
+        });
 
-            // Check NodeId
-            let node_id_str = node_id.to_string().to_ascii_lowercase();
-            if node_id_str.contains(&query_lower) {
-                log::info!("search: NodeId match '{node_id_str}' contains '{query}'");
-                return true;
-            }
+        // Check NodeId
+        let node_id_str = node_id.to_string().to_ascii_lowercase();
+        if node_id_str.contains(&query_lower) {
+            log::info!("search: NodeId match '{node_id_str}' contains '{query}'");
+            return true;
+        }
 
-            // Check BrowseName
-            let browse_name_lower = browse_name.to_ascii_lowercase();
-            if browse_name_lower.contains(&query_lower) {
-                log::info!("search: BrowseName match '{browse_name_lower}' contains '{query}'");
-                return true;
-            }
+        // Check BrowseName
+        let browse_name_lower = attributes.browse_name.to_ascii_lowercase();
+        if browse_name_lower.contains(&query_lower) {
+            log::info!("search: BrowseName match '{browse_name_lower}' contains '{query}'");
+            return true;
+        }
 
-            // Check DisplayName
-            let display_name_lower = display_name.to_ascii_lowercase();
-            if display_name_lower.contains(&query_lower) {
-                log::info!("search: DisplayName match '{display_name_lower}' contains '{query}'");
-                return true;
-            }
+        // Check DisplayName
+        let display_name_lower = attributes.display_name.to_ascii_lowercase();
+        if display_name_lower.contains(&query_lower) {
+            log::info!("search: DisplayName match '{display_name_lower}' contains '{query}'");
+            return true;
+        }
 
-            // Check Value attribute if it was requested and available
-            if search_by_value {
-                if let Some(value) = value_attr {
-                    let value_lower = value.to_ascii_lowercase();
-                    if value_lower.contains(&query_lower) {
-                        log::info!("✓ Value attribute match: '{value_lower}' contains '{query}'");
-                        return true;
-                    }
-                }
+        // Check Value attribute if it was requested and available
+        if let Some(value) = &attributes.value {
+            let value_lower = value.to_ascii_lowercase();
+            if value_lower.contains(&query_lower) {
+                log::info!("✓ Value attribute match: '{value_lower}' contains '{query}'");
+                return true;
             }
         }
 
@@ -0,0 +1,97 @@
+use super::types::AttributesTab;
+use crate::client::ConnectionStatus;
+use anyhow::Result;
+use crossterm::event::KeyCode;
+
+impl super::BrowseScreen {
+    /// Switch the panel next to the tree between attributes and references
+    pub async fn toggle_attributes_tab(&mut self) {
+        self.attributes_tab = match self.attributes_tab {
+            AttributesTab::Attributes => AttributesTab::References,
+            AttributesTab::References => AttributesTab::Attributes,
+        };
+        if self.attributes_tab == AttributesTab::References {
+            self.update_references().await;
+        }
+    }
+
+    /// Browse the references of the selected node; only done while the references tab is shown
+    pub async fn update_references(&mut self) {
+        self.reference_selected_index = 0;
+        self.reference_scroll_offset = 0;
+
+        let Some(node_id) = self
+            .tree_nodes
+            .get(self.selected_node_index)
+            .and_then(|node| node.opcua_node_id.clone())
+        else {
+            self.selected_references.clear();
+            return;
+        };
+
+        let client_guard = self.client.read().await;
+        if !client_guard.is_connected() {
+            self.selected_references.clear();
+            return;
+        }
+        match client_guard.browse_references(&node_id).await {
+            Ok(mut references) => {
+                // Forward references first, then grouped by ReferenceType
+                references.sort_by(|a, b| {
+                    b.is_forward
+                        .cmp(&a.is_forward)
+                        .then_with(|| a.reference_type.cmp(&b.reference_type))
+                });
+                self.selected_references = references;
+            }
+            Err(e) => {
+                log::error!("browse: failed to browse references of {node_id}: {e}");
+                self.selected_references.clear();
+            }
+        }
+    }
+
+    /// Key handling while the references tab has focus
+    pub async fn handle_references_panel_input(
+        &mut self,
+        key: KeyCode,
+    ) -> Result<Option<ConnectionStatus>> {
+        let last_index = self.selected_references.len().saturating_sub(1);
+
+        match key {
+            KeyCode::Tab => self.focus_after_attributes_panel(),
+            KeyCode::Char('t') => self.toggle_attributes_tab().await,
+            KeyCode::Up => {
+                self.reference_selected_index = self.reference_selected_index.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                self.reference_selected_index = (self.reference_selected_index + 1).min(last_index);
+            }
+            KeyCode::PageUp => {
+                self.reference_selected_index = self.reference_selected_index.saturating_sub(10);
+            }
+            KeyCode::PageDown => {
+                self.reference_selected_index =
+                    (self.reference_selected_index + 10).min(last_index);
+            }
+            KeyCode::Home => self.reference_selected_index = 0,
+            KeyCode::End => self.reference_selected_index = last_index,
+            KeyCode::Enter => {
+                // Jump the tree to the reference target
+                let Some(target_id) = self
+                    .selected_references
+                    .get(self.reference_selected_index)
+                    .map(|reference| reference.target_id.to_string())
+                else {
+                    return Ok(None);
+                };
+                self.attributes_focused = false;
+                if let Err(e) = self.expand_to_find_node(&target_id).await {
+                    log::error!("browse: failed to jump to reference target {target_id}: {e}");
+                }
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+}
@@ -1,4 +1,5 @@
-use super::types::NodeType;
+use super::types::{AttributesTab, NodeAttribute, NodeType};
+use crate::client::ConnectionStatus;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -22,6 +23,15 @@ impl super::BrowseScreen {
             ])
             .split(area);
 
+        // Event panel spans the full width below the tree and attributes while subscribed
+        let body_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),                             // Tree and attributes
+                Constraint::Length(self.events_panel_height()), // Event panel
+            ])
+            .split(main_chunks[0]);
+
         // Main content area: Tree view on left, attributes on right
         let content_chunks = Layout::default()
             .direction(Direction::Horizontal)
@@ -29,13 +39,29 @@ impl super::BrowseScreen {
                 Constraint::Percentage(50), // Tree view
                 Constraint::Percentage(50), // Attributes panel
             ])
-            .split(main_chunks[0]);
+            .split(body_chunks[0]);
 
         // Tree view
         self.render_tree_view(f, content_chunks[0]);
 
-        // Attributes panel
-        self.render_attributes_panel(f, content_chunks[1]);
+        // Attributes panel, with the watch list below it once variables are pinned
+        let right_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),                            // Attributes panel
+                Constraint::Length(self.watch_panel_height()), // Watch list
+            ])
+            .split(content_chunks[1]);
+        match self.attributes_tab {
+            AttributesTab::Attributes => self.render_attributes_panel(f, right_chunks[0]),
+            AttributesTab::References => self.render_references_panel(f, right_chunks[0]),
+        }
+        if !self.watch_items.is_empty() {
+            self.render_watch_panel(f, right_chunks[1]);
+        }
+        if self.event_monitor.is_some() {
+            self.render_events_panel(f, body_chunks[1]);
+        }
 
         // Status bar
         self.render_status_bar(f, main_chunks[1]);
@@ -48,11 +74,74 @@ impl super::BrowseScreen {
         };
 
         let progress_dialog_area = if self.search_progress_open {
-            Some(self.render_progress_dialog(f, area))
+            Some(Self::render_progress_dialog(
+                f,
+                area,
+                " Search Progress ",
+                &self.search_progress_message,
+            ))
         } else {
             None
         };
 
+        if self.export_dialog_open {
+            self.render_export_dialog(f, area);
+        }
+
+        if self.export_progress_open {
+            Self::render_progress_dialog(
+                f,
+                area,
+                " Export Progress ",
+                &self.export_progress_message,
+            );
+        }
+
+        if self.diff_dialog_open {
+            self.render_diff_dialog(f, area);
+        }
+
+        if self.diff_progress_open {
+            Self::render_progress_dialog(
+                f,
+                area,
+                " Compare Progress ",
+                &self.diff_progress_message,
+            );
+        }
+
+        if self.write_dialog_open {
+            self.render_write_dialog(f, area);
+        }
+
+        if self.watch_settings_open {
+            self.render_watch_settings_dialog(f, area);
+        }
+
+        if self.method_dialog_open {
+            self.render_method_dialog(f, area);
+        }
+
+        if self.event_dialog_open {
+            self.render_event_dialog(f, area);
+        }
+
+        if self.condition_dialog_open {
+            self.render_condition_dialog(f, area);
+        }
+
+        if self.history_view_open {
+            self.render_history_view(f, area);
+        }
+
+        if self.array_view_open {
+            self.render_array_view(f, area);
+        }
+
+        if self.diff_view_open {
+            self.render_diff_view(f, area);
+        }
+
         let log_viewer_area = if self.log_viewer_open {
             Some(self.render_log_viewer(f, area))
         } else {
@@ -70,6 +159,29 @@ impl super::BrowseScreen {
             "No node selected".to_string()
         };
 
+        let connection_state = match &self.connection_status {
+            ConnectionStatus::Connected => {
+                Span::styled("Connected", Style::default().fg(Color::Green))
+            }
+            ConnectionStatus::Reconnecting(0) => Span::styled(
+                "Connection lost, reconnecting…",
+                Style::default().fg(Color::Yellow),
+            ),
+            ConnectionStatus::Reconnecting(attempt) => Span::styled(
+                format!("Reconnecting (attempt {attempt})…"),
+                Style::default().fg(Color::Yellow),
+            ),
+            ConnectionStatus::Connecting => {
+                Span::styled("Connecting…", Style::default().fg(Color::Yellow))
+            }
+            ConnectionStatus::Disconnected => {
+                Span::styled("Disconnected", Style::default().fg(Color::Red))
+            }
+            ConnectionStatus::Error(error) => {
+                Span::styled(format!("Error: {error}"), Style::default().fg(Color::Red))
+            }
+        };
+
         let status_text = vec![
             Span::styled(
                 "OPC UA Server: ",
@@ -79,11 +191,11 @@ impl super::BrowseScreen {
             ),
             Span::styled(&self.server_url, Style::default().fg(Color::Cyan)),
             Span::raw(" | "),
-            Span::styled("Connected", Style::default().fg(Color::Green)),
+            connection_state,
             Span::raw(" | "),
             Span::styled(&selected_node_info, Style::default().fg(Color::Yellow)),
             Span::raw(
-                " | Use ←/→ expand/collapse, ↑/↓ navigate, F3/Ctrl+F search, F12 logs, q/Esc exit",
+                " | Use ←/→ expand/collapse, ↑/↓ navigate, Enter call method, F3/Ctrl+F search, w write, p pin to watch list, h history, a array, t references, o root, e events, x export, d diff, F2 certificates, F12 logs, Ctrl+T new connection, Alt+←/→ switch tab, q/Esc disconnect",
             ),
         ];
 
@@ -136,17 +248,25 @@ impl super::BrowseScreen {
                         " " // Node type can have children but this instance doesn't
                     }
                 } else {
-                    " " // Node type never has children (e.g., Variables, Methods)
+                    " " // Node type never has children (e.g., Methods)
                 };
 
                 // Format: [indent][expand_icon] [type_icon] [name]
-                let name = format!("{}{} {} {}", indent, expand_icon, icon, node.name);
+                let name = if node.is_load_more() {
+                    format!("{indent}  {}", node.name)
+                } else {
+                    format!("{}{} {} {}", indent, expand_icon, icon, node.name)
+                };
 
                 let style = if is_selected {
                     Style::default()
                         .bg(Color::Blue)
                         .fg(Color::White)
                         .add_modifier(Modifier::BOLD)
+                } else if node.is_load_more() {
+                    Style::default()
+                        .fg(Color::DarkGray)
+                        .add_modifier(Modifier::ITALIC)
                 } else {
                     Style::default().fg(Color::White)
                 };
@@ -156,16 +276,32 @@ impl super::BrowseScreen {
             .collect();
 
         // Add scroll indicator
-        let title = if self.tree_nodes.len() > visible_height {
+        let mut title = if self.tree_nodes.len() > visible_height {
             format!(
-                "OPC UA Node Tree ({}/{} shown)",
+                "OPC UA Node Tree [{}] ({}/{} shown)",
+                self.browse_root.label(),
                 visible_nodes.len(),
                 self.tree_nodes.len()
             )
         } else {
-            "OPC UA Node Tree".to_string()
+            format!("OPC UA Node Tree [{}]", self.browse_root.label())
         };
 
+        // Number of rows in the selected node's folder, and whether the server has more
+        if let Some(selected) = self.tree_nodes.get(self.selected_node_index) {
+            let folder_has_more = self.tree_nodes.iter().any(|node| {
+                node.is_load_more()
+                    && node.level == selected.level
+                    && node.parent_path == selected.parent_path
+            });
+            let loaded = self.loaded_sibling_count(self.selected_node_index);
+            if folder_has_more {
+                title.push_str(&format!(" - {loaded} loaded, more available"));
+            } else {
+                title.push_str(&format!(" - {loaded}"));
+            }
+        }
+
         let list = List::new(items).block(
             Block::default()
                 .title(title)
@@ -224,19 +360,55 @@ impl super::BrowseScreen {
     fn render_attributes_panel(&mut self, f: &mut Frame, area: Rect) {
         let visible_height = area.height.saturating_sub(4) as usize; // Subtract borders and header
 
+        // Rows inside collapsed structures and arrays are skipped
+        let visible_indices = self.visible_attribute_indices();
+        self.attribute_selected_index = self
+            .attribute_selected_index
+            .min(visible_indices.len().saturating_sub(1));
+
+        // Keep the selected row on screen while the panel has focus
+        if self.attributes_focused {
+            if self.attribute_selected_index < self.attribute_scroll_offset {
+                self.attribute_scroll_offset = self.attribute_selected_index;
+            } else if visible_height > 0
+                && self.attribute_selected_index >= self.attribute_scroll_offset + visible_height
+            {
+                self.attribute_scroll_offset = self.attribute_selected_index + 1 - visible_height;
+            }
+        }
+        self.attribute_scroll_offset = self
+            .attribute_scroll_offset
+            .min(visible_indices.len().saturating_sub(visible_height));
+
         let start_idx = self.attribute_scroll_offset;
-        let end_idx = (start_idx + visible_height).min(self.selected_attributes.len());
-        let visible_attributes = if !self.selected_attributes.is_empty() {
-            &self.selected_attributes[start_idx..end_idx]
-        } else {
-            &[]
-        };
+        let end_idx = (start_idx + visible_height).min(visible_indices.len());
+        let visible_attributes: Vec<(usize, String, &NodeAttribute)> = visible_indices
+            [start_idx..end_idx]
+            .iter()
+            .enumerate()
+            .map(|(row, &i)| {
+                let attr = &self.selected_attributes[i];
+                let marker = if !self.attribute_has_fields(i) {
+                    "  "
+                } else if self.expanded_attributes.contains(&attr.path) {
+                    "▼ "
+                } else {
+                    "▶ "
+                };
+                let label = if attr.depth == 0 && marker == "  " {
+                    attr.name.clone()
+                } else {
+                    format!("{}{marker}{}", "  ".repeat(attr.depth), attr.name)
+                };
+                (start_idx + row, label, attr)
+            })
+            .collect();
 
         // Calculate optimal attribute name column width
         let max_attr_name_length = if !self.selected_attributes.is_empty() {
             self.selected_attributes
                 .iter()
-                .map(|attr| attr.name.len())
+                .map(|attr| attr.name.chars().count() + attr.depth * 2 + 2)
                 .max()
                 .unwrap_or(0)
         } else {
@@ -245,17 +417,15 @@ impl super::BrowseScreen {
 
         // Calculate percentage based on area width, but cap at 40%
         let available_width = area.width.saturating_sub(3) as usize; // Subtract borders and spacing
-        let attr_name_percentage = if available_width > 0 {
-            let calculated_percentage = (max_attr_name_length * 100) / available_width;
-            calculated_percentage.min(40) // Cap at 40%
-        } else {
-            40
-        };
+        let attr_name_percentage = (max_attr_name_length * 100)
+            .checked_div(available_width)
+            .map(|calculated_percentage| calculated_percentage.min(40)) // Cap at 40%
+            .unwrap_or(40);
 
         let value_percentage = 100 - attr_name_percentage;
         let rows: Vec<Row> = visible_attributes
             .iter()
-            .map(|attr| {
+            .map(|(row_index, label, attr)| {
                 let value_cell = if attr.name == "Value" {
                     // Color code the Value attribute based on is_value_good
                     if attr.is_value_good {
@@ -324,7 +494,17 @@ impl super::BrowseScreen {
                     }
                 };
 
-                Row::new(vec![Cell::from(attr.name.as_str()), value_cell])
+                let row = Row::new(vec![Cell::from(label.as_str()), value_cell]);
+                if self.attributes_focused && *row_index == self.attribute_selected_index {
+                    row.style(
+                        Style::default()
+                            .bg(Color::Blue)
+                            .fg(Color::White)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    row
+                }
             })
             .collect();
 
@@ -344,14 +524,44 @@ impl super::BrowseScreen {
         )
         .block(
             Block::default()
-                .title("Node Attributes")
+                .title(self.attributes_panel_title("→/← expand/collapse fields"))
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Gray)),
+                .border_style(Style::default().fg(if self.attributes_focused {
+                    Color::Yellow
+                } else {
+                    Color::Gray
+                })),
         )
         .column_spacing(1);
 
         f.render_widget(table, area);
     }
+
+    /// Title of the panel next to the tree, showing its tabs with the active one highlighted
+    pub(super) fn attributes_panel_title(&self, focused_hint: &str) -> Line<'static> {
+        let tab_style = |tab: AttributesTab| {
+            if self.attributes_tab == tab {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+            } else {
+                Style::default().fg(Color::Gray)
+            }
+        };
+
+        let mut spans = vec![
+            Span::styled(" Node Attributes ", tab_style(AttributesTab::Attributes)),
+            Span::raw(" "),
+            Span::styled(" References ", tab_style(AttributesTab::References)),
+        ];
+        if self.attributes_focused {
+            spans.push(Span::raw(format!(
+                " - {focused_hint}, t switch tab, Tab next"
+            )));
+        }
+        Line::from(spans)
+    }
+
     fn render_search_dialog(&self, f: &mut Frame, area: Rect) -> Rect {
         // Calculate dialog position (centered)
         let dialog_width = 50;
@@ -514,7 +724,7 @@ impl super::BrowseScreen {
         // Return the dialog area for mouse handling
         dialog_area
     }
-    fn render_progress_dialog(&self, f: &mut Frame, area: Rect) -> Rect {
+    fn render_progress_dialog(f: &mut Frame, area: Rect, title: &str, message: &str) -> Rect {
         // Calculate dialog position (centered, wider than before)
         let dialog_width = 60.min(area.width.saturating_sub(4));
         let dialog_height = 5.min(area.height.saturating_sub(4));
@@ -541,7 +751,7 @@ impl super::BrowseScreen {
 
         // Create the dialog block with full blue background
         let dialog_block = Block::default()
-            .title(" Search Progress ")
+            .title(title)
             .title_style(
                 Style::default()
                     .fg(Color::White)
@@ -572,8 +782,8 @@ impl super::BrowseScreen {
             .split(inner_area);
 
         // Render progress message
-        let message_paragraph = Paragraph::new(self.search_progress_message.clone())
-            .style(Style::default().fg(Color::White).bg(Color::Blue));
+        let message_paragraph =
+            Paragraph::new(message).style(Style::default().fg(Color::White).bg(Color::Blue));
         f.render_widget(message_paragraph, chunks[0]);
 
         // Render empty separator line
@@ -0,0 +1,153 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+
+impl super::BrowseScreen {
+    pub(super) fn render_array_view(&mut self, f: &mut Frame, area: Rect) -> Rect {
+        let Some(state) = &mut self.array_view else {
+            return Rect::default();
+        };
+
+        // Full-screen overlay like the history viewer
+        f.render_widget(Clear, area);
+        let title = if state.value_type.is_empty() {
+            format!(" Array: {} ", state.node_name)
+        } else {
+            format!(" Array: {} ({}) ", state.node_name, state.value_type)
+        };
+        let block = Block::default()
+            .title(title)
+            .title_style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::White))
+            .style(Style::default().bg(Color::Black).fg(Color::White));
+        f.render_widget(block, area);
+
+        let inner_area = Rect::new(
+            area.x + 1,
+            area.y + 1,
+            area.width.saturating_sub(2),
+            area.height.saturating_sub(2),
+        );
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Index range
+                Constraint::Length(1), // Status
+                Constraint::Min(0),    // Elements table
+                Constraint::Length(1), // Instructions
+            ])
+            .split(inner_area);
+
+        // Index range input
+        let range_border = if state.range_focused {
+            Color::Yellow
+        } else {
+            Color::White
+        };
+        let width = chunks[0].width.max(3) - 3; // Account for borders
+        let scroll = state.range_input.visual_scroll(width as usize);
+        let range = Paragraph::new(state.range_input.value())
+            .style(Style::default().fg(Color::White))
+            .scroll((0, scroll as u16))
+            .block(
+                Block::default()
+                    .title("Index range (e.g. 10:20 or 0:1,2:3; empty reads the whole array)")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(range_border))
+                    .title_style(Style::default().fg(Color::Yellow)),
+            );
+        f.render_widget(range, chunks[0]);
+        if state.range_focused {
+            let cursor_x = state.range_input.visual_cursor().max(scroll) - scroll + 1;
+            f.set_cursor_position((chunks[0].x + cursor_x as u16, chunks[0].y + 1));
+        }
+
+        if let Some((message, is_good)) = &state.status_message {
+            let color = if *is_good {
+                Color::LightGreen
+            } else {
+                Color::LightRed
+            };
+            f.render_widget(
+                Paragraph::new(message.as_str()).style(Style::default().fg(color)),
+                chunks[1],
+            );
+        }
+
+        // Borders and header take three lines; pages are aligned to the page size
+        let page_size = (chunks[2].height.saturating_sub(3) as usize).max(1);
+        state.page_size = page_size;
+        let page = state.selected_row / page_size;
+        let page_count = state.elements.len().div_ceil(page_size).max(1);
+        let table_focused = !state.range_focused;
+
+        let rows: Vec<Row> = state
+            .elements
+            .iter()
+            .enumerate()
+            .skip(page * page_size)
+            .take(page_size)
+            .map(|(i, element)| {
+                let row = Row::new(vec![
+                    Cell::from(Self::array_index_label(state, i))
+                        .style(Style::default().fg(Color::Cyan)),
+                    Cell::from(element.as_str()),
+                ]);
+                if table_focused && i == state.selected_row {
+                    row.style(
+                        Style::default()
+                            .bg(Color::Blue)
+                            .fg(Color::White)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    row
+                }
+            })
+            .collect();
+
+        let table_border = if table_focused {
+            Color::Yellow
+        } else {
+            Color::White
+        };
+        let table = Table::new(rows, [Constraint::Length(16), Constraint::Min(0)])
+            .header(
+                Row::new(vec!["Index", "Value"]).style(
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            )
+            .block(
+                Block::default()
+                    .title(format!(
+                        "Elements ({}) - Page {} of {page_count}",
+                        state.elements.len(),
+                        page + 1
+                    ))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(table_border))
+                    .title_style(Style::default().fg(Color::Yellow)),
+            )
+            .column_spacing(1);
+        f.render_widget(table, chunks[2]);
+
+        let instructions = Paragraph::new(
+            "Tab range/table | Enter read | ↑/↓ scroll | PgUp/PgDn or ←/→ page | Home/End | Esc close",
+        )
+        .style(Style::default().fg(Color::Yellow).bg(Color::Black))
+        .alignment(ratatui::layout::Alignment::Center);
+        f.render_widget(instructions, chunks[3]);
+
+        area
+    }
+}
@@ -0,0 +1,217 @@
+use super::types::{DiffField, DiffRow};
+use crate::snapshot_utils::DiffKind;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+
+impl super::BrowseScreen {
+    pub(super) fn render_diff_dialog(&self, f: &mut Frame, area: Rect) -> Rect {
+        let dialog_width = 70.min(area.width.saturating_sub(4));
+        let dialog_height = 10.min(area.height.saturating_sub(2));
+        let dialog_area = Self::blue_dialog_frame(f, area, dialog_width, dialog_height);
+
+        let dialog_block = Block::default()
+            .title(" Compare Snapshot ")
+            .title_style(
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::White))
+            .style(Style::default().bg(Color::Blue));
+        f.render_widget(dialog_block, dialog_area);
+
+        let inner_area = Rect::new(
+            dialog_area.x + 1,
+            dialog_area.y + 1,
+            dialog_area.width.saturating_sub(2),
+            dialog_area.height.saturating_sub(2),
+        );
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Old snapshot
+                Constraint::Length(3), // New snapshot
+                Constraint::Length(1), // Error message
+                Constraint::Length(1), // Instructions
+            ])
+            .split(inner_area);
+
+        Self::render_event_input(
+            f,
+            chunks[0],
+            "Snapshot file",
+            &self.diff_old_input,
+            self.diff_focus == DiffField::Old,
+        );
+        Self::render_event_input(
+            f,
+            chunks[1],
+            "Compare with snapshot file (empty compares with the live server)",
+            &self.diff_new_input,
+            self.diff_focus == DiffField::New,
+        );
+
+        if let Some(error) = &self.diff_status {
+            let error_paragraph = Paragraph::new(error.as_str()).style(
+                Style::default()
+                    .fg(Color::LightRed)
+                    .bg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            );
+            f.render_widget(error_paragraph, chunks[2]);
+        }
+
+        let instructions = Paragraph::new("Tab next field | Enter compare | Esc close")
+            .style(Style::default().fg(Color::Yellow).bg(Color::Blue))
+            .alignment(ratatui::layout::Alignment::Center);
+        f.render_widget(instructions, chunks[3]);
+
+        dialog_area
+    }
+
+    pub(super) fn render_diff_view(&mut self, f: &mut Frame, area: Rect) -> Rect {
+        let Some(state) = &mut self.diff_view else {
+            return Rect::default();
+        };
+
+        // Full-screen overlay like the array viewer
+        f.render_widget(Clear, area);
+        let block = Block::default()
+            .title(format!(" Diff: {} ", state.title))
+            .title_style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::White))
+            .style(Style::default().bg(Color::Black).fg(Color::White));
+        f.render_widget(block, area);
+
+        let inner_area = Rect::new(
+            area.x + 1,
+            area.y + 1,
+            area.width.saturating_sub(2),
+            area.height.saturating_sub(2),
+        );
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),    // Differences table
+                Constraint::Length(1), // Instructions
+            ])
+            .split(inner_area);
+
+        // Borders and header take three lines; pages are aligned to the page size
+        let page_size = (chunks[0].height.saturating_sub(3) as usize).max(1);
+        state.page_size = page_size;
+        let page = state.selected_row / page_size;
+        let page_count = state.rows.len().div_ceil(page_size).max(1);
+
+        let rows: Vec<Row> = state
+            .rows
+            .iter()
+            .enumerate()
+            .skip(page * page_size)
+            .take(page_size)
+            .map(|(i, diff_row)| {
+                let row = match diff_row {
+                    DiffRow::Node {
+                        kind,
+                        depth,
+                        name,
+                        node_id,
+                    } => {
+                        let color = match kind {
+                            Some(DiffKind::Added) => Color::LightGreen,
+                            Some(DiffKind::Removed) => Color::LightRed,
+                            Some(DiffKind::Changed) => Color::Yellow,
+                            None => Color::DarkGray,
+                        };
+                        let marker = kind.map(DiffKind::marker).unwrap_or(" ");
+                        let label = if node_id.is_empty() {
+                            format!("{}{name}", "  ".repeat(*depth))
+                        } else {
+                            format!("{}{name} ({node_id})", "  ".repeat(*depth))
+                        };
+                        Row::new(vec![
+                            Cell::from(marker),
+                            Cell::from(label),
+                            Cell::from(""),
+                            Cell::from(""),
+                        ])
+                        .style(Style::default().fg(color))
+                    }
+                    DiffRow::Attribute { depth, change } => Row::new(vec![
+                        Cell::from(""),
+                        Cell::from(format!("{}  {}", "  ".repeat(*depth), change.name))
+                            .style(Style::default().fg(Color::Cyan)),
+                        Cell::from(change.old.clone().unwrap_or_else(|| "—".to_string()))
+                            .style(Style::default().fg(Color::LightRed)),
+                        Cell::from(change.new.clone().unwrap_or_else(|| "—".to_string()))
+                            .style(Style::default().fg(Color::LightGreen)),
+                    ]),
+                };
+                if i == state.selected_row {
+                    row.style(
+                        Style::default()
+                            .bg(Color::Blue)
+                            .fg(Color::White)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    row
+                }
+            })
+            .collect();
+
+        let title = if state.node_count == 0 {
+            "No differences".to_string()
+        } else {
+            format!(
+                "{} node(s) differ - Page {} of {page_count}",
+                state.node_count,
+                page + 1
+            )
+        };
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(1),
+                Constraint::Percentage(50),
+                Constraint::Percentage(25),
+                Constraint::Percentage(25),
+            ],
+        )
+        .header(
+            Row::new(vec!["", "Node / Attribute", "Old", "New"]).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title_style(Style::default().fg(Color::Yellow)),
+        )
+        .column_spacing(1);
+        f.render_widget(table, chunks[0]);
+
+        let instructions = Paragraph::new(
+            "+ added | - removed | ~ changed | ↑/↓ scroll | PgUp/PgDn or ←/→ page | Home/End | Esc close",
+        )
+        .style(Style::default().fg(Color::Yellow).bg(Color::Black))
+        .alignment(ratatui::layout::Alignment::Center);
+        f.render_widget(instructions, chunks[1]);
+
+        area
+    }
+}
@@ -0,0 +1,276 @@
+use super::types::EventFilterField;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+use tui_input::Input;
+
+impl super::BrowseScreen {
+    /// Height of the event panel, or 0 when no event monitor is running
+    pub fn events_panel_height(&self) -> u16 {
+        if self.event_monitor.is_some() {
+            12
+        } else {
+            0
+        }
+    }
+
+    pub(super) fn render_events_panel(&self, f: &mut Frame, area: Rect) {
+        let Some(monitor) = &self.event_monitor else {
+            return;
+        };
+
+        // Keep the selected event visible: borders and header take three lines
+        let visible_rows = area.height.saturating_sub(3) as usize;
+        let offset = if visible_rows > 0 && monitor.selected_index >= visible_rows {
+            monitor.selected_index + 1 - visible_rows
+        } else {
+            0
+        };
+
+        let rows: Vec<Row> = monitor
+            .events
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible_rows)
+            .map(|(i, event)| {
+                let row = Row::new(event.fields.iter().map(|field| Cell::from(field.as_str())));
+                if self.events_focused && i == monitor.selected_index {
+                    row.style(
+                        Style::default()
+                            .bg(Color::Blue)
+                            .fg(Color::White)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else if event.condition_id.is_some() {
+                    row.style(Style::default().fg(Color::LightYellow))
+                } else {
+                    row
+                }
+            })
+            .collect();
+
+        let border_color = if self.events_focused {
+            Color::Yellow
+        } else {
+            Color::Gray
+        };
+        let title = if self.events_focused {
+            format!(
+                "Events: {} ({}) - a ack, c confirm, m comment, x clear, Del stop, Tab back",
+                monitor.notifier_name,
+                monitor.events.len()
+            )
+        } else {
+            format!(
+                "Events: {} [{}] ({}) - Tab to focus",
+                monitor.notifier_name,
+                monitor.notifier_id,
+                monitor.events.len()
+            )
+        };
+
+        let column_count = monitor.columns.len().max(1) as u32;
+        let widths = vec![Constraint::Ratio(1, column_count); monitor.columns.len()];
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new(monitor.columns.iter().map(|column| column.as_str())).style(
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            )
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(border_color)),
+            )
+            .column_spacing(1);
+
+        f.render_widget(table, area);
+    }
+
+    pub(super) fn render_event_dialog(&self, f: &mut Frame, area: Rect) -> Rect {
+        let Some((notifier_id, notifier_name)) = &self.event_dialog_target else {
+            return Rect::default();
+        };
+
+        let dialog_width = 80.min(area.width.saturating_sub(4));
+        let dialog_height = 12.min(area.height.saturating_sub(2));
+        let dialog_area = Self::blue_dialog_frame(f, area, dialog_width, dialog_height);
+
+        let dialog_block = Block::default()
+            .title(" Subscribe to Events ")
+            .title_style(
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::White))
+            .style(Style::default().bg(Color::Blue));
+        f.render_widget(dialog_block, dialog_area);
+
+        let inner_area = Rect::new(
+            dialog_area.x + 1,
+            dialog_area.y + 1,
+            dialog_area.width.saturating_sub(2),
+            dialog_area.height.saturating_sub(2),
+        );
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Notifier
+                Constraint::Length(3), // Select clause
+                Constraint::Length(3), // Where clause
+                Constraint::Length(1), // Error message
+                Constraint::Length(1), // Instructions
+            ])
+            .split(inner_area);
+
+        let label_style = Style::default().fg(Color::Yellow).bg(Color::Blue);
+        let value_style = Style::default().fg(Color::White).bg(Color::Blue);
+        let notifier_line = Line::from(vec![
+            Span::styled("Notifier: ", label_style),
+            Span::styled(format!("{notifier_name} ({notifier_id})"), value_style),
+        ]);
+        f.render_widget(Paragraph::new(notifier_line).style(value_style), chunks[0]);
+
+        Self::render_event_input(
+            f,
+            chunks[1],
+            "Select (comma separated BaseEventType fields)",
+            &self.event_select_input,
+            self.event_dialog_focus == EventFilterField::Select,
+        );
+        Self::render_event_input(
+            f,
+            chunks[2],
+            "Where (optional, e.g. Severity >= 500 and SourceName == 'Pump1')",
+            &self.event_where_input,
+            self.event_dialog_focus == EventFilterField::Where,
+        );
+
+        if let Some(error) = &self.event_dialog_error {
+            let error_paragraph = Paragraph::new(error.as_str()).style(
+                Style::default()
+                    .fg(Color::LightRed)
+                    .bg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            );
+            f.render_widget(error_paragraph, chunks[3]);
+        }
+
+        let instructions = Paragraph::new("Tab next field | Enter subscribe | Esc cancel")
+            .style(Style::default().fg(Color::Yellow).bg(Color::Blue))
+            .alignment(ratatui::layout::Alignment::Center);
+        f.render_widget(instructions, chunks[4]);
+
+        dialog_area
+    }
+
+    pub(super) fn render_condition_dialog(&self, f: &mut Frame, area: Rect) -> Rect {
+        let dialog_width = 60.min(area.width.saturating_sub(4));
+        let dialog_height = 8.min(area.height.saturating_sub(2));
+        let dialog_area = Self::blue_dialog_frame(f, area, dialog_width, dialog_height);
+
+        let dialog_block = Block::default()
+            .title(format!(" {} Condition ", self.condition_action.label()))
+            .title_style(
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::White))
+            .style(Style::default().bg(Color::Blue));
+        f.render_widget(dialog_block, dialog_area);
+
+        let inner_area = Rect::new(
+            dialog_area.x + 1,
+            dialog_area.y + 1,
+            dialog_area.width.saturating_sub(2),
+            dialog_area.height.saturating_sub(2),
+        );
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Comment
+                Constraint::Length(1), // Error message
+                Constraint::Length(1), // Instructions
+            ])
+            .split(inner_area);
+
+        Self::render_event_input(f, chunks[0], "Comment", &self.condition_comment_input, true);
+
+        if let Some(status) = &self.condition_status {
+            let status_paragraph = Paragraph::new(status.as_str()).style(
+                Style::default()
+                    .fg(Color::LightRed)
+                    .bg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            );
+            f.render_widget(status_paragraph, chunks[1]);
+        }
+
+        let instructions = Paragraph::new("Enter to send | Esc to cancel")
+            .style(Style::default().fg(Color::Yellow).bg(Color::Blue))
+            .alignment(ratatui::layout::Alignment::Center);
+        f.render_widget(instructions, chunks[2]);
+
+        dialog_area
+    }
+
+    /// Center a dialog of the given size and draw the black overlay around it
+    pub(super) fn blue_dialog_frame(f: &mut Frame, area: Rect, width: u16, height: u16) -> Rect {
+        let x = (area.width.saturating_sub(width)) / 2;
+        let y = (area.height.saturating_sub(height)) / 2;
+        let dialog_area = Rect::new(x, y, width, height);
+
+        let overlay_padding = 1;
+        let overlay_area = Rect::new(
+            dialog_area.x.saturating_sub(overlay_padding),
+            dialog_area.y.saturating_sub(overlay_padding),
+            dialog_area.width + (overlay_padding * 2),
+            dialog_area.height + (overlay_padding * 2),
+        );
+        let overlay = Block::default().style(Style::default().bg(Color::Black));
+        f.render_widget(overlay, overlay_area);
+        f.render_widget(Clear, dialog_area);
+
+        dialog_area
+    }
+
+    pub(super) fn render_event_input(
+        f: &mut Frame,
+        area: Rect,
+        title: &str,
+        input: &Input,
+        focused: bool,
+    ) {
+        let border_color = if focused { Color::Yellow } else { Color::White };
+        let width = area.width.max(3) - 3; // Account for borders
+        let scroll = input.visual_scroll(width as usize);
+        let paragraph = Paragraph::new(input.value())
+            .style(Style::default().fg(Color::White))
+            .scroll((0, scroll as u16))
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(border_color))
+                    .title_style(Style::default().fg(Color::Yellow)),
+            );
+        f.render_widget(paragraph, area);
+
+        if focused {
+            let cursor_x = input.visual_cursor().max(scroll) - scroll + 1;
+            f.set_cursor_position((area.x + cursor_x as u16, area.y + 1));
+        }
+    }
+}
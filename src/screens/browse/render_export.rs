@@ -0,0 +1,101 @@
+use super::types::ExportField;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+impl super::BrowseScreen {
+    pub(super) fn render_export_dialog(&self, f: &mut Frame, area: Rect) -> Rect {
+        let Some(target) = &self.export_target else {
+            return Rect::default();
+        };
+
+        let dialog_width = 70.min(area.width.saturating_sub(4));
+        let dialog_height = 10.min(area.height.saturating_sub(2));
+        let dialog_area = Self::blue_dialog_frame(f, area, dialog_width, dialog_height);
+
+        let dialog_block = Block::default()
+            .title(" Export Subtree ")
+            .title_style(
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::White))
+            .style(Style::default().bg(Color::Blue));
+        f.render_widget(dialog_block, dialog_area);
+
+        let inner_area = Rect::new(
+            dialog_area.x + 1,
+            dialog_area.y + 1,
+            dialog_area.width.saturating_sub(2),
+            dialog_area.height.saturating_sub(2),
+        );
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Node
+                Constraint::Length(3), // Depth
+                Constraint::Length(1), // Format
+                Constraint::Length(1), // Status message
+                Constraint::Length(1), // Instructions
+            ])
+            .split(inner_area);
+
+        let label_style = Style::default().fg(Color::Yellow).bg(Color::Blue);
+        let value_style = Style::default().fg(Color::White).bg(Color::Blue);
+        let node_line = Line::from(vec![
+            Span::styled("Node: ", label_style),
+            Span::styled(format!("{} ({})", target.name, target.node_id), value_style),
+        ]);
+        f.render_widget(Paragraph::new(node_line).style(value_style), chunks[0]);
+
+        Self::render_event_input(
+            f,
+            chunks[1],
+            "Depth (levels below the node)",
+            &self.export_depth_input,
+            self.export_focus == ExportField::Depth,
+        );
+
+        let format_style = if self.export_focus == ExportField::Format {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            value_style
+        };
+        let format_line = Line::from(vec![
+            Span::styled("Format: ", label_style),
+            Span::styled(format!("◀ {} ▶", self.export_format.label()), format_style),
+        ]);
+        f.render_widget(Paragraph::new(format_line).style(value_style), chunks[2]);
+
+        if let Some((message, is_good)) = &self.export_status {
+            let color = if *is_good {
+                Color::LightGreen
+            } else {
+                Color::LightRed
+            };
+            let status_paragraph = Paragraph::new(message.as_str()).style(
+                Style::default()
+                    .fg(color)
+                    .bg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            );
+            f.render_widget(status_paragraph, chunks[3]);
+        }
+
+        let instructions = Paragraph::new("Tab next field | ←/→ format | Enter export | Esc close")
+            .style(Style::default().fg(Color::Yellow).bg(Color::Blue))
+            .alignment(ratatui::layout::Alignment::Center);
+        f.render_widget(instructions, chunks[4]);
+
+        dialog_area
+    }
+}
@@ -0,0 +1,219 @@
+use super::types::HistoryField;
+use crate::client::HistoryReadMode;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+use tui_input::Input;
+
+impl super::BrowseScreen {
+    pub(super) fn render_history_view(&self, f: &mut Frame, area: Rect) -> Rect {
+        let Some(state) = &self.history_view else {
+            return Rect::default();
+        };
+
+        // Full-screen overlay like the log viewer
+        f.render_widget(Clear, area);
+        let block = Block::default()
+            .title(format!(" History: {} ", state.node_name))
+            .title_style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::White))
+            .style(Style::default().bg(Color::Black).fg(Color::White));
+        f.render_widget(block, area);
+
+        let inner_area = Rect::new(
+            area.x + 1,
+            area.y + 1,
+            area.width.saturating_sub(2),
+            area.height.saturating_sub(2),
+        );
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Query fields
+                Constraint::Length(1), // Status
+                Constraint::Min(0),    // Values table
+                Constraint::Length(1), // Instructions
+            ])
+            .split(inner_area);
+
+        let field_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(30),
+                Constraint::Percentage(30),
+                Constraint::Percentage(20),
+                Constraint::Percentage(20),
+            ])
+            .split(chunks[0]);
+
+        Self::render_history_input(
+            f,
+            field_chunks[0],
+            "Start (UTC)",
+            &state.start_input,
+            state.focus == HistoryField::StartTime,
+        );
+        Self::render_history_input(
+            f,
+            field_chunks[1],
+            "End (UTC)",
+            &state.end_input,
+            state.focus == HistoryField::EndTime,
+        );
+
+        let mode_focused = state.focus == HistoryField::Mode;
+        let mode_text = if mode_focused {
+            format!("◀ {} ▶", state.mode.label())
+        } else {
+            state.mode.label().to_string()
+        };
+        let mode = Paragraph::new(mode_text)
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Self::history_field_block("Mode", mode_focused));
+        f.render_widget(mode, field_chunks[2]);
+
+        // The processing interval only applies to aggregate reads
+        if state.mode == HistoryReadMode::Raw {
+            let interval = Paragraph::new("n/a")
+                .style(Style::default().fg(Color::DarkGray))
+                .block(Self::history_field_block(
+                    "Interval (ms)",
+                    state.focus == HistoryField::Interval,
+                ));
+            f.render_widget(interval, field_chunks[3]);
+        } else {
+            Self::render_history_input(
+                f,
+                field_chunks[3],
+                "Interval (ms)",
+                &state.interval_input,
+                state.focus == HistoryField::Interval,
+            );
+        }
+
+        if let Some((message, is_good)) = &state.status_message {
+            let color = if *is_good {
+                Color::LightGreen
+            } else {
+                Color::LightRed
+            };
+            f.render_widget(
+                Paragraph::new(message.as_str()).style(Style::default().fg(color)),
+                chunks[1],
+            );
+        }
+
+        self.render_history_table(f, chunks[2]);
+
+        let instructions = Paragraph::new(
+            "Tab next field | ←/→ mode | Enter read | ↑/↓ PgUp/PgDn scroll | n next page | e export CSV | Esc close",
+        )
+        .style(Style::default().fg(Color::Yellow).bg(Color::Black))
+        .alignment(ratatui::layout::Alignment::Center);
+        f.render_widget(instructions, chunks[3]);
+
+        area
+    }
+
+    fn render_history_table(&self, f: &mut Frame, area: Rect) {
+        let Some(state) = &self.history_view else {
+            return;
+        };
+
+        // Keep the selected row visible: borders and header take three lines
+        let visible_rows = area.height.saturating_sub(3) as usize;
+        let offset = if visible_rows > 0 && state.selected_row >= visible_rows {
+            state.selected_row + 1 - visible_rows
+        } else {
+            0
+        };
+        let table_focused = state.focus == HistoryField::Table;
+
+        let rows: Vec<Row> = state
+            .rows
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible_rows)
+            .map(|(i, row)| {
+                let value_color = if row.is_value_good {
+                    Color::Green
+                } else {
+                    Color::Red
+                };
+                let table_row = Row::new(vec![
+                    Cell::from(row.source_timestamp.as_str()),
+                    Cell::from(row.server_timestamp.as_str()),
+                    Cell::from(row.value.as_str()).style(Style::default().fg(value_color)),
+                    Cell::from(row.status.as_str()),
+                ]);
+                if table_focused && i == state.selected_row {
+                    table_row.style(
+                        Style::default()
+                            .bg(Color::Blue)
+                            .fg(Color::White)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    table_row
+                }
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            &[
+                Constraint::Percentage(25),
+                Constraint::Percentage(25),
+                Constraint::Percentage(30),
+                Constraint::Percentage(20),
+            ],
+        )
+        .header(
+            Row::new(vec!["Source Time", "Server Time", "Value", "Status"]).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .block(Self::history_field_block(
+            &format!("Values ({})", state.rows.len()),
+            table_focused,
+        ))
+        .column_spacing(1);
+
+        f.render_widget(table, area);
+    }
+
+    fn render_history_input(f: &mut Frame, area: Rect, title: &str, input: &Input, focused: bool) {
+        let width = area.width.max(3) - 3; // Account for borders
+        let scroll = input.visual_scroll(width as usize);
+        let paragraph = Paragraph::new(input.value())
+            .style(Style::default().fg(Color::White))
+            .scroll((0, scroll as u16))
+            .block(Self::history_field_block(title, focused));
+        f.render_widget(paragraph, area);
+
+        if focused {
+            let cursor_x = input.visual_cursor().max(scroll) - scroll + 1;
+            f.set_cursor_position((area.x + cursor_x as u16, area.y + 1));
+        }
+    }
+
+    fn history_field_block(title: &str, focused: bool) -> Block<'static> {
+        let border_color = if focused { Color::Yellow } else { Color::White };
+        Block::default()
+            .title(title.to_string())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color))
+            .title_style(Style::default().fg(Color::Yellow))
+    }
+}
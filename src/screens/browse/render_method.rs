@@ -0,0 +1,180 @@
+use crate::client::OpcUaClientManager;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+impl super::BrowseScreen {
+    pub(super) fn render_method_dialog(&self, f: &mut Frame, area: Rect) -> Rect {
+        let Some(state) = &self.method_call else {
+            return Rect::default();
+        };
+
+        let input_rows = (state.inputs.len().max(1) * 3) as u16;
+        let result_rows = state
+            .outcome
+            .as_ref()
+            .map(|outcome| outcome.lines.len() as u16 + 1)
+            .unwrap_or(1);
+        let dialog_width = 70.min(area.width.saturating_sub(4));
+        let dialog_height = (input_rows + result_rows + 5).min(area.height.saturating_sub(2));
+        let x = (area.width.saturating_sub(dialog_width)) / 2;
+        let y = (area.height.saturating_sub(dialog_height)) / 2;
+        let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+        // Black overlay around the dialog borders
+        let overlay_padding = 1;
+        let overlay_area = Rect::new(
+            dialog_area.x.saturating_sub(overlay_padding),
+            dialog_area.y.saturating_sub(overlay_padding),
+            dialog_area.width + (overlay_padding * 2),
+            dialog_area.height + (overlay_padding * 2),
+        );
+        let overlay = Block::default().style(Style::default().bg(Color::Black));
+        f.render_widget(overlay, overlay_area);
+        f.render_widget(Clear, dialog_area);
+
+        let dialog_block = Block::default()
+            .title(format!(" Call Method: {} ", state.method_name))
+            .title_style(
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::White))
+            .style(Style::default().bg(Color::Blue));
+        f.render_widget(dialog_block, dialog_area);
+
+        let inner_area = Rect::new(
+            dialog_area.x + 1,
+            dialog_area.y + 1,
+            dialog_area.width.saturating_sub(2),
+            dialog_area.height.saturating_sub(2),
+        );
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),           // Object
+                Constraint::Length(input_rows),  // Input arguments
+                Constraint::Length(1),           // Separator
+                Constraint::Length(result_rows), // Status and outputs
+                Constraint::Min(0),
+                Constraint::Length(1), // Instructions
+            ])
+            .split(inner_area);
+
+        let label_style = Style::default().fg(Color::Yellow).bg(Color::Blue);
+        let value_style = Style::default().fg(Color::White).bg(Color::Blue);
+
+        let object_line = Line::from(vec![
+            Span::styled("Object: ", label_style),
+            Span::styled(state.object_id.to_string(), value_style),
+        ]);
+        f.render_widget(Paragraph::new(object_line).style(value_style), chunks[0]);
+
+        if state.inputs.is_empty() {
+            f.render_widget(
+                Paragraph::new("No input arguments").style(value_style),
+                chunks[1],
+            );
+        } else {
+            let input_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(vec![Constraint::Length(3); state.inputs.len()])
+                .split(chunks[1]);
+
+            for (i, ((argument, input), chunk)) in state
+                .input_arguments
+                .iter()
+                .zip(&state.inputs)
+                .zip(input_chunks.iter())
+                .enumerate()
+            {
+                let is_focused = i == state.focused_input;
+                let array_suffix = if argument.value_rank >= 0 { "[]" } else { "" };
+                let title = format!(
+                    "{} ({}{})",
+                    argument.name.as_ref(),
+                    OpcUaClientManager::format_data_type(&argument.data_type),
+                    array_suffix
+                );
+                let border_color = if is_focused {
+                    Color::Yellow
+                } else {
+                    Color::White
+                };
+
+                let width = chunk.width.max(3) - 3; // Account for borders
+                let scroll = input.visual_scroll(width as usize);
+                let input_paragraph = Paragraph::new(input.value())
+                    .style(Style::default().fg(Color::White))
+                    .scroll((0, scroll as u16))
+                    .block(
+                        Block::default()
+                            .title(title)
+                            .borders(Borders::ALL)
+                            .border_style(Style::default().fg(border_color))
+                            .title_style(Style::default().fg(Color::Yellow)),
+                    );
+                f.render_widget(input_paragraph, *chunk);
+
+                if is_focused {
+                    let cursor_x = input.visual_cursor().max(scroll) - scroll + 1;
+                    f.set_cursor_position((chunk.x + cursor_x as u16, chunk.y + 1));
+                }
+            }
+        }
+
+        let result_lines: Vec<Line> = match &state.outcome {
+            Some(outcome) => {
+                let status_color = if outcome.is_good {
+                    Color::LightGreen
+                } else {
+                    Color::LightRed
+                };
+                let mut lines = vec![Line::from(vec![
+                    Span::styled("Result: ", label_style),
+                    Span::styled(
+                        outcome.status.as_str(),
+                        Style::default()
+                            .fg(status_color)
+                            .bg(Color::Blue)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                ])];
+                lines.extend(outcome.lines.iter().map(|(name, value)| {
+                    Line::from(vec![
+                        Span::styled(format!("  {name} = "), label_style),
+                        Span::styled(value.as_str(), value_style),
+                    ])
+                }));
+                lines
+            }
+            None => {
+                let outputs: Vec<&str> = state
+                    .output_arguments
+                    .iter()
+                    .map(|argument| argument.name.as_ref())
+                    .collect();
+                let text = if outputs.is_empty() {
+                    "Outputs: none".to_string()
+                } else {
+                    format!("Outputs: {}", outputs.join(", "))
+                };
+                vec![Line::from(Span::styled(text, value_style))]
+            }
+        };
+        f.render_widget(Paragraph::new(result_lines).style(value_style), chunks[3]);
+
+        let instructions = Paragraph::new("Tab next argument | Enter call | Esc close")
+            .style(Style::default().fg(Color::Yellow).bg(Color::Blue))
+            .alignment(ratatui::layout::Alignment::Center);
+        f.render_widget(instructions, chunks[5]);
+
+        dialog_area
+    }
+}
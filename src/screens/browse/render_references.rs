@@ -0,0 +1,94 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table},
+    Frame,
+};
+
+impl super::BrowseScreen {
+    pub(super) fn render_references_panel(&mut self, f: &mut Frame, area: Rect) {
+        let visible_height = area.height.saturating_sub(3) as usize; // Borders and header
+
+        // Keep the selected reference on screen
+        self.reference_selected_index = self
+            .reference_selected_index
+            .min(self.selected_references.len().saturating_sub(1));
+        if self.reference_selected_index < self.reference_scroll_offset {
+            self.reference_scroll_offset = self.reference_selected_index;
+        } else if visible_height > 0
+            && self.reference_selected_index >= self.reference_scroll_offset + visible_height
+        {
+            self.reference_scroll_offset = self.reference_selected_index + 1 - visible_height;
+        }
+
+        let rows: Vec<Row> = self
+            .selected_references
+            .iter()
+            .enumerate()
+            .skip(self.reference_scroll_offset)
+            .take(visible_height)
+            .map(|(i, reference)| {
+                let (direction, direction_color) = if reference.is_forward {
+                    ("→", Color::Green)
+                } else {
+                    ("←", Color::Magenta)
+                };
+                let row = Row::new(vec![
+                    Cell::from(reference.reference_type.as_str()),
+                    Cell::from(direction).style(Style::default().fg(direction_color)),
+                    Cell::from(reference.target_id.to_string()),
+                    Cell::from(reference.browse_name.as_str()),
+                    Cell::from(format!("{:?}", reference.node_class)),
+                ]);
+                if self.attributes_focused && i == self.reference_selected_index {
+                    row.style(
+                        Style::default()
+                            .bg(Color::Blue)
+                            .fg(Color::White)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    row
+                }
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(24),
+                Constraint::Length(3),
+                Constraint::Percentage(28),
+                Constraint::Percentage(28),
+                Constraint::Percentage(14),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                "ReferenceType",
+                "Dir",
+                "Target",
+                "BrowseName",
+                "NodeClass",
+            ])
+            .style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .block(
+            Block::default()
+                .title(self.attributes_panel_title("Enter jump to target"))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(if self.attributes_focused {
+                    Color::Yellow
+                } else {
+                    Color::Gray
+                })),
+        )
+        .column_spacing(1);
+
+        f.render_widget(table, area);
+    }
+}
@@ -0,0 +1,217 @@
+use super::types::WatchSettingsField;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+use tui_input::Input;
+
+impl super::BrowseScreen {
+    /// Height of the watch list panel, or 0 when nothing is pinned
+    pub(super) fn watch_panel_height(&self) -> u16 {
+        if self.watch_items.is_empty() {
+            0
+        } else {
+            (self.watch_items.len() as u16 + 3).min(12) // Borders + header
+        }
+    }
+
+    pub(super) fn render_watch_panel(&self, f: &mut Frame, area: Rect) {
+        let rows: Vec<Row> = self
+            .watch_items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let value_color = if item.is_value_good {
+                    Color::Green
+                } else {
+                    Color::Red
+                };
+                let row = Row::new(vec![
+                    Cell::from(item.name.as_str()),
+                    Cell::from(item.value.as_str()).style(Style::default().fg(value_color)),
+                    Cell::from(item.status.as_str()),
+                    Cell::from(item.source_timestamp.as_str()),
+                ]);
+                if self.watch_focused && i == self.watch_selected_index {
+                    row.style(
+                        Style::default()
+                            .bg(Color::Blue)
+                            .fg(Color::White)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    row
+                }
+            })
+            .collect();
+
+        let border_color = if self.watch_focused {
+            Color::Yellow
+        } else {
+            Color::Gray
+        };
+        let title = if self.watch_focused {
+            format!(
+                "Watch List ({}) - Enter settings, Del unpin, Tab back",
+                self.watch_items.len()
+            )
+        } else {
+            format!("Watch List ({}) - Tab to focus", self.watch_items.len())
+        };
+
+        let table = Table::new(
+            rows,
+            &[
+                Constraint::Percentage(30),
+                Constraint::Percentage(30),
+                Constraint::Percentage(20),
+                Constraint::Percentage(20),
+            ],
+        )
+        .header(
+            Row::new(vec!["Node", "Value", "Status", "Source Time"]).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(border_color)),
+        )
+        .column_spacing(1);
+
+        f.render_widget(table, area);
+    }
+
+    pub(super) fn render_watch_settings_dialog(&self, f: &mut Frame, area: Rect) -> Rect {
+        let dialog_width = 50.min(area.width.saturating_sub(4));
+        let dialog_height = 13.min(area.height.saturating_sub(2));
+        let x = (area.width.saturating_sub(dialog_width)) / 2;
+        let y = (area.height.saturating_sub(dialog_height)) / 2;
+        let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+        // Black overlay around the dialog borders
+        let overlay_padding = 1;
+        let overlay_area = Rect::new(
+            dialog_area.x.saturating_sub(overlay_padding),
+            dialog_area.y.saturating_sub(overlay_padding),
+            dialog_area.width + (overlay_padding * 2),
+            dialog_area.height + (overlay_padding * 2),
+        );
+        let overlay = Block::default().style(Style::default().bg(Color::Black));
+        f.render_widget(overlay, overlay_area);
+        f.render_widget(Clear, dialog_area);
+
+        let item_name = self
+            .watch_items
+            .get(self.watch_selected_index)
+            .map(|item| item.name.as_str())
+            .unwrap_or_default();
+        let dialog_block = Block::default()
+            .title(format!(" Monitoring: {item_name} "))
+            .title_style(
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::White))
+            .style(Style::default().bg(Color::Blue));
+        f.render_widget(dialog_block, dialog_area);
+
+        let inner_area = Rect::new(
+            dialog_area.x + 1,
+            dialog_area.y + 1,
+            dialog_area.width.saturating_sub(2),
+            dialog_area.height.saturating_sub(2),
+        );
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Sampling interval
+                Constraint::Length(3), // Queue size
+                Constraint::Length(3), // Deadband
+                Constraint::Length(1), // Error message
+                Constraint::Length(1), // Instructions
+            ])
+            .split(inner_area);
+
+        let fields = [
+            (
+                WatchSettingsField::SamplingInterval,
+                "Sampling interval (ms)",
+                &self.watch_sampling_input,
+            ),
+            (
+                WatchSettingsField::QueueSize,
+                "Queue size",
+                &self.watch_queue_input,
+            ),
+            (
+                WatchSettingsField::Deadband,
+                "Absolute deadband (0 = off)",
+                &self.watch_deadband_input,
+            ),
+        ];
+        for (chunk, (field, title, input)) in chunks.iter().zip(fields) {
+            self.render_watch_settings_input(f, *chunk, title, input, field);
+        }
+
+        if let Some(error) = &self.watch_settings_error {
+            let error_paragraph = Paragraph::new(error.as_str()).style(
+                Style::default()
+                    .fg(Color::LightRed)
+                    .bg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            );
+            f.render_widget(error_paragraph, chunks[3]);
+        }
+
+        let instructions = Paragraph::new("Tab next field | Enter apply | Esc cancel")
+            .style(Style::default().fg(Color::Yellow).bg(Color::Blue))
+            .alignment(ratatui::layout::Alignment::Center);
+        f.render_widget(instructions, chunks[4]);
+
+        dialog_area
+    }
+
+    fn render_watch_settings_input(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        title: &str,
+        input: &Input,
+        field: WatchSettingsField,
+    ) {
+        let is_focused = self.watch_settings_focus == field;
+        let border_color = if is_focused {
+            Color::Yellow
+        } else {
+            Color::White
+        };
+
+        let width = area.width.max(3) - 3; // Account for borders
+        let scroll = input.visual_scroll(width as usize);
+        let paragraph = Paragraph::new(input.value())
+            .style(Style::default().fg(Color::White))
+            .scroll((0, scroll as u16))
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(border_color))
+                    .title_style(Style::default().fg(Color::Yellow)),
+            );
+        f.render_widget(paragraph, area);
+
+        if is_focused {
+            let cursor_x = input.visual_cursor().max(scroll) - scroll + 1;
+            f.set_cursor_position((area.x + cursor_x as u16, area.y + 1));
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use crate::client::OpcUaClientManager;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+impl super::BrowseScreen {
+    pub(super) fn render_write_dialog(&self, f: &mut Frame, area: Rect) -> Rect {
+        let Some(target) = &self.write_target else {
+            return Rect::default();
+        };
+
+        // Calculate dialog position (centered)
+        let dialog_width = 70.min(area.width.saturating_sub(4));
+        let dialog_height = 11.min(area.height.saturating_sub(2));
+        let x = (area.width.saturating_sub(dialog_width)) / 2;
+        let y = (area.height.saturating_sub(dialog_height)) / 2;
+        let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+        // Black overlay around the dialog borders
+        let overlay_padding = 1;
+        let overlay_area = Rect::new(
+            dialog_area.x.saturating_sub(overlay_padding),
+            dialog_area.y.saturating_sub(overlay_padding),
+            dialog_area.width + (overlay_padding * 2),
+            dialog_area.height + (overlay_padding * 2),
+        );
+        let overlay = Block::default().style(Style::default().bg(Color::Black));
+        f.render_widget(overlay, overlay_area);
+        f.render_widget(Clear, dialog_area);
+
+        let dialog_block = Block::default()
+            .title(" Write Value ")
+            .title_style(
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::White))
+            .style(Style::default().bg(Color::Blue));
+        f.render_widget(dialog_block, dialog_area);
+
+        let inner_area = Rect::new(
+            dialog_area.x + 1,
+            dialog_area.y + 1,
+            dialog_area.width.saturating_sub(2),
+            dialog_area.height.saturating_sub(2),
+        );
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Node name
+                Constraint::Length(1), // DataType and ValueRank
+                Constraint::Length(1), // Access level
+                Constraint::Length(3), // Input field
+                Constraint::Length(1), // Status message
+                Constraint::Length(1), // Instructions
+            ])
+            .split(inner_area);
+
+        let label_style = Style::default().fg(Color::Yellow).bg(Color::Blue);
+        let value_style = Style::default().fg(Color::White).bg(Color::Blue);
+
+        let value_rank_text = match target.info.value_rank {
+            -3 => "ScalarOrOneDimension".to_string(),
+            -2 => "Any".to_string(),
+            -1 => "Scalar".to_string(),
+            0 => "OneOrMoreDimensions".to_string(),
+            rank => format!("Array ({rank} dim)"),
+        };
+
+        let info_lines = [
+            Line::from(vec![
+                Span::styled("Node: ", label_style),
+                Span::styled(
+                    format!("{} ({})", target.node_name, target.node_id),
+                    value_style,
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("DataType: ", label_style),
+                Span::styled(
+                    OpcUaClientManager::format_data_type(&target.info.data_type),
+                    value_style,
+                ),
+                Span::styled("  ValueRank: ", label_style),
+                Span::styled(value_rank_text, value_style),
+            ]),
+            Line::from(vec![
+                Span::styled("UserAccessLevel: ", label_style),
+                Span::styled(
+                    OpcUaClientManager::format_access_level(target.info.user_access_level),
+                    value_style,
+                ),
+            ]),
+        ];
+        for (line, chunk) in info_lines.into_iter().zip(chunks.iter()) {
+            f.render_widget(Paragraph::new(line).style(value_style), *chunk);
+        }
+
+        // Input field styled like the search dialog
+        let writable = self.write_target_is_writable();
+        let input_border_color = if writable {
+            Color::Yellow
+        } else {
+            Color::DarkGray
+        };
+        let width = chunks[3].width.max(3) - 3; // Account for borders
+        let scroll = self.write_input.visual_scroll(width as usize);
+        let input_paragraph = Paragraph::new(self.write_input.value())
+            .style(Style::default().fg(Color::White))
+            .scroll((0, scroll as u16))
+            .block(
+                Block::default()
+                    .title("New value (arrays: a, b, c)")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(input_border_color))
+                    .title_style(Style::default().fg(Color::Yellow)),
+            );
+        f.render_widget(input_paragraph, chunks[3]);
+
+        if writable {
+            let cursor_x = self.write_input.visual_cursor().max(scroll) - scroll + 1;
+            f.set_cursor_position((chunks[3].x + cursor_x as u16, chunks[3].y + 1));
+        }
+
+        if let Some((message, is_good)) = &self.write_status {
+            let color = if *is_good {
+                Color::LightGreen
+            } else {
+                Color::LightRed
+            };
+            let status_paragraph = Paragraph::new(message.as_str()).style(
+                Style::default()
+                    .fg(color)
+                    .bg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            );
+            f.render_widget(status_paragraph, chunks[4]);
+        }
+
+        let instructions = if writable {
+            "Enter to write | Esc to close"
+        } else {
+            "Esc to close"
+        };
+        let instructions_paragraph = Paragraph::new(instructions)
+            .style(Style::default().fg(Color::Yellow).bg(Color::Blue))
+            .alignment(ratatui::layout::Alignment::Center);
+        f.render_widget(instructions_paragraph, chunks[5]);
+
+        dialog_area
+    }
+}
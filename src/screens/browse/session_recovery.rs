@@ -0,0 +1,110 @@
+use super::event_monitor::EVENT_CLIENT_HANDLE;
+use super::watch_list::WATCH_PUBLISHING_INTERVAL;
+use tokio::sync::mpsc;
+
+impl super::BrowseScreen {
+    /// Bring the screen back to its state before the connection was lost: reload the tree with
+    /// the same nodes expanded and selected, and re-create the watch and event subscriptions
+    pub async fn restore_after_reconnect(&mut self) {
+        log::info!("browse: restoring tree and subscriptions after reconnect");
+        self.restore_tree().await;
+        self.restore_watch_subscription().await;
+        self.restore_event_subscription().await;
+    }
+
+    async fn restore_tree(&mut self) {
+        let expanded_nodes = self.expanded_nodes.clone();
+        let selected_node_id = self
+            .tree_nodes
+            .get(self.selected_node_index)
+            .map(|node| node.node_id.clone());
+
+        if let Err(e) = self.load_real_tree().await {
+            log::error!("browse: failed to reload tree after reconnect: {e}");
+            return;
+        }
+
+        // Expanding a top level node also restores the expanded nodes below it
+        self.expanded_nodes = expanded_nodes;
+        let mut index = 0;
+        while index < self.tree_nodes.len() {
+            let node = &self.tree_nodes[index];
+            let path = self.get_node_path(node);
+            if node.level == 0 && self.expanded_nodes.contains(&path) {
+                if let Err(e) = self.expand_node_async(index).await {
+                    log::warn!("browse: failed to restore expansion of {path}: {e}");
+                }
+            }
+            index += 1;
+        }
+
+        if let Some(index) = selected_node_id
+            .and_then(|id| self.tree_nodes.iter().position(|node| node.node_id == id))
+        {
+            self.selected_node_index = index;
+            self.update_scroll();
+        }
+        if let Err(e) = self.update_selected_attributes_async().await {
+            log::warn!("browse: failed to read attributes after reconnect: {e}");
+        }
+    }
+
+    async fn restore_watch_subscription(&mut self) {
+        self.watch_message_rx = None;
+        if self.watch_items.is_empty() {
+            return;
+        }
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let result = {
+            let mut client_guard = self.client.write().await;
+            client_guard
+                .create_watch_subscription(WATCH_PUBLISHING_INTERVAL, tx)
+                .await
+        };
+        if let Err(e) = result {
+            log::error!("watch: failed to re-create subscription: {e}");
+            return;
+        }
+        self.watch_message_rx = Some(rx);
+
+        let client_guard = self.client.read().await;
+        for item in &mut self.watch_items {
+            match client_guard
+                .add_monitored_item(&item.node_id, item.client_handle, &item.settings)
+                .await
+            {
+                Ok(monitored_item_id) => item.monitored_item_id = monitored_item_id,
+                Err(e) => {
+                    log::error!("watch: failed to watch {} again: {e}", item.node_id);
+                    item.status = "Not monitored".to_string();
+                    item.is_value_good = false;
+                }
+            }
+        }
+    }
+
+    async fn restore_event_subscription(&mut self) {
+        self.event_message_rx = None;
+        let Some(monitor) = &self.event_monitor else {
+            return;
+        };
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let result = {
+            let mut client_guard = self.client.write().await;
+            client_guard
+                .create_event_subscription(
+                    &monitor.notifier_id,
+                    EVENT_CLIENT_HANDLE,
+                    &monitor.filter,
+                    tx,
+                )
+                .await
+        };
+        match result {
+            Ok(_) => self.event_message_rx = Some(rx),
+            Err(e) => log::error!("events: failed to re-create subscription: {e}"),
+        }
+    }
+}
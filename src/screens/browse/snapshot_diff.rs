@@ -0,0 +1,286 @@
+use super::types::{DiffCommand, DiffField, DiffMessage, DiffRow, DiffViewState};
+use crate::client::{ConnectionStatus, OpcUaClientManager};
+use crate::snapshot_utils::{NodeDiff, Snapshot, SnapshotUtils};
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyModifiers};
+use opcua::types::NodeId;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::{mpsc, RwLock};
+use tui_input::backend::crossterm::EventHandler;
+
+impl super::BrowseScreen {
+    /// Open the dialog that compares a snapshot with another one or with the live server
+    pub fn open_diff_dialog(&mut self) {
+        self.diff_focus = DiffField::Old;
+        self.diff_status = None;
+        self.diff_dialog_open = true;
+    }
+
+    pub async fn handle_diff_dialog_input(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Result<Option<ConnectionStatus>> {
+        match key {
+            KeyCode::Esc => {
+                self.diff_dialog_open = false;
+            }
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                self.diff_focus = match self.diff_focus {
+                    DiffField::Old => DiffField::New,
+                    DiffField::New => DiffField::Old,
+                };
+            }
+            KeyCode::Enter => {
+                self.start_diff();
+            }
+            _ => {
+                let input = match self.diff_focus {
+                    DiffField::Old => &mut self.diff_old_input,
+                    DiffField::New => &mut self.diff_new_input,
+                };
+                input.handle_event(&crossterm::event::Event::Key(
+                    crossterm::event::KeyEvent::new(key, modifiers),
+                ));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Compare two snapshot files directly, or capture the live server in a background task
+    fn start_diff(&mut self) {
+        let old_path = self.diff_old_input.value().trim().to_string();
+        let new_path = self.diff_new_input.value().trim().to_string();
+        if old_path.is_empty() {
+            self.diff_status = Some("Enter the snapshot file to compare".to_string());
+            return;
+        }
+        let old_snapshot = match SnapshotUtils::load(Path::new(&old_path)) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                self.diff_status = Some(e.to_string());
+                return;
+            }
+        };
+
+        if !new_path.is_empty() {
+            match SnapshotUtils::load(Path::new(&new_path)) {
+                Ok(new_snapshot) => {
+                    let diffs = SnapshotUtils::diff(&old_snapshot, &new_snapshot);
+                    self.diff_dialog_open = false;
+                    self.open_diff_view(format!("{old_path} → {new_path}"), diffs);
+                }
+                Err(e) => self.diff_status = Some(e.to_string()),
+            }
+            return;
+        }
+
+        let Ok(root_id) = NodeId::from_str(&old_snapshot.root) else {
+            self.diff_status = Some(format!("'{}' is not a NodeId", old_snapshot.root));
+            return;
+        };
+        log::info!(
+            "diff: comparing {old_path} with the live server below {root_id}, depth {}",
+            old_snapshot.depth
+        );
+
+        let (command_tx, mut command_rx) = mpsc::unbounded_channel::<DiffCommand>();
+        let (message_tx, message_rx) = mpsc::unbounded_channel::<DiffMessage>();
+        self.diff_command_tx = Some(command_tx);
+        self.diff_message_rx = Some(message_rx);
+        self.diff_dialog_open = false;
+        self.diff_progress_open = true;
+        self.diff_progress_message = format!("Capturing {root_id}...");
+
+        let client = self.client.clone();
+        let title = format!("{old_path} → live server");
+        tokio::spawn(async move {
+            let message = match Self::capture_for_diff(
+                &old_snapshot,
+                &root_id,
+                &client,
+                &message_tx,
+                &mut command_rx,
+            )
+            .await
+            {
+                Ok(Some(live)) => DiffMessage::Complete {
+                    title,
+                    diffs: SnapshotUtils::diff(&old_snapshot, &live),
+                },
+                Ok(None) => DiffMessage::Cancelled,
+                Err(e) => DiffMessage::Failed(e.to_string()),
+            };
+            let _ = message_tx.send(message);
+        });
+    }
+
+    /// Capture the live subtree with the root, depth and values of the old snapshot
+    async fn capture_for_diff(
+        old_snapshot: &Snapshot,
+        root_id: &NodeId,
+        client: &Arc<RwLock<OpcUaClientManager>>,
+        message_tx: &mpsc::UnboundedSender<DiffMessage>,
+        command_rx: &mut mpsc::UnboundedReceiver<DiffCommand>,
+    ) -> Result<Option<Snapshot>> {
+        let mut on_progress = |visited: usize, path: &str| {
+            if let Ok(DiffCommand::Cancel) = command_rx.try_recv() {
+                return false;
+            }
+            let _ = message_tx.send(DiffMessage::Progress {
+                visited,
+                current_node: path.to_string(),
+            });
+            true
+        };
+        SnapshotUtils::capture(
+            client,
+            root_id,
+            old_snapshot.depth,
+            old_snapshot.include_values,
+            &mut on_progress,
+        )
+        .await
+    }
+
+    /// Stop capturing the live server; no diff is shown
+    pub fn cancel_diff(&mut self) {
+        log::info!("diff: cancelling");
+        if let Some(tx) = &self.diff_command_tx {
+            let _ = tx.send(DiffCommand::Cancel);
+        }
+        self.diff_progress_open = false;
+        self.diff_command_tx = None;
+        self.diff_message_rx = None;
+    }
+
+    /// Apply progress of the live capture; the diff view opens when it completes
+    pub fn process_diff_messages(&mut self) {
+        let Some(rx) = &mut self.diff_message_rx else {
+            return;
+        };
+
+        let mut finished = false;
+        let mut result = None;
+        while let Ok(message) = rx.try_recv() {
+            match message {
+                DiffMessage::Progress {
+                    visited,
+                    current_node,
+                } => {
+                    self.diff_progress_message = format!("{visited} nodes: {current_node}");
+                }
+                DiffMessage::Complete { title, diffs } => {
+                    finished = true;
+                    result = Some(Ok((title, diffs)));
+                }
+                DiffMessage::Failed(e) => {
+                    log::error!("diff: {e}");
+                    finished = true;
+                    result = Some(Err(e));
+                }
+                DiffMessage::Cancelled => {
+                    log::info!("diff: cancelled");
+                    finished = true;
+                }
+            }
+        }
+
+        if finished {
+            self.diff_progress_open = false;
+            self.diff_command_tx = None;
+            self.diff_message_rx = None;
+            match result {
+                Some(Ok((title, diffs))) => self.open_diff_view(title, diffs),
+                Some(Err(e)) => {
+                    self.diff_status = Some(format!("Capture failed: {e}"));
+                    self.diff_dialog_open = true;
+                }
+                None => {}
+            }
+        }
+    }
+
+    fn open_diff_view(&mut self, title: String, diffs: Vec<NodeDiff>) {
+        log::info!("diff: {} node(s) differ", diffs.len());
+        self.diff_view = Some(DiffViewState {
+            title,
+            node_count: diffs.len(),
+            rows: Self::diff_rows(&diffs),
+            selected_row: 0,
+            page_size: 20,
+        });
+        self.diff_view_open = true;
+    }
+
+    /// Lay the differences out as a tree, each node followed by its changed attributes
+    fn diff_rows(diffs: &[NodeDiff]) -> Vec<DiffRow> {
+        let mut rows = Vec::new();
+        let mut previous: Vec<&str> = Vec::new();
+        for node in diffs {
+            let segments: Vec<&str> = node.path.split('/').collect();
+            let depth = segments.len() - 1;
+
+            // Unchanged ancestors not shown yet give the node its place in the tree
+            let shared = previous
+                .iter()
+                .zip(&segments)
+                .take_while(|(a, b)| a == b)
+                .count();
+            for (ancestor_depth, name) in segments.iter().enumerate().take(depth).skip(shared) {
+                rows.push(DiffRow::Node {
+                    kind: None,
+                    depth: ancestor_depth,
+                    name: name.to_string(),
+                    node_id: String::new(),
+                });
+            }
+
+            rows.push(DiffRow::Node {
+                kind: Some(node.kind),
+                depth,
+                name: segments[depth].to_string(),
+                node_id: node.node_id.clone(),
+            });
+            rows.extend(node.changes.iter().map(|change| DiffRow::Attribute {
+                depth,
+                change: change.clone(),
+            }));
+            previous = segments;
+        }
+        rows
+    }
+
+    pub async fn handle_diff_view_input(
+        &mut self,
+        key: KeyCode,
+        _modifiers: KeyModifiers,
+    ) -> Result<Option<ConnectionStatus>> {
+        let Some(state) = &mut self.diff_view else {
+            self.diff_view_open = false;
+            return Ok(None);
+        };
+
+        let last_row = state.rows.len().saturating_sub(1);
+        match key {
+            KeyCode::Esc => {
+                self.diff_view_open = false;
+                self.diff_view = None;
+            }
+            KeyCode::Up => state.selected_row = state.selected_row.saturating_sub(1),
+            KeyCode::Down => state.selected_row = (state.selected_row + 1).min(last_row),
+            KeyCode::PageUp | KeyCode::Left => {
+                state.selected_row = state.selected_row.saturating_sub(state.page_size);
+            }
+            KeyCode::PageDown | KeyCode::Right => {
+                state.selected_row = (state.selected_row + state.page_size).min(last_row);
+            }
+            KeyCode::Home => state.selected_row = 0,
+            KeyCode::End => state.selected_row = last_row,
+            _ => {}
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,468 @@
+use super::types::{ExportCommand, ExportField, ExportFormat, ExportMessage, NodeType, TreeNode};
+use crate::client::{ConnectionStatus, OpcUaClientManager, OpcUaExportAttributes, OpcUaNode};
+use crate::export_utils::ExportUtils;
+use crate::node_utils::NodeUtils;
+use crate::nodeset_utils::{NodeSetNode, NodeSetUtils};
+use crate::snapshot_utils::SnapshotUtils;
+use crate::value_utils::ValueUtils;
+use anyhow::{anyhow, Result};
+use crossterm::event::{KeyCode, KeyModifiers};
+use opcua::types::{NodeClass, NodeId};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::{mpsc, RwLock};
+use tui_input::backend::crossterm::EventHandler;
+
+// Deepest subtree the export dialog accepts, relative to the selected node
+const MAX_EXPORT_DEPTH: usize = 20;
+
+/// A node of the exported subtree, in depth-first order
+struct ExportedNode {
+    depth: usize, // 0 for the selected node
+    path: String,
+    node_id: NodeId,
+    browse_name: String,
+    display_name: String,
+    node_class: NodeClass,
+    attributes: OpcUaExportAttributes,
+}
+
+impl super::BrowseScreen {
+    /// Open the export dialog for the subtree of the selected node
+    pub fn open_export_dialog(&mut self) {
+        let Some(node) = self.tree_nodes.get(self.selected_node_index) else {
+            return;
+        };
+        if node.opcua_node_id.is_none() {
+            log::warn!("export: no real NodeId available for selected node");
+            return;
+        }
+
+        self.export_target = Some(node.clone());
+        self.export_focus = ExportField::Depth;
+        self.export_status = None;
+        self.export_dialog_open = true;
+    }
+
+    pub async fn handle_export_dialog_input(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Result<Option<ConnectionStatus>> {
+        match key {
+            KeyCode::Esc => {
+                self.export_dialog_open = false;
+            }
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                self.export_focus = match self.export_focus {
+                    ExportField::Depth => ExportField::Format,
+                    ExportField::Format => ExportField::Depth,
+                };
+            }
+            KeyCode::Right | KeyCode::Char(' ') if self.export_focus == ExportField::Format => {
+                self.export_format = self.export_format.next();
+            }
+            KeyCode::Left if self.export_focus == ExportField::Format => {
+                self.export_format = self.export_format.previous();
+            }
+            KeyCode::Enter => {
+                self.start_subtree_export();
+            }
+            _ if self.export_focus == ExportField::Depth => {
+                self.export_depth_input
+                    .handle_event(&crossterm::event::Event::Key(
+                        crossterm::event::KeyEvent::new(key, modifiers),
+                    ));
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    /// Validate the dialog and start browsing the subtree in a background task
+    fn start_subtree_export(&mut self) {
+        let Some(target) = self.export_target.clone() else {
+            self.export_dialog_open = false;
+            return;
+        };
+        let max_depth = match self.export_depth_input.value().trim().parse::<usize>() {
+            Ok(depth) if (1..=MAX_EXPORT_DEPTH).contains(&depth) => depth,
+            _ => {
+                self.export_status = Some((
+                    format!("Depth must be a number from 1 to {MAX_EXPORT_DEPTH}"),
+                    false,
+                ));
+                return;
+            }
+        };
+        let format = self.export_format;
+
+        log::info!(
+            "export: exporting '{}' to {} with depth {max_depth}",
+            target.name,
+            format.label()
+        );
+
+        let (command_tx, mut command_rx) = mpsc::unbounded_channel::<ExportCommand>();
+        let (message_tx, message_rx) = mpsc::unbounded_channel::<ExportMessage>();
+        self.export_command_tx = Some(command_tx);
+        self.export_message_rx = Some(message_rx);
+        self.export_dialog_open = false;
+        self.export_progress_open = true;
+        self.export_progress_message = format!("Exporting '{}'...", target.name);
+
+        let client = self.client.clone();
+        tokio::spawn(async move {
+            let message = match Self::export_subtree_task(
+                &target,
+                max_depth,
+                format,
+                &client,
+                &message_tx,
+                &mut command_rx,
+            )
+            .await
+            {
+                Ok(Some((path, node_count))) => ExportMessage::Complete { path, node_count },
+                Ok(None) => ExportMessage::Cancelled,
+                Err(e) => ExportMessage::Failed(e.to_string()),
+            };
+            let _ = message_tx.send(message);
+        });
+    }
+
+    /// Stop the running export; nothing is written
+    pub fn cancel_export(&mut self) {
+        log::info!("export: cancelling");
+        if let Some(tx) = &self.export_command_tx {
+            let _ = tx.send(ExportCommand::Cancel);
+        }
+        self.export_progress_open = false;
+        self.export_command_tx = None;
+        self.export_message_rx = None;
+    }
+
+    /// Apply progress from the background export; the dialog shows the outcome when it ends
+    pub fn process_export_messages(&mut self) {
+        let Some(rx) = &mut self.export_message_rx else {
+            return;
+        };
+
+        let mut outcome = None;
+        while let Ok(message) = rx.try_recv() {
+            match message {
+                ExportMessage::Progress {
+                    visited,
+                    current_node,
+                } => {
+                    self.export_progress_message = format!("{visited} nodes: {current_node}");
+                }
+                ExportMessage::Complete { path, node_count } => {
+                    log::info!("export: wrote {node_count} nodes to {}", path.display());
+                    outcome = Some(Some((
+                        format!("Exported {node_count} nodes to {}", path.display()),
+                        true,
+                    )));
+                }
+                ExportMessage::Failed(e) => {
+                    log::error!("export: {e}");
+                    outcome = Some(Some((format!("Export failed: {e}"), false)));
+                }
+                ExportMessage::Cancelled => {
+                    log::info!("export: cancelled");
+                    outcome = Some(None);
+                }
+            }
+        }
+
+        if let Some(status) = outcome {
+            self.export_progress_open = false;
+            self.export_command_tx = None;
+            self.export_message_rx = None;
+            if status.is_some() {
+                self.export_status = status;
+                self.export_dialog_open = true;
+            }
+        }
+    }
+
+    /// Browse the subtree depth-first and write the file. Returns the path and node count, or
+    /// `None` when cancelled. The attributes of each node's children are read in one request.
+    async fn export_subtree_task(
+        target: &TreeNode,
+        max_depth: usize,
+        format: ExportFormat,
+        client: &Arc<RwLock<OpcUaClientManager>>,
+        message_tx: &mpsc::UnboundedSender<ExportMessage>,
+        command_rx: &mut mpsc::UnboundedReceiver<ExportCommand>,
+    ) -> Result<Option<(PathBuf, usize)>> {
+        let root_id = target
+            .opcua_node_id
+            .clone()
+            .ok_or_else(|| anyhow!("Selected node has no NodeId"))?;
+        if format == ExportFormat::Snapshot {
+            return Self::export_snapshot(
+                target, &root_id, max_depth, client, message_tx, command_rx,
+            )
+            .await;
+        }
+
+        let root = {
+            let client_guard = client.read().await;
+            let search_attributes = client_guard
+                .read_nodes_search_attributes(std::slice::from_ref(&root_id), false)
+                .await?
+                .pop()
+                .ok_or_else(|| anyhow!("No attributes for {}", root_id))?;
+            let attributes = client_guard
+                .read_nodes_export_attributes(std::slice::from_ref(&root_id))
+                .await?
+                .pop()
+                .unwrap_or_default();
+            ExportedNode {
+                depth: 0,
+                path: NodeUtils::generate_node_path(target),
+                node_id: root_id,
+                browse_name: search_attributes.browse_name,
+                display_name: target.name.clone(),
+                node_class: search_attributes.node_class,
+                attributes,
+            }
+        };
+
+        // A node under two parents, or in a cycle such as HasNotifier, is exported once; the
+        // NodeSet keeps the references from every parent
+        let mut visited: HashSet<NodeId> = HashSet::new();
+        let mut nodes: Vec<ExportedNode> = Vec::new();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if let Ok(ExportCommand::Cancel) = command_rx.try_recv() {
+                return Ok(None);
+            }
+            if !visited.insert(node.node_id.clone()) {
+                continue;
+            }
+            let _ = message_tx.send(ExportMessage::Progress {
+                visited: nodes.len() + 1,
+                current_node: format!("{} [{}]", node.display_name, node.node_id),
+            });
+
+            // Method arguments are not part of the tree, so Methods are exported as leaves,
+            // except in a NodeSet where a Method needs its argument properties to be callable
+            let descend = node.node_class != NodeClass::Method || format == ExportFormat::NodeSet2;
+            if node.depth < max_depth && descend {
+                let children = Self::read_export_children(&node, client).await?;
+                stack.extend(children.into_iter().rev());
+            }
+            nodes.push(node);
+        }
+
+        let path = ExportUtils::timestamped_file_name("export", &target.name, format.extension());
+        match format {
+            ExportFormat::NodeSet2 => {
+                let Some(nodeset_nodes) =
+                    Self::read_nodeset_references(nodes, client, message_tx, command_rx).await?
+                else {
+                    return Ok(None);
+                };
+                let namespace_array = client.read().await.read_namespace_array().await?;
+                let xml = NodeSetUtils::build_nodeset(&namespace_array, &nodeset_nodes);
+                std::fs::write(&path, xml)?;
+                return Ok(Some((path, nodeset_nodes.len())));
+            }
+            ExportFormat::Snapshot => unreachable!("captured above"),
+            ExportFormat::Json => {
+                let rows = nodes.iter().map(|node| {
+                    let value = json!({
+                        "node_id": node.node_id.to_string(),
+                        "browse_name": node.browse_name,
+                        "display_name": node.display_name,
+                        "node_class": format!("{:?}", node.node_class),
+                        "data_type": Self::export_data_type(node),
+                        "access_level": Self::export_access_level(node),
+                        "value": node.attributes.value.as_ref().map(ValueUtils::to_json),
+                    });
+                    (node.depth, value)
+                });
+                ExportUtils::write_json(&path, &Value::Array(ExportUtils::nest_json(rows)))?;
+            }
+            ExportFormat::Csv => {
+                let rows: Vec<Vec<String>> = nodes
+                    .iter()
+                    .map(|node| {
+                        vec![
+                            node.path.clone(),
+                            node.node_id.to_string(),
+                            node.browse_name.clone(),
+                            node.display_name.clone(),
+                            format!("{:?}", node.node_class),
+                            Self::export_data_type(node).unwrap_or_default(),
+                            Self::export_access_level(node).unwrap_or_default(),
+                            node.attributes
+                                .value
+                                .as_ref()
+                                .map(ValueUtils::format_for_edit)
+                                .unwrap_or_default(),
+                        ]
+                    })
+                    .collect();
+                ExportUtils::write_csv(
+                    &path,
+                    &[
+                        "Path",
+                        "NodeId",
+                        "BrowseName",
+                        "DisplayName",
+                        "NodeClass",
+                        "DataType",
+                        "AccessLevel",
+                        "Value",
+                    ],
+                    &rows,
+                )?;
+            }
+        }
+        Ok(Some((path, nodes.len())))
+    }
+
+    /// Capture a snapshot including values for a later compare, or `None` when cancelled
+    async fn export_snapshot(
+        target: &TreeNode,
+        root_id: &NodeId,
+        max_depth: usize,
+        client: &Arc<RwLock<OpcUaClientManager>>,
+        message_tx: &mpsc::UnboundedSender<ExportMessage>,
+        command_rx: &mut mpsc::UnboundedReceiver<ExportCommand>,
+    ) -> Result<Option<(PathBuf, usize)>> {
+        let mut on_progress = |visited: usize, path: &str| {
+            if let Ok(ExportCommand::Cancel) = command_rx.try_recv() {
+                return false;
+            }
+            let _ = message_tx.send(ExportMessage::Progress {
+                visited,
+                current_node: path.to_string(),
+            });
+            true
+        };
+        let Some(snapshot) =
+            SnapshotUtils::capture(client, root_id, max_depth, true, &mut on_progress).await?
+        else {
+            return Ok(None);
+        };
+
+        let path = ExportUtils::timestamped_file_name("snapshot", &target.name, "json");
+        SnapshotUtils::save(&path, &snapshot)?;
+        Ok(Some((path, snapshot.nodes.len())))
+    }
+
+    /// Browse the references of every exported node for the NodeSet, or `None` when cancelled
+    async fn read_nodeset_references(
+        nodes: Vec<ExportedNode>,
+        client: &Arc<RwLock<OpcUaClientManager>>,
+        message_tx: &mpsc::UnboundedSender<ExportMessage>,
+        command_rx: &mut mpsc::UnboundedReceiver<ExportCommand>,
+    ) -> Result<Option<Vec<NodeSetNode>>> {
+        let total = nodes.len();
+        let mut nodeset_nodes = Vec::with_capacity(total);
+        for (index, node) in nodes.into_iter().enumerate() {
+            if let Ok(ExportCommand::Cancel) = command_rx.try_recv() {
+                return Ok(None);
+            }
+            let _ = message_tx.send(ExportMessage::Progress {
+                visited: total,
+                current_node: format!("references {}/{total} [{}]", index + 1, node.node_id),
+            });
+
+            let references = client.read().await.browse_references(&node.node_id).await?;
+            nodeset_nodes.push(NodeSetNode {
+                node_id: node.node_id,
+                node_class: node.node_class,
+                display_name: node.display_name,
+                attributes: node.attributes,
+                references,
+            });
+        }
+        Ok(Some(nodeset_nodes))
+    }
+
+    /// Browse the children of an exported node, sorted like the tree, with their attributes
+    async fn read_export_children(
+        parent: &ExportedNode,
+        client: &Arc<RwLock<OpcUaClientManager>>,
+    ) -> Result<Vec<ExportedNode>> {
+        let client_guard = client.read().await;
+        let browsed = match client_guard.browse_node(&parent.node_id).await {
+            Ok(browsed) => browsed,
+            Err(e) => {
+                log::warn!("export: failed to browse {}: {e}", parent.node_id);
+                return Ok(Vec::new());
+            }
+        };
+        let mut children: Vec<(NodeType, OpcUaNode)> = browsed
+            .into_iter()
+            .filter_map(|child| Some((NodeType::from_node_class(child.node_class)?, child)))
+            .collect();
+        children.sort_by(|(type_a, a), (type_b, b)| {
+            type_a
+                .get_sort_priority()
+                .cmp(&type_b.get_sort_priority())
+                .then_with(|| {
+                    a.display_name
+                        .to_lowercase()
+                        .cmp(&b.display_name.to_lowercase())
+                })
+        });
+
+        let child_ids: Vec<NodeId> = children
+            .iter()
+            .map(|(_, child)| child.node_id.clone())
+            .collect();
+        let attributes = client_guard
+            .read_nodes_export_attributes(&child_ids)
+            .await?;
+
+        Ok(children
+            .into_iter()
+            .zip(attributes)
+            .map(|((node_type, child), attributes)| {
+                // Same path the tree shows once the child is expanded
+                let tree_node = TreeNode {
+                    name: child.display_name.clone(),
+                    node_id: child.node_id.to_string(),
+                    opcua_node_id: Some(child.node_id.clone()),
+                    node_type,
+                    level: parent.depth + 1,
+                    has_children: child.has_children,
+                    children_checked: false,
+                    is_expanded: false,
+                    parent_path: parent.path.clone(),
+                    continuation_point: None,
+                };
+                ExportedNode {
+                    depth: parent.depth + 1,
+                    path: NodeUtils::generate_node_path(&tree_node),
+                    node_id: child.node_id,
+                    browse_name: child.browse_name,
+                    display_name: child.display_name,
+                    node_class: child.node_class,
+                    attributes,
+                }
+            })
+            .collect())
+    }
+
+    fn export_data_type(node: &ExportedNode) -> Option<String> {
+        node.attributes
+            .data_type
+            .as_ref()
+            .map(OpcUaClientManager::format_data_type)
+    }
+
+    fn export_access_level(node: &ExportedNode) -> Option<String> {
+        node.attributes
+            .access_level
+            .map(OpcUaClientManager::format_access_level)
+    }
+}
@@ -1,5 +1,12 @@
-use crate::client::OpcUaClientManager;
-use opcua::types::NodeId;
+use crate::client::{
+    BrowseRoot, ConditionAction, ConnectionStatus, HistoryQuery, HistoryReadMode,
+    MonitoringSettings, OpcUaClientManager, OpcUaDataChange, OpcUaEvent, OpcUaReference,
+    OpcUaValueInfo,
+};
+use crate::event_utils::DEFAULT_EVENT_SELECT;
+use crate::snapshot_utils::{AttributeChange, DiffKind, NodeDiff};
+use opcua::types::{argument::Argument, ByteString, EventFilter, NodeClass, NodeId};
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::{mpsc, RwLock};
 use tui_input::Input;
@@ -13,8 +20,11 @@ pub struct TreeNode {
     pub node_type: NodeType,
     pub level: usize,
     pub has_children: bool,
+    pub children_checked: bool, // `has_children` was confirmed with a Browse, not guessed
     pub is_expanded: bool,
     pub parent_path: String,
+    // Set on the "load more" row that ends a partially browsed folder; null on a retry row
+    pub continuation_point: Option<ByteString>,
 }
 
 #[derive(Clone)]
@@ -22,6 +32,15 @@ pub struct NodeAttribute {
     pub name: String,
     pub value: String,
     pub is_value_good: bool, // True if this is a Value attribute with Good status
+    pub depth: usize,        // 0 for attributes, >0 for decoded structure and array fields
+    pub path: String,        // Attribute and field names joined by '/', used as expansion key
+}
+
+/// Tabs of the panel next to the tree
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AttributesTab {
+    Attributes,
+    References,
 }
 
 #[derive(Clone, Debug)]
@@ -37,6 +56,21 @@ pub enum NodeType {
 }
 
 impl NodeType {
+    /// Tree node type of a NodeClass, `None` for classes the tree does not show
+    pub fn from_node_class(node_class: NodeClass) -> Option<Self> {
+        match node_class {
+            NodeClass::Object => Some(NodeType::Object),
+            NodeClass::Variable => Some(NodeType::Variable),
+            NodeClass::Method => Some(NodeType::Method),
+            NodeClass::View => Some(NodeType::View),
+            NodeClass::ObjectType => Some(NodeType::ObjectType),
+            NodeClass::VariableType => Some(NodeType::VariableType),
+            NodeClass::DataType => Some(NodeType::DataType),
+            NodeClass::ReferenceType => Some(NodeType::ReferenceType),
+            NodeClass::Unspecified => None,
+        }
+    }
+
     /// Get sorting priority for nodes in the browse tree
     /// Lower numbers = higher priority (sorted first)
     pub fn get_sort_priority(&self) -> u8 {
@@ -81,9 +115,254 @@ pub enum SearchCommand {
     Cancel,
 }
 
+/// File format of the subtree export
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    Json,     // Nested objects following the tree
+    Csv,      // One row per node with its path
+    NodeSet2, // UA NodeSet2 XML with references, loadable into other servers
+    Snapshot, // Attributes and values of every node, for comparing later
+}
+
+impl ExportFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "JSON (nested)",
+            ExportFormat::Csv => "CSV (flat)",
+            ExportFormat::NodeSet2 => "NodeSet2 XML",
+            ExportFormat::Snapshot => "Snapshot (for diff)",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+            ExportFormat::NodeSet2 => "xml",
+            ExportFormat::Snapshot => "json",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ExportFormat::Json => ExportFormat::Csv,
+            ExportFormat::Csv => ExportFormat::NodeSet2,
+            ExportFormat::NodeSet2 => ExportFormat::Snapshot,
+            ExportFormat::Snapshot => ExportFormat::Json,
+        }
+    }
+
+    pub fn previous(&self) -> Self {
+        match self {
+            ExportFormat::Json => ExportFormat::Snapshot,
+            ExportFormat::Csv => ExportFormat::Json,
+            ExportFormat::NodeSet2 => ExportFormat::Csv,
+            ExportFormat::Snapshot => ExportFormat::NodeSet2,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExportField {
+    Depth,
+    Format,
+}
+
+#[derive(Debug)]
+pub enum ExportMessage {
+    Progress {
+        visited: usize,
+        current_node: String,
+    },
+    Complete {
+        path: PathBuf,
+        node_count: usize,
+    },
+    Failed(String),
+    Cancelled,
+}
+
+#[derive(Debug)]
+pub enum ExportCommand {
+    Cancel,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DiffField {
+    Old,
+    New,
+}
+
+#[derive(Debug)]
+pub enum DiffMessage {
+    Progress {
+        visited: usize,
+        current_node: String,
+    },
+    Complete {
+        title: String,
+        diffs: Vec<NodeDiff>,
+    },
+    Failed(String),
+    Cancelled,
+}
+
+#[derive(Debug)]
+pub enum DiffCommand {
+    Cancel,
+}
+
+/// A line of the snapshot diff view
+pub enum DiffRow {
+    Node {
+        kind: Option<DiffKind>, // None for an unchanged ancestor shown for context
+        depth: usize,
+        name: String,
+        node_id: String,
+    },
+    Attribute {
+        depth: usize,
+        change: AttributeChange,
+    },
+}
+
+/// State of the snapshot diff view
+pub struct DiffViewState {
+    pub title: String,
+    pub rows: Vec<DiffRow>,
+    pub node_count: usize, // Nodes that differ
+    pub selected_row: usize,
+    pub page_size: usize, // Rows visible in the table, updated on render
+}
+
+/// A Variable pinned to the watch list, backed by a monitored item
+#[derive(Clone, Debug)]
+pub struct WatchItem {
+    pub client_handle: u32,
+    pub monitored_item_id: u32,
+    pub node_id: NodeId,
+    pub name: String,
+    pub settings: MonitoringSettings,
+    pub value: String,
+    pub status: String,
+    pub source_timestamp: String,
+    pub is_value_good: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum WatchSettingsField {
+    SamplingInterval,
+    QueueSize,
+    Deadband,
+}
+
+/// The Method being called from the method call dialog, with one input per argument
+pub struct MethodCallState {
+    pub object_id: NodeId,
+    pub method_id: NodeId,
+    pub method_name: String,
+    pub input_arguments: Vec<Argument>,
+    pub output_arguments: Vec<Argument>,
+    pub inputs: Vec<Input>,
+    pub focused_input: usize,
+    pub outcome: Option<MethodCallOutcome>,
+}
+
+/// Result of the last call, formatted for display
+#[derive(Clone, Debug)]
+pub struct MethodCallOutcome {
+    pub status: String,
+    pub is_good: bool,
+    pub lines: Vec<(String, String)>, // Output arguments and per-argument input results
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum HistoryField {
+    StartTime,
+    EndTime,
+    Mode,
+    Interval,
+    Table,
+}
+
+/// A history value formatted for the table and CSV export
+#[derive(Clone, Debug)]
+pub struct HistoryRow {
+    pub source_timestamp: String,
+    pub server_timestamp: String,
+    pub value: String,
+    pub status: String,
+    pub is_value_good: bool,
+}
+
+/// State of the history viewer for one historizing Variable
+pub struct HistoryViewState {
+    pub node_id: NodeId,
+    pub node_name: String,
+    pub mode: HistoryReadMode,
+    pub start_input: Input,
+    pub end_input: Input,
+    pub interval_input: Input,
+    pub focus: HistoryField,
+    pub rows: Vec<HistoryRow>,
+    pub selected_row: usize,
+    pub query: Option<HistoryQuery>, // Query that produced `rows`, used for paging
+    pub continuation_point: Option<ByteString>,
+    pub status_message: Option<(String, bool)>, // Message and whether it is a success
+}
+
+/// State of the array viewer for one array-valued Variable
+pub struct ArrayViewState {
+    pub node_id: NodeId,
+    pub node_name: String,
+    pub range_input: Input, // Index range such as "10:20"; empty reads the whole array
+    pub range_focused: bool,
+    pub value_type: String,
+    pub dimensions: Vec<u32>, // Dimensions of the loaded elements
+    pub offsets: Vec<u32>,    // First index of the loaded slice in each dimension
+    pub elements: Vec<String>,
+    pub selected_row: usize,
+    pub page_size: usize, // Rows visible in the table, updated on render
+    pub status_message: Option<(String, bool)>, // Message and whether it is a success
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EventFilterField {
+    Select,
+    Where,
+}
+
+/// An event received by the event monitor, formatted for display
+#[derive(Clone, Debug)]
+pub struct EventRow {
+    pub fields: Vec<String>,          // One entry per selected column
+    pub event_id: Option<ByteString>, // Needed for condition method calls
+    pub condition_id: Option<NodeId>, // Only set for ConditionType events
+}
+
+/// Event subscription on a notifier node and the events received so far (newest first)
+pub struct EventMonitor {
+    pub notifier_id: NodeId,
+    pub notifier_name: String,
+    pub columns: Vec<String>,
+    pub filter: EventFilter, // Kept to re-create the subscription after a reconnect
+    pub events: Vec<EventRow>,
+    pub selected_index: usize,
+}
+
+/// The Variable node being edited in the write dialog
+#[derive(Clone, Debug)]
+pub struct ValueWriteTarget {
+    pub node_id: NodeId,
+    pub node_name: String,
+    pub info: OpcUaValueInfo,
+}
+
 pub struct BrowseScreen {
     // Tree navigation state
     pub tree_nodes: Vec<TreeNode>,
+    pub browse_root: BrowseRoot, // Folder whose children form the top level of the tree
+    pub stale_continuation_points: Vec<ByteString>, // Removed "load more" rows, released on tick
     pub selected_node_index: usize,
     pub expanded_nodes: std::collections::HashSet<String>,
     pub scroll_offset: usize,
@@ -92,9 +371,17 @@ pub struct BrowseScreen {
     // Attributes panel state
     pub selected_attributes: Vec<NodeAttribute>,
     pub attribute_scroll_offset: usize,
+    pub attributes_focused: bool,
+    pub attribute_selected_index: usize, // Index into the visible attribute rows
+    pub expanded_attributes: std::collections::HashSet<String>,
+    pub attributes_tab: AttributesTab,
+    pub selected_references: Vec<OpcUaReference>,
+    pub reference_selected_index: usize,
+    pub reference_scroll_offset: usize,
 
     // Connection info
     pub server_url: String,
+    pub connection_status: ConnectionStatus, // Updated from the client manager on every tick
 
     // Mouse state for double-click detection
     pub last_click_time: Option<std::time::Instant>,
@@ -127,6 +414,89 @@ pub struct BrowseScreen {
     // Log viewer
     pub log_viewer_open: bool,
     pub logger_widget_state: TuiWidgetState,
+
+    // Value write dialog
+    pub write_dialog_open: bool,
+    pub write_input: Input,
+    pub write_target: Option<ValueWriteTarget>,
+    pub write_status: Option<(String, bool)>, // Message and whether it is a success
+
+    // Watch list backed by a subscription
+    pub watch_items: Vec<WatchItem>,
+    pub watch_selected_index: usize,
+    pub watch_focused: bool,
+    pub next_watch_handle: u32,
+    pub watch_message_rx: Option<mpsc::UnboundedReceiver<OpcUaDataChange>>,
+
+    // Watch item settings dialog
+    pub watch_settings_open: bool,
+    pub watch_settings_focus: WatchSettingsField,
+    pub watch_sampling_input: Input,
+    pub watch_queue_input: Input,
+    pub watch_deadband_input: Input,
+    pub watch_settings_error: Option<String>,
+
+    // Method call dialog
+    pub method_dialog_open: bool,
+    pub method_call: Option<MethodCallState>,
+
+    // History viewer
+    pub history_view_open: bool,
+    pub history_view: Option<HistoryViewState>,
+
+    // Array element viewer
+    pub array_view_open: bool,
+    pub array_view: Option<ArrayViewState>,
+
+    // Event subscription dialog
+    pub event_dialog_open: bool,
+    pub event_dialog_focus: EventFilterField,
+    pub event_dialog_target: Option<(NodeId, String)>, // Notifier NodeId and name
+    pub event_select_input: Input,
+    pub event_where_input: Input,
+    pub event_dialog_error: Option<String>,
+
+    // Event monitor panel
+    pub event_monitor: Option<EventMonitor>,
+    pub events_focused: bool,
+    pub event_message_rx: Option<mpsc::UnboundedReceiver<OpcUaEvent>>,
+
+    // Condition Acknowledge/Confirm/AddComment dialog
+    pub condition_dialog_open: bool,
+    pub condition_action: ConditionAction,
+    pub condition_comment_input: Input,
+    pub condition_status: Option<String>, // Error from the last attempt
+
+    // Subtree export dialog
+    pub export_dialog_open: bool,
+    pub export_target: Option<TreeNode>,
+    pub export_focus: ExportField,
+    pub export_depth_input: Input,
+    pub export_format: ExportFormat,
+    pub export_status: Option<(String, bool)>, // Message and whether it is a success
+
+    // Progress of the background subtree export
+    pub export_progress_open: bool,
+    pub export_progress_message: String,
+    pub export_command_tx: Option<mpsc::UnboundedSender<ExportCommand>>,
+    pub export_message_rx: Option<mpsc::UnboundedReceiver<ExportMessage>>,
+
+    // Snapshot compare dialog
+    pub diff_dialog_open: bool,
+    pub diff_focus: DiffField,
+    pub diff_old_input: Input,
+    pub diff_new_input: Input, // Empty compares against the live server
+    pub diff_status: Option<String>, // Error from the last attempt
+
+    // Progress of capturing the live server for a compare
+    pub diff_progress_open: bool,
+    pub diff_progress_message: String,
+    pub diff_command_tx: Option<mpsc::UnboundedSender<DiffCommand>>,
+    pub diff_message_rx: Option<mpsc::UnboundedReceiver<DiffMessage>>,
+
+    // Snapshot diff view
+    pub diff_view_open: bool,
+    pub diff_view: Option<DiffViewState>,
 }
 
 impl BrowseScreen {
@@ -134,13 +504,23 @@ impl BrowseScreen {
         // Real data will be loaded asynchronously via load_real_tree() from real_data.rs
         Self {
             tree_nodes: Vec::new(),
+            browse_root: BrowseRoot::Objects,
+            stale_continuation_points: Vec::new(),
             selected_node_index: 0,
             expanded_nodes: std::collections::HashSet::new(),
             scroll_offset: 0,
             current_visible_height: 20, // Default height, will be updated in render
             selected_attributes: Vec::new(),
             attribute_scroll_offset: 0,
+            attributes_focused: false,
+            attribute_selected_index: 0,
+            expanded_attributes: std::collections::HashSet::new(),
+            attributes_tab: AttributesTab::Attributes,
+            selected_references: Vec::new(),
+            reference_selected_index: 0,
+            reference_scroll_offset: 0,
             server_url,
+            connection_status: ConnectionStatus::Connected,
             last_click_time: None,
             last_click_position: None,
             client,
@@ -161,11 +541,71 @@ impl BrowseScreen {
             search_message_rx: None,
             log_viewer_open: false,
             logger_widget_state: TuiWidgetState::new(),
+            write_dialog_open: false,
+            write_input: Input::default(),
+            write_target: None,
+            write_status: None,
+            watch_items: Vec::new(),
+            watch_selected_index: 0,
+            watch_focused: false,
+            next_watch_handle: 1,
+            watch_message_rx: None,
+            watch_settings_open: false,
+            watch_settings_focus: WatchSettingsField::SamplingInterval,
+            watch_sampling_input: Input::default(),
+            watch_queue_input: Input::default(),
+            watch_deadband_input: Input::default(),
+            watch_settings_error: None,
+            method_dialog_open: false,
+            method_call: None,
+            history_view_open: false,
+            history_view: None,
+            array_view_open: false,
+            array_view: None,
+            event_dialog_open: false,
+            event_dialog_focus: EventFilterField::Select,
+            event_dialog_target: None,
+            event_select_input: Input::new(DEFAULT_EVENT_SELECT.to_string()),
+            event_where_input: Input::default(),
+            event_dialog_error: None,
+            event_monitor: None,
+            events_focused: false,
+            event_message_rx: None,
+            condition_dialog_open: false,
+            condition_action: ConditionAction::Acknowledge,
+            condition_comment_input: Input::default(),
+            condition_status: None,
+            export_dialog_open: false,
+            export_target: None,
+            export_focus: ExportField::Depth,
+            export_depth_input: Input::new("3".to_string()),
+            export_format: ExportFormat::Json,
+            export_status: None,
+            export_progress_open: false,
+            export_progress_message: String::new(),
+            export_command_tx: None,
+            export_message_rx: None,
+            diff_dialog_open: false,
+            diff_focus: DiffField::Old,
+            diff_old_input: Input::default(),
+            diff_new_input: Input::default(),
+            diff_status: None,
+            diff_progress_open: false,
+            diff_progress_message: String::new(),
+            diff_command_tx: None,
+            diff_message_rx: None,
+            diff_view_open: false,
+            diff_view: None,
         }
     }
 }
 
 impl TreeNode {
+    /// Whether this is the "load more" row of a folder with unbrowsed children
+    pub fn is_load_more(&self) -> bool {
+        self.continuation_point.is_some()
+    }
+
     /// Determines if this node should show an expand indicator based on its type
     /// following OPC UA best practices
     pub fn should_show_expand_indicator(&self) -> bool {
@@ -177,10 +617,10 @@ impl TreeNode {
             NodeType::VariableType => true,
             NodeType::DataType => true,
             NodeType::ReferenceType => true,
+            NodeType::Variable => true, // Expandable when it has properties or components
 
             // Leaves - don't show expand
             NodeType::Method => false,
-            NodeType::Variable => false, // Usually a leaf, may have properties but not interesting for browsing
         }
     }
 }
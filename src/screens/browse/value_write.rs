@@ -0,0 +1,161 @@
+use super::types::{NodeType, ValueWriteTarget};
+use crate::client::{ConnectionStatus, OpcUaClientManager};
+use crate::value_utils::ValueUtils;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+
+// UserAccessLevel bit that allows writing the current value
+const ACCESS_LEVEL_CURRENT_WRITE: u8 = 0x02;
+
+impl super::BrowseScreen {
+    /// Open the value write dialog for the selected Variable node
+    pub async fn open_write_dialog(&mut self) {
+        let Some(node) = self.tree_nodes.get(self.selected_node_index) else {
+            return;
+        };
+        if !matches!(node.node_type, NodeType::Variable) {
+            log::info!("write: selected node '{}' is not a Variable", node.name);
+            return;
+        }
+        let Some(node_id) = node.opcua_node_id.clone() else {
+            log::warn!("write: no real NodeId available for selected node");
+            return;
+        };
+        let node_name = node.name.clone();
+
+        let info = {
+            let client_guard = self.client.read().await;
+            client_guard.read_value_info(&node_id).await
+        };
+
+        match info {
+            Ok(info) => {
+                let initial_text = info
+                    .current_value
+                    .as_ref()
+                    .map(ValueUtils::format_for_edit)
+                    .unwrap_or_default();
+
+                self.write_status = if info.user_access_level & ACCESS_LEVEL_CURRENT_WRITE == 0 {
+                    Some((
+                        format!(
+                            "Not writable - UserAccessLevel: {}",
+                            OpcUaClientManager::format_access_level(info.user_access_level)
+                        ),
+                        false,
+                    ))
+                } else {
+                    None
+                };
+                self.write_input = Input::new(initial_text);
+                self.write_target = Some(ValueWriteTarget {
+                    node_id,
+                    node_name,
+                    info,
+                });
+                self.write_dialog_open = true;
+            }
+            Err(e) => {
+                log::error!("write: failed to read value info for {node_id}: {e}");
+            }
+        }
+    }
+
+    pub fn close_write_dialog(&mut self) {
+        self.write_dialog_open = false;
+        self.write_target = None;
+        self.write_status = None;
+        self.write_input.reset();
+    }
+
+    /// Whether the node in the write dialog grants CurrentWrite to the current user
+    pub fn write_target_is_writable(&self) -> bool {
+        self.write_target
+            .as_ref()
+            .map(|target| target.info.user_access_level & ACCESS_LEVEL_CURRENT_WRITE != 0)
+            .unwrap_or(false)
+    }
+
+    pub async fn handle_write_input(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Result<Option<ConnectionStatus>> {
+        match key {
+            KeyCode::Esc => {
+                self.close_write_dialog();
+            }
+            KeyCode::Enter => {
+                self.submit_write().await;
+            }
+            _ => {
+                self.write_input.handle_event(&crossterm::event::Event::Key(
+                    crossterm::event::KeyEvent::new(key, modifiers),
+                ));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn submit_write(&mut self) {
+        let Some(target) = self.write_target.clone() else {
+            return;
+        };
+
+        if !self.write_target_is_writable() {
+            log::warn!(
+                "write: node {} does not allow CurrentWrite for this user",
+                target.node_id
+            );
+            return;
+        }
+
+        let Some(value_type) = ValueUtils::resolve_scalar_type(
+            &target.info.data_type,
+            target.info.current_value.as_ref(),
+        ) else {
+            self.write_status = Some((
+                format!(
+                    "Unsupported DataType {}",
+                    OpcUaClientManager::format_data_type(&target.info.data_type)
+                ),
+                false,
+            ));
+            return;
+        };
+
+        let value = match ValueUtils::parse_variant(
+            self.write_input.value(),
+            value_type,
+            target.info.value_rank,
+        ) {
+            Ok(value) => value,
+            Err(e) => {
+                self.write_status = Some((format!("Invalid value: {e}"), false));
+                return;
+            }
+        };
+
+        let result = {
+            let client_guard = self.client.read().await;
+            client_guard.write_node_value(&target.node_id, value).await
+        };
+
+        match result {
+            Ok(status) if status.is_good() => {
+                self.write_status = Some((format!("Write succeeded: {status}"), true));
+                if let Err(e) = self.update_selected_attributes_async().await {
+                    log::error!("browse: failed to update attributes: {e}");
+                }
+            }
+            Ok(status) => {
+                self.write_status = Some((format!("Write failed: {status}"), false));
+            }
+            Err(e) => {
+                self.write_status = Some((format!("Write failed: {e}"), false));
+            }
+        }
+    }
+}
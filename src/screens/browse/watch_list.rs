@@ -0,0 +1,286 @@
+use super::types::{NodeType, WatchItem, WatchSettingsField};
+use crate::client::{ConnectionStatus, MonitoringSettings};
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyModifiers};
+use opcua::types::DataValue;
+use tokio::sync::mpsc;
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+
+// Publishing interval of the watch subscription in milliseconds
+pub(super) const WATCH_PUBLISHING_INTERVAL: f64 = 250.0;
+
+impl super::BrowseScreen {
+    /// Pin the selected Variable to the watch list, or unpin it if it is already watched
+    pub async fn toggle_watch_for_selected(&mut self) {
+        let Some(node) = self.tree_nodes.get(self.selected_node_index) else {
+            return;
+        };
+        if !matches!(node.node_type, NodeType::Variable) {
+            log::info!(
+                "watch: only Variables can be watched, '{}' is not",
+                node.name
+            );
+            return;
+        }
+        let Some(node_id) = node.opcua_node_id.clone() else {
+            log::warn!("watch: no real NodeId available for selected node");
+            return;
+        };
+        let name = node.name.clone();
+
+        if let Some(index) = self
+            .watch_items
+            .iter()
+            .position(|item| item.node_id == node_id)
+        {
+            self.remove_watch_item(index).await;
+            return;
+        }
+
+        if self.watch_message_rx.is_none() {
+            let (tx, rx) = mpsc::unbounded_channel();
+            let result = {
+                let mut client_guard = self.client.write().await;
+                client_guard
+                    .create_watch_subscription(WATCH_PUBLISHING_INTERVAL, tx)
+                    .await
+            };
+            match result {
+                Ok(_) => self.watch_message_rx = Some(rx),
+                Err(e) => {
+                    log::error!("watch: {e}");
+                    return;
+                }
+            }
+        }
+
+        let client_handle = self.next_watch_handle;
+        self.next_watch_handle += 1;
+        let settings = MonitoringSettings::default();
+
+        let result = {
+            let client_guard = self.client.read().await;
+            client_guard
+                .add_monitored_item(&node_id, client_handle, &settings)
+                .await
+        };
+
+        match result {
+            Ok(monitored_item_id) => {
+                log::info!("watch: added {node_id} as monitored item {monitored_item_id}");
+                self.watch_items.push(WatchItem {
+                    client_handle,
+                    monitored_item_id,
+                    node_id,
+                    name,
+                    settings,
+                    value: "(waiting)".to_string(),
+                    status: String::new(),
+                    source_timestamp: String::new(),
+                    is_value_good: false,
+                });
+            }
+            Err(e) => {
+                log::error!("watch: failed to watch {node_id}: {e}");
+            }
+        }
+    }
+
+    async fn remove_watch_item(&mut self, index: usize) {
+        if index >= self.watch_items.len() {
+            return;
+        }
+        let item = self.watch_items.remove(index);
+
+        let client_guard = self.client.read().await;
+        if let Err(e) = client_guard
+            .remove_monitored_item(item.monitored_item_id)
+            .await
+        {
+            log::warn!("watch: {e}");
+        }
+
+        if self.watch_items.is_empty() {
+            self.watch_focused = false;
+            self.watch_selected_index = 0;
+        } else {
+            self.watch_selected_index = self.watch_selected_index.min(self.watch_items.len() - 1);
+        }
+    }
+
+    /// Apply DataChange notifications received since the last tick
+    pub fn process_watch_notifications(&mut self) {
+        let Some(rx) = &mut self.watch_message_rx else {
+            return;
+        };
+
+        while let Ok(change) = rx.try_recv() {
+            if let Some(item) = self
+                .watch_items
+                .iter_mut()
+                .find(|item| item.client_handle == change.client_handle)
+            {
+                Self::apply_data_value(item, &change.value);
+            }
+        }
+    }
+
+    fn apply_data_value(item: &mut WatchItem, data_value: &DataValue) {
+        item.value = data_value
+            .value
+            .as_ref()
+            .map(|value| value.to_string())
+            .unwrap_or_else(|| "(null)".to_string());
+        item.status = data_value.status().to_string();
+        item.source_timestamp = data_value
+            .source_timestamp
+            .as_ref()
+            .map(|timestamp| timestamp.as_chrono().format("%H:%M:%S%.3f").to_string())
+            .unwrap_or_else(|| "None".to_string());
+        item.is_value_good = data_value.is_valid();
+    }
+
+    /// Key handling while the watch list panel has focus
+    pub async fn handle_watch_list_input(
+        &mut self,
+        key: KeyCode,
+    ) -> Result<Option<ConnectionStatus>> {
+        match key {
+            KeyCode::Tab => {
+                self.watch_focused = false;
+                self.events_focused = self.event_monitor.is_some();
+            }
+            KeyCode::Up => {
+                self.watch_selected_index = self.watch_selected_index.saturating_sub(1);
+            }
+            KeyCode::Down if self.watch_selected_index + 1 < self.watch_items.len() => {
+                self.watch_selected_index += 1;
+            }
+            KeyCode::Delete | KeyCode::Char('p') => {
+                self.remove_watch_item(self.watch_selected_index).await;
+            }
+            KeyCode::Enter => {
+                self.open_watch_settings();
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn open_watch_settings(&mut self) {
+        let Some(item) = self.watch_items.get(self.watch_selected_index) else {
+            return;
+        };
+        self.watch_sampling_input = Input::new(item.settings.sampling_interval.to_string());
+        self.watch_queue_input = Input::new(item.settings.queue_size.to_string());
+        self.watch_deadband_input = Input::new(item.settings.deadband.to_string());
+        self.watch_settings_focus = WatchSettingsField::SamplingInterval;
+        self.watch_settings_error = None;
+        self.watch_settings_open = true;
+    }
+
+    pub async fn handle_watch_settings_input(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Result<Option<ConnectionStatus>> {
+        match key {
+            KeyCode::Esc => {
+                self.watch_settings_open = false;
+            }
+            KeyCode::Tab | KeyCode::Down => {
+                self.watch_settings_focus = match self.watch_settings_focus {
+                    WatchSettingsField::SamplingInterval => WatchSettingsField::QueueSize,
+                    WatchSettingsField::QueueSize => WatchSettingsField::Deadband,
+                    WatchSettingsField::Deadband => WatchSettingsField::SamplingInterval,
+                };
+            }
+            KeyCode::BackTab | KeyCode::Up => {
+                self.watch_settings_focus = match self.watch_settings_focus {
+                    WatchSettingsField::SamplingInterval => WatchSettingsField::Deadband,
+                    WatchSettingsField::QueueSize => WatchSettingsField::SamplingInterval,
+                    WatchSettingsField::Deadband => WatchSettingsField::QueueSize,
+                };
+            }
+            KeyCode::Enter => {
+                self.apply_watch_settings().await;
+            }
+            _ => {
+                let input = match self.watch_settings_focus {
+                    WatchSettingsField::SamplingInterval => &mut self.watch_sampling_input,
+                    WatchSettingsField::QueueSize => &mut self.watch_queue_input,
+                    WatchSettingsField::Deadband => &mut self.watch_deadband_input,
+                };
+                input.handle_event(&crossterm::event::Event::Key(
+                    crossterm::event::KeyEvent::new(key, modifiers),
+                ));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn apply_watch_settings(&mut self) {
+        let settings = match self.parse_watch_settings() {
+            Ok(settings) => settings,
+            Err(message) => {
+                self.watch_settings_error = Some(message);
+                return;
+            }
+        };
+        let Some(item) = self.watch_items.get(self.watch_selected_index) else {
+            self.watch_settings_open = false;
+            return;
+        };
+
+        let result = {
+            let client_guard = self.client.read().await;
+            client_guard
+                .modify_monitored_item(item.monitored_item_id, item.client_handle, &settings)
+                .await
+        };
+
+        match result {
+            Ok(()) => {
+                self.watch_items[self.watch_selected_index].settings = settings;
+                self.watch_settings_open = false;
+            }
+            Err(e) => {
+                self.watch_settings_error = Some(e.to_string());
+            }
+        }
+    }
+
+    fn parse_watch_settings(&self) -> std::result::Result<MonitoringSettings, String> {
+        let sampling_interval = self
+            .watch_sampling_input
+            .value()
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|value| *value >= 0.0)
+            .ok_or("Sampling interval must be a number of milliseconds")?;
+        let queue_size = self
+            .watch_queue_input
+            .value()
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|value| *value > 0)
+            .ok_or("Queue size must be a positive integer")?;
+        let deadband = self
+            .watch_deadband_input
+            .value()
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|value| *value >= 0.0)
+            .ok_or("Deadband must be zero or a positive number")?;
+
+        Ok(MonitoringSettings {
+            sampling_interval,
+            queue_size,
+            deadband,
+        })
+    }
+}
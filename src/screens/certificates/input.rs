@@ -0,0 +1,112 @@
+use crate::cert_utils::{CertUtils, CertificateLocation, PKI_DIR};
+use crossterm::event::KeyCode;
+use std::path::Path;
+
+impl super::CertificateScreen {
+    /// Handle a key press; returns true when the screen should close
+    pub fn handle_input(&mut self, key: KeyCode) -> bool {
+        if self.confirm_delete {
+            self.confirm_delete = false;
+            if matches!(key, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                self.delete_selected();
+            } else {
+                self.status_message = None;
+            }
+            return false;
+        }
+
+        let last_index = self.certificates.len().saturating_sub(1);
+        match key {
+            KeyCode::Esc | KeyCode::F(2) => return true,
+            KeyCode::Up => self.selected_index = self.selected_index.saturating_sub(1),
+            KeyCode::Down => self.selected_index = (self.selected_index + 1).min(last_index),
+            KeyCode::Home => self.selected_index = 0,
+            KeyCode::End => self.selected_index = last_index,
+            KeyCode::Char('t') => self.move_selected(CertificateLocation::Trusted),
+            KeyCode::Char('r') => self.move_selected(CertificateLocation::Rejected),
+            KeyCode::Delete | KeyCode::Char('d') => {
+                if let Some(certificate) = self.selected_certificate() {
+                    let warning = if certificate.location == CertificateLocation::Own {
+                        " This is the client's own certificate."
+                    } else {
+                        ""
+                    };
+                    self.status_message = Some((
+                        format!(
+                            "Delete {}?{warning} Press y to confirm",
+                            certificate.path.display()
+                        ),
+                        false,
+                    ));
+                    self.confirm_delete = true;
+                }
+            }
+            KeyCode::F(5) => {
+                self.refresh();
+                self.status_message = None;
+            }
+            _ => {}
+        }
+        false
+    }
+
+    /// Move the selected server certificate between the trusted and rejected directories
+    fn move_selected(&mut self, location: CertificateLocation) {
+        let Some(certificate) = self.selected_certificate().cloned() else {
+            return;
+        };
+        if certificate.location == CertificateLocation::Own {
+            self.status_message = Some((
+                "The client's own certificate cannot be trusted or rejected".to_string(),
+                false,
+            ));
+            return;
+        }
+        if certificate.location == location {
+            self.status_message = Some((
+                format!("Certificate is already {}", location.label().to_lowercase()),
+                false,
+            ));
+            return;
+        }
+
+        match CertUtils::move_certificate(Path::new(PKI_DIR), &certificate, location) {
+            Ok(path) => {
+                log::info!(
+                    "certificates: moved {} to {}",
+                    certificate.path.display(),
+                    path.display()
+                );
+                self.refresh();
+                if let Some(index) = self.certificates.iter().position(|c| c.path == path) {
+                    self.selected_index = index;
+                }
+                self.status_message = Some((
+                    format!("Certificate {}", location.label().to_lowercase()),
+                    true,
+                ));
+            }
+            Err(e) => {
+                log::error!("certificates: {e}");
+                self.status_message = Some((e.to_string(), false));
+            }
+        }
+    }
+
+    fn delete_selected(&mut self) {
+        let Some(certificate) = self.selected_certificate().cloned() else {
+            return;
+        };
+        match CertUtils::delete_certificate(&certificate) {
+            Ok(()) => {
+                log::info!("certificates: deleted {}", certificate.path.display());
+                self.refresh();
+                self.status_message = Some(("Certificate deleted".to_string(), true));
+            }
+            Err(e) => {
+                log::error!("certificates: {e}");
+                self.status_message = Some((e.to_string(), false));
+            }
+        }
+    }
+}
@@ -0,0 +1,38 @@
+mod input;
+mod render;
+
+use crate::cert_utils::{CertUtils, CertificateInfo, PKI_DIR};
+use std::path::Path;
+
+/// Lists the own, trusted and rejected certificates of the `pki` directory
+pub struct CertificateScreen {
+    pub certificates: Vec<CertificateInfo>,
+    pub selected_index: usize,
+    pub confirm_delete: bool, // Waiting for "y" to delete the selected certificate
+    pub status_message: Option<(String, bool)>, // Message and whether it is a success
+}
+
+impl CertificateScreen {
+    pub fn new() -> Self {
+        let mut screen = Self {
+            certificates: Vec::new(),
+            selected_index: 0,
+            confirm_delete: false,
+            status_message: None,
+        };
+        screen.refresh();
+        screen
+    }
+
+    /// Re-read the certificate store, keeping the selection in range
+    pub fn refresh(&mut self) {
+        self.certificates = CertUtils::list_certificates(Path::new(PKI_DIR));
+        self.selected_index = self
+            .selected_index
+            .min(self.certificates.len().saturating_sub(1));
+    }
+
+    pub fn selected_certificate(&self) -> Option<&CertificateInfo> {
+        self.certificates.get(self.selected_index)
+    }
+}
@@ -0,0 +1,170 @@
+use crate::cert_utils::{CertificateInfo, CertificateLocation, PKI_DIR};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+impl super::CertificateScreen {
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        // Full-screen overlay over the connect or browse screen
+        f.render_widget(Clear, area);
+        let block = Block::default()
+            .title(format!(" Certificates ({PKI_DIR}) "))
+            .title_style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::White))
+            .style(Style::default().bg(Color::Black).fg(Color::White));
+        f.render_widget(block, area);
+
+        let inner_area = Rect::new(
+            area.x + 1,
+            area.y + 1,
+            area.width.saturating_sub(2),
+            area.height.saturating_sub(2),
+        );
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),    // Certificate table
+                Constraint::Length(9), // Details of the selected certificate
+                Constraint::Length(1), // Status
+                Constraint::Length(1), // Instructions
+            ])
+            .split(inner_area);
+
+        let rows: Vec<Row> = self
+            .certificates
+            .iter()
+            .map(|certificate| {
+                let color = match certificate.location {
+                    CertificateLocation::Own => Color::Cyan,
+                    CertificateLocation::Trusted => Color::LightGreen,
+                    CertificateLocation::Rejected => Color::LightRed,
+                };
+                let valid_until = if certificate.expired {
+                    format!("{} (expired)", certificate.not_after)
+                } else {
+                    certificate.not_after.clone()
+                };
+                let subject = certificate
+                    .error
+                    .clone()
+                    .unwrap_or_else(|| certificate.subject.clone());
+                Row::new(vec![
+                    Cell::from(certificate.location.label()).style(Style::default().fg(color)),
+                    Cell::from(subject),
+                    Cell::from(valid_until).style(if certificate.expired {
+                        Style::default().fg(Color::LightRed)
+                    } else {
+                        Style::default()
+                    }),
+                    Cell::from(certificate.thumbprint.as_str()),
+                ])
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(9),
+                Constraint::Percentage(40),
+                Constraint::Length(34),
+                Constraint::Min(0),
+            ],
+        )
+        .header(
+            Row::new(vec!["Location", "Subject", "Valid until", "Thumbprint"]).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .block(
+            Block::default()
+                .title(format!("Certificates ({})", self.certificates.len()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title_style(Style::default().fg(Color::Yellow)),
+        )
+        .row_highlight_style(
+            Style::default()
+                .bg(Color::Blue)
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )
+        .column_spacing(1);
+        let mut table_state = TableState::default()
+            .with_selected((!self.certificates.is_empty()).then_some(self.selected_index));
+        f.render_stateful_widget(table, chunks[0], &mut table_state);
+
+        Self::render_details(f, chunks[1], self.selected_certificate());
+
+        if let Some((message, is_good)) = &self.status_message {
+            let color = if self.confirm_delete {
+                Color::Yellow
+            } else if *is_good {
+                Color::LightGreen
+            } else {
+                Color::LightRed
+            };
+            f.render_widget(
+                Paragraph::new(message.as_str()).style(Style::default().fg(color)),
+                chunks[2],
+            );
+        }
+
+        let instructions = Paragraph::new(
+            "↑/↓ select | t trust | r reject | Del/d delete | F5 refresh | Esc/F2 close",
+        )
+        .style(Style::default().fg(Color::Yellow).bg(Color::Black))
+        .alignment(ratatui::layout::Alignment::Center);
+        f.render_widget(instructions, chunks[3]);
+    }
+
+    fn render_details(f: &mut Frame, area: Rect, certificate: Option<&CertificateInfo>) {
+        let label_style = Style::default().fg(Color::Yellow);
+        let field = |label: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{label:<16}"), label_style),
+                Span::raw(value),
+            ])
+        };
+
+        let lines = match certificate {
+            Some(certificate) => vec![
+                field("File", certificate.path.display().to_string()),
+                field("Subject", certificate.subject.clone()),
+                field("Issuer", certificate.issuer.clone()),
+                field(
+                    "Valid",
+                    format!("{} to {}", certificate.not_before, certificate.not_after),
+                ),
+                field("Thumbprint", certificate.thumbprint.clone()),
+                field(
+                    "Application URI",
+                    certificate.application_uri.clone().unwrap_or_default(),
+                ),
+                field("Error", certificate.error.clone().unwrap_or_default()),
+            ],
+            None => vec![Line::from(format!(
+                "No certificates in {PKI_DIR}/own, {PKI_DIR}/trusted or {PKI_DIR}/rejected"
+            ))],
+        };
+
+        let details = Paragraph::new(lines).block(
+            Block::default()
+                .title("Details")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::White))
+                .title_style(Style::default().fg(Color::Yellow)),
+        );
+        f.render_widget(details, area);
+    }
+}
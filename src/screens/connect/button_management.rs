@@ -57,8 +57,12 @@ impl ConnectScreen {
         };
 
         match self.step {
-            ConnectDialogStep::ServerUrl => vec![cancel_btn, next_btn],
-            ConnectDialogStep::EndpointSelection => vec![cancel_btn, back_btn, next_btn],
+            ConnectDialogStep::ProfileSelection | ConnectDialogStep::ServerUrl => {
+                vec![cancel_btn, next_btn]
+            }
+            ConnectDialogStep::ServerSelection | ConnectDialogStep::EndpointSelection => {
+                vec![cancel_btn, back_btn, next_btn]
+            }
             ConnectDialogStep::SecurityConfiguration => vec![cancel_btn, back_btn, next_btn],
             ConnectDialogStep::Authentication => vec![cancel_btn, back_btn, connect_btn],
         }
@@ -78,7 +82,9 @@ impl ConnectScreen {
         match button_id {
             "cancel" => Ok(Some(ConnectionStatus::Disconnected)),
             "next" => match self.step {
-                ConnectDialogStep::ServerUrl
+                ConnectDialogStep::ProfileSelection
+                | ConnectDialogStep::ServerUrl
+                | ConnectDialogStep::ServerSelection
                 | ConnectDialogStep::EndpointSelection
                 | ConnectDialogStep::SecurityConfiguration => {
                     self.advance_to_next_step()?;
@@ -97,11 +103,8 @@ impl ConnectScreen {
 
     fn handle_back_navigation(&mut self) {
         match self.step {
-            ConnectDialogStep::EndpointSelection => {
-                self.step = ConnectDialogStep::ServerUrl;
-                self.input_mode = InputMode::Editing;
-                self.setup_buttons_for_current_step();
-            }
+            ConnectDialogStep::ServerSelection => self.back_to_server_url(),
+            ConnectDialogStep::EndpointSelection => self.navigate_back_from_endpoints(),
             ConnectDialogStep::SecurityConfiguration => {
                 self.step = ConnectDialogStep::EndpointSelection;
                 self.input_mode = InputMode::Normal;
@@ -1,6 +1,7 @@
 use super::types::*;
 use super::validator::AuthInputs;
 use crate::client::ConnectionStatus;
+use crate::connection_manager::{ConnectionTarget, UntrustedServerCertificate};
 use anyhow::{anyhow, Result};
 use log::{error, info, warn};
 use opcua::client::prelude::*;
@@ -13,6 +14,7 @@ pub struct SecurityConfig {
     pub auto_trust: bool,
     pub client_cert_path: String,
     pub client_key_path: String,
+    pub trust_once_thumbprint: Option<String>,
 }
 
 pub struct ConnectionBuilder {
@@ -56,7 +58,8 @@ impl ConnectionBuilder {
         self.security_config = Some(config);
         self
     }
-    pub async fn connect(self) -> Result<(Client, Arc<RwLock<Session>>)> {
+    /// Connect and return the connection target, which is kept to re-create the session later
+    pub async fn connect(self) -> Result<(Client, Arc<RwLock<Session>>, ConnectionTarget)> {
         use crate::connection_manager::{ConnectionConfig, ConnectionManager};
 
         let identity_token = self
@@ -64,6 +67,11 @@ impl ConnectionBuilder {
             .clone()
             .ok_or_else(|| anyhow!("Identity token not set"))?;
 
+        let trust_once_thumbprint = self
+            .security_config
+            .as_ref()
+            .and_then(|c| c.trust_once_thumbprint.clone());
+
         // Parse security policy from endpoint
         let security_policy = Self::parse_security_policy(&self.endpoint.security_policy_uri);
 
@@ -131,6 +139,7 @@ impl ConnectionBuilder {
                     }),
                 )
                 .with_authentication(identity_token)
+                .with_trust_once(trust_once_thumbprint)
         } else {
             log::info!("Using regular UI connection configuration");
             // Use regular UI connection configuration for non-secure connections
@@ -158,6 +167,7 @@ impl ConnectionBuilder {
                     }),
                 )
                 .with_authentication(identity_token)
+                .with_trust_once(trust_once_thumbprint)
         };
 
         log::info!(
@@ -166,7 +176,14 @@ impl ConnectionBuilder {
             config.security_mode,
             config.application_uri
         );
-        ConnectionManager::connect_to_endpoint(self.endpoint, &config).await
+        let (client, session) =
+            ConnectionManager::connect_to_endpoint(self.endpoint.clone(), &config).await?;
+        let target = ConnectionTarget {
+            endpoint: self.endpoint,
+            config,
+            reverse: None,
+        };
+        Ok((client, session, target))
     }
 
     fn parse_security_policy(uri: &opcua::types::UAString) -> opcua::crypto::SecurityPolicy {
@@ -315,6 +332,11 @@ impl ConnectScreen {
         {
             Ok(result) => result,
             Err(e) => {
+                // Ask the user about an untrusted server certificate instead of failing
+                if let Some(untrusted) = e.downcast_ref::<UntrustedServerCertificate>() {
+                    self.server_certificate_prompt = Some(untrusted.clone());
+                    return Ok(None);
+                }
                 error!("Connection failed: {e}");
                 return Ok(Some(ConnectionStatus::Error(format!(
                     "Connection failed: {e}"
@@ -322,10 +344,16 @@ impl ConnectScreen {
             }
         };
 
-        let (client, session) = connection_result;
+        let (client, session, mut target) = connection_result;
+        // The relay was only needed for this attempt; reconnects start their own
+        target.reverse = self
+            .reverse_relay
+            .take()
+            .map(|relay| relay.target().clone());
 
         self.client = Some(client);
         self.session = Some(session);
+        self.connection_target = Some(target);
 
         info!("OPC UA connection established successfully");
         Ok(Some(ConnectionStatus::Connecting))
@@ -345,6 +373,7 @@ impl ConnectScreen {
         }
 
         self.client = None;
+        self.connection_target = None;
         info!("Disconnected from OPC UA server");
         Ok(())
     }
@@ -359,6 +388,11 @@ impl ConnectScreen {
         self.session.take()
     }
 
+    /// Take the endpoint and settings used for the connection (moving them out)
+    pub fn take_connection_target(&mut self) -> Option<ConnectionTarget> {
+        self.connection_target.take()
+    }
+
     fn get_auth_description(&self) -> String {
         match self.authentication_type {
             AuthenticationType::Anonymous => "Anonymous".to_string(),
@@ -377,6 +411,7 @@ impl ConnectScreen {
             auto_trust: self.auto_trust_server_cert,
             client_cert_path: self.client_certificate_input.value().trim().to_string(),
             client_key_path: self.client_private_key_input.value().trim().to_string(),
+            trust_once_thumbprint: self.trust_once_thumbprint.clone(),
         }
     }
 
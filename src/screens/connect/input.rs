@@ -11,12 +11,30 @@ impl ConnectScreen {
         key: KeyCode,
         modifiers: KeyModifiers,
     ) -> Result<Option<ConnectionStatus>> {
+        // The save profile prompt captures all keys while open
+        if self.profile_name_input.is_some() {
+            self.handle_save_profile_input(key, modifiers);
+            return Ok(None);
+        }
+
+        // The server certificate prompt waits for a trust decision
+        if self.server_certificate_prompt.is_some() {
+            self.handle_server_trust_input(key);
+            return Ok(None);
+        }
+
         // Handle button input first
         if let Some(button_id) = self.button_manager.handle_key_input(key, modifiers) {
             return self.handle_button_action(&button_id).await;
         }
         match self.step {
+            ConnectDialogStep::ProfileSelection => {
+                self.handle_profile_selection_input(key, modifiers).await
+            }
             ConnectDialogStep::ServerUrl => self.handle_server_url_input(key, modifiers).await,
+            ConnectDialogStep::ServerSelection => {
+                self.handle_server_selection_input(key, modifiers).await
+            }
             ConnectDialogStep::EndpointSelection => {
                 self.handle_endpoint_selection_input(key, modifiers).await
             }
@@ -39,6 +57,20 @@ impl ConnectScreen {
                 self.advance_to_next_step()?;
                 Ok(None)
             }
+            KeyCode::Char('l') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.start_server_discovery();
+                Ok(None)
+            }
+            KeyCode::Char('r') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.show_reverse_servers();
+                Ok(None)
+            }
+            KeyCode::Esc if !self.profiles.is_empty() => {
+                // Go back to the profile picker
+                self.step = ConnectDialogStep::ProfileSelection;
+                self.setup_buttons_for_current_step();
+                Ok(None)
+            }
             KeyCode::Esc => Ok(Some(ConnectionStatus::Disconnected)),
             KeyCode::PageUp => {
                 // Scroll connection log up
@@ -120,9 +152,8 @@ impl ConnectScreen {
                 Ok(None)
             }
             KeyCode::Esc => {
-                // Go back to URL step
-                self.step = ConnectDialogStep::ServerUrl;
-                self.setup_buttons_for_current_step();
+                // Go back to the URL step or the server list
+                self.navigate_back_from_endpoints();
                 Ok(None)
             }
             KeyCode::PageUp => {
@@ -188,6 +219,10 @@ impl ConnectScreen {
                 self.navigate_back_from_auth();
                 Ok(None)
             }
+            KeyCode::Char('s') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.open_save_profile_prompt();
+                Ok(None)
+            }
             KeyCode::Char(_) | KeyCode::Backspace | KeyCode::Left | KeyCode::Right => {
                 self.handle_auth_field_input(key, modifiers);
                 Ok(None)
@@ -250,6 +285,10 @@ impl ConnectScreen {
                 self.setup_buttons_for_current_step();
                 Ok(None)
             }
+            KeyCode::Char('g') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.generate_client_certificate();
+                Ok(None)
+            }
             KeyCode::Char(' ') => {
                 // Handle space key
                 if self.active_security_field == SecurityField::AutoTrustCheckbox {
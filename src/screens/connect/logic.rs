@@ -1,16 +1,24 @@
 use super::types::*;
+use crate::cert_utils::{CertUtils, CertificateSettings, OWN_CERT_FILE, OWN_KEY_FILE, PKI_DIR};
 use crate::client::ConnectionStatus;
 use anyhow::Result;
+use std::path::Path;
 use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
 
 impl ConnectScreen {
     pub async fn handle_pending_operations(&mut self) -> Result<Option<ConnectionStatus>> {
+        if self.listing_reverse_servers && self.step == ConnectDialogStep::ServerSelection {
+            self.refresh_reverse_servers();
+        }
+
         if self.pending_discovery {
             self.pending_discovery = false;
 
             match self.discover_endpoints().await {
                 Ok(()) => {
                     self.connect_in_progress = false;
+                    self.select_profile_endpoint();
                     self.step = ConnectDialogStep::EndpointSelection;
                     self.setup_buttons_for_current_step();
                     self.input_mode = InputMode::Normal;
@@ -23,6 +31,19 @@ impl ConnectScreen {
             }
         }
 
+        if self.pending_server_discovery {
+            self.pending_server_discovery = false;
+            let found = self.find_servers().await.is_ok();
+            self.connect_in_progress = false;
+            if found {
+                self.step = ConnectDialogStep::ServerSelection;
+                self.input_mode = InputMode::Normal;
+            } else {
+                self.input_mode = InputMode::Editing;
+            }
+            self.setup_buttons_for_current_step();
+        }
+
         if self.pending_connection {
             self.pending_connection = false;
             let connection_result = self.perform_connection().await?;
@@ -103,6 +124,37 @@ impl ConnectScreen {
         }
     }
 
+    /// Fill in the client certificate and key from the pki directory, generating a self-signed
+    /// pair for this host first when there is none yet
+    pub fn generate_client_certificate(&mut self) {
+        let pki_dir = Path::new(PKI_DIR);
+        let cert_path = pki_dir.join(OWN_CERT_FILE);
+        let key_path = pki_dir.join(OWN_KEY_FILE);
+        if cert_path.exists() && key_path.exists() {
+            log::info!(
+                "Using existing client certificate {}; run gen-cert --force to replace it",
+                cert_path.display()
+            );
+        } else {
+            let settings = CertificateSettings::for_local_host();
+            match CertUtils::generate_self_signed(pki_dir, &settings, true) {
+                Ok(_) => log::info!(
+                    "Generated client certificate {} for {}",
+                    cert_path.display(),
+                    settings.application_uri
+                ),
+                Err(e) => {
+                    log::error!("Failed to generate client certificate: {e}");
+                    return;
+                }
+            }
+        }
+
+        self.client_certificate_input =
+            Input::default().with_value(cert_path.display().to_string());
+        self.client_private_key_input = Input::default().with_value(key_path.display().to_string());
+    }
+
     pub fn get_selected_endpoint(&self) -> Option<&EndpointInfo> {
         if self.discovered_endpoints.is_empty() {
             return None;
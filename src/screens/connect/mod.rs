@@ -10,11 +10,17 @@ mod input;
 mod logic;
 mod mouse;
 mod navigation;
+mod profiles;
 mod render;
 mod render_auth;
 mod render_endpoint;
+mod render_profiles;
 mod render_security;
+mod render_server_trust;
 mod render_server_url;
+mod render_servers;
+mod server_trust;
+mod servers;
 mod state;
 pub mod types;
 mod validator;
@@ -3,17 +3,33 @@ use ratatui::layout::{Constraint, Direction, Layout, Rect};
 
 impl ConnectScreen {
     pub fn handle_mouse_down(&mut self, column: u16, row: u16) -> bool {
+        // The server certificate prompt is answered with the keyboard only
+        if self.server_certificate_prompt.is_some() {
+            return false;
+        }
         self.button_manager.handle_mouse_down(column, row)
     }
 
     pub fn handle_mouse_up(&mut self, column: u16, row: u16) -> Option<String> {
+        if self.server_certificate_prompt.is_some() {
+            return None;
+        }
         self.button_manager.handle_mouse_up(column, row)
     }
 
     /// Handle mouse click events for the current connect step
     pub fn handle_mouse_click(&mut self, column: u16, row: u16, area: Rect) -> bool {
+        if self.server_certificate_prompt.is_some() {
+            return false;
+        }
         match self.step {
+            ConnectDialogStep::ProfileSelection => {
+                self.handle_mouse_click_profiles(column, row, area)
+            }
             ConnectDialogStep::ServerUrl => self.handle_mouse_click_server_url(column, row, area),
+            ConnectDialogStep::ServerSelection => {
+                self.handle_mouse_click_servers(column, row, area)
+            }
             ConnectDialogStep::EndpointSelection => {
                 self.handle_mouse_click_endpoint(column, row, area)
             }
@@ -25,6 +41,34 @@ impl ConnectScreen {
             }
         }
     }
+    /// Handle mouse clicks in the profile picker
+    fn handle_mouse_click_profiles(&mut self, column: u16, row: u16, area: Rect) -> bool {
+        let chunks = self.create_step_layout(area);
+        if !self.is_point_in_rect(column, row, chunks[1]) {
+            return false;
+        }
+        let clicked_index = row.saturating_sub(chunks[1].y + 1) as usize;
+        if clicked_index <= self.profiles.len() {
+            self.selected_profile_index = clicked_index;
+            return true;
+        }
+        false
+    }
+
+    /// Handle mouse clicks in the server list of a discovery server
+    fn handle_mouse_click_servers(&mut self, column: u16, row: u16, area: Rect) -> bool {
+        let chunks = self.create_step_layout(area);
+        if !self.is_point_in_rect(column, row, chunks[1]) {
+            return false;
+        }
+        let clicked_index = row.saturating_sub(chunks[1].y + 1) as usize;
+        if clicked_index < self.discovered_servers.len() {
+            self.selected_server_index = clicked_index;
+            return true;
+        }
+        false
+    }
+
     /// Handle mouse clicks in the server URL step
     fn handle_mouse_click_server_url(&mut self, column: u16, row: u16, area: Rect) -> bool {
         let chunks = self.create_server_url_layout(area);
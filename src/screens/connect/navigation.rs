@@ -10,7 +10,10 @@ impl ConnectStateMachine {
         needs_security: bool,
     ) -> Option<ConnectDialogStep> {
         match current {
-            ConnectDialogStep::ServerUrl => Some(ConnectDialogStep::EndpointSelection),
+            ConnectDialogStep::ProfileSelection => Some(ConnectDialogStep::ServerUrl),
+            ConnectDialogStep::ServerUrl | ConnectDialogStep::ServerSelection => {
+                Some(ConnectDialogStep::EndpointSelection)
+            }
             ConnectDialogStep::EndpointSelection => {
                 if needs_security {
                     Some(ConnectDialogStep::SecurityConfiguration)
@@ -28,7 +31,9 @@ impl ConnectStateMachine {
         needs_security: bool,
     ) -> Option<ConnectDialogStep> {
         match current {
+            ConnectDialogStep::ProfileSelection => None,
             ConnectDialogStep::ServerUrl => None,
+            ConnectDialogStep::ServerSelection => Some(ConnectDialogStep::ServerUrl),
             ConnectDialogStep::EndpointSelection => Some(ConnectDialogStep::ServerUrl),
             ConnectDialogStep::SecurityConfiguration => Some(ConnectDialogStep::EndpointSelection),
             ConnectDialogStep::Authentication => {
@@ -45,9 +50,16 @@ impl ConnectStateMachine {
 impl ConnectScreen {
     pub fn advance_to_next_step(&mut self) -> Result<()> {
         match self.step {
+            ConnectDialogStep::ProfileSelection => {
+                self.advance_from_profile_selection();
+                Ok(())
+            }
             ConnectDialogStep::ServerUrl => {
                 self.validate_server_url();
                 if self.server_url_validation_error.is_none() {
+                    // A typed URL is a server of its own, not one picked from a discovery server
+                    self.discovered_servers.clear();
+                    self.listing_reverse_servers = false;
                     self.connect_in_progress = true;
                     self.pending_discovery = true;
                     self.input_mode = InputMode::Normal;
@@ -56,6 +68,10 @@ impl ConnectScreen {
                 }
                 Ok(())
             }
+            ConnectDialogStep::ServerSelection => {
+                self.select_server();
+                Ok(())
+            }
             ConnectDialogStep::EndpointSelection => {
                 if let Some(next_step) = ConnectStateMachine::next_step(
                     self.step.clone(),
@@ -98,6 +114,18 @@ impl ConnectScreen {
         }
     }
 
+    /// Endpoint selection goes back to the server list when the server was picked from one
+    pub fn navigate_back_from_endpoints(&mut self) {
+        if self.discovered_servers.is_empty() && !self.listing_reverse_servers {
+            self.step = ConnectDialogStep::ServerUrl;
+            self.input_mode = InputMode::Editing;
+        } else {
+            self.step = ConnectDialogStep::ServerSelection;
+            self.input_mode = InputMode::Normal;
+        }
+        self.setup_buttons_for_current_step();
+    }
+
     pub fn navigate_back_from_auth(&mut self) {
         if let Some(prev_step) = ConnectStateMachine::previous_step(
             self.step.clone(),
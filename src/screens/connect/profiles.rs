@@ -0,0 +1,250 @@
+use super::types::*;
+use crate::client::ConnectionStatus;
+use crate::config::{ConnectionProfile, OpcUaConfig};
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyModifiers};
+use log::{error, info, warn};
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+use tui_logger::TuiWidgetEvent;
+
+impl ConnectScreen {
+    /// Reload the connection profiles from config.json
+    pub fn load_profiles(&mut self) {
+        match OpcUaConfig::load() {
+            Ok(config) => {
+                info!(
+                    "Loaded {} connection profile(s) from {}",
+                    config.profiles.len(),
+                    crate::config::CONFIG_FILE
+                );
+                self.profiles = config.profiles;
+            }
+            Err(e) => {
+                warn!("{e}; starting without connection profiles");
+                self.profiles.clear();
+            }
+        }
+        self.selected_profile_index = self.selected_profile_index.min(self.profiles.len());
+    }
+
+    /// The profile picker is only shown when there is something to pick
+    pub fn initial_step(&self) -> ConnectDialogStep {
+        if self.profiles.is_empty() {
+            ConnectDialogStep::ServerUrl
+        } else {
+            ConnectDialogStep::ProfileSelection
+        }
+    }
+
+    pub(super) async fn handle_profile_selection_input(
+        &mut self,
+        key: KeyCode,
+        _modifiers: KeyModifiers,
+    ) -> Result<Option<ConnectionStatus>> {
+        // Row 0 is "New connection"
+        let last_index = self.profiles.len();
+        match key {
+            KeyCode::Up => {
+                self.selected_profile_index = if self.selected_profile_index == 0 {
+                    last_index
+                } else {
+                    self.selected_profile_index - 1
+                };
+            }
+            KeyCode::Down => {
+                self.selected_profile_index = if self.selected_profile_index >= last_index {
+                    0
+                } else {
+                    self.selected_profile_index + 1
+                };
+            }
+            KeyCode::Enter => self.advance_from_profile_selection(),
+            KeyCode::Delete => self.delete_selected_profile(),
+            KeyCode::Esc => return Ok(Some(ConnectionStatus::Disconnected)),
+            KeyCode::PageUp => {
+                self.logger_widget_state
+                    .transition(TuiWidgetEvent::PrevPageKey);
+            }
+            KeyCode::PageDown => {
+                self.logger_widget_state
+                    .transition(TuiWidgetEvent::NextPageKey);
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    /// Start a new connection, or fill the form from the selected profile and discover its endpoints
+    pub fn advance_from_profile_selection(&mut self) {
+        let Some(profile) = self
+            .selected_profile_index
+            .checked_sub(1)
+            .and_then(|index| self.profiles.get(index))
+            .cloned()
+        else {
+            self.active_profile = None;
+            self.step = ConnectDialogStep::ServerUrl;
+            self.input_mode = InputMode::Editing;
+            self.setup_buttons_for_current_step();
+            return;
+        };
+
+        info!("Using connection profile '{}'", profile.name);
+        self.apply_profile(&profile);
+        self.active_profile = Some(profile);
+        self.step = ConnectDialogStep::ServerUrl;
+        self.setup_buttons_for_current_step();
+        self.advance_to_next_step()
+            .unwrap_or_else(|e| error!("Profile: {e}"));
+    }
+
+    fn apply_profile(&mut self, profile: &ConnectionProfile) {
+        let text =
+            |value: &Option<String>| Input::default().with_value(value.clone().unwrap_or_default());
+
+        self.server_url_input = Input::default().with_value(profile.server_url.clone());
+        self.validate_server_url();
+        self.use_original_url = profile.use_original_url;
+
+        self.client_certificate_input = text(&profile.client_certificate);
+        self.client_private_key_input = text(&profile.client_private_key);
+        self.auto_trust_server_cert = profile.auto_trust;
+        self.trusted_server_store_input = text(&profile.trusted_store);
+
+        self.authentication_type = match profile.auth_type.as_str() {
+            "UserPassword" => AuthenticationType::UserPassword,
+            "X509Certificate" => AuthenticationType::X509Certificate,
+            _ => AuthenticationType::Anonymous,
+        };
+        self.username_input = text(&profile.username);
+        self.password_input.reset();
+        self.user_certificate_input = text(&profile.user_certificate);
+        self.user_private_key_input = text(&profile.user_private_key);
+    }
+
+    /// After discovery, select the endpoint stored in the active profile if the server still offers it
+    pub fn select_profile_endpoint(&mut self) {
+        let Some(profile) = &self.active_profile else {
+            return;
+        };
+        match self.discovered_endpoints.iter().position(|endpoint| {
+            format!("{:?}", endpoint.security_policy) == profile.security_policy
+                && format!("{:?}", endpoint.security_mode) == profile.security_mode
+        }) {
+            Some(index) => self.selected_endpoint_index = index,
+            None => warn!(
+                "Profile endpoint {} - {} is not offered by the server",
+                profile.security_policy, profile.security_mode
+            ),
+        }
+    }
+
+    /// Current form settings as a profile; the password is deliberately left out
+    fn profile_from_settings(&self, name: &str) -> ConnectionProfile {
+        let text = |input: &Input| {
+            let value = input.value().trim();
+            (!value.is_empty()).then(|| value.to_string())
+        };
+        let (security_policy, security_mode) = match self.get_selected_endpoint() {
+            Some(endpoint) => (
+                format!("{:?}", endpoint.security_policy),
+                format!("{:?}", endpoint.security_mode),
+            ),
+            None => ("None".to_string(), "None".to_string()),
+        };
+
+        ConnectionProfile {
+            name: name.to_string(),
+            server_url: self.get_server_url(),
+            use_original_url: self.use_original_url,
+            security_policy,
+            security_mode,
+            client_certificate: text(&self.client_certificate_input),
+            client_private_key: text(&self.client_private_key_input),
+            auto_trust: self.auto_trust_server_cert,
+            trusted_store: text(&self.trusted_server_store_input),
+            auth_type: format!("{:?}", self.authentication_type),
+            username: text(&self.username_input)
+                .filter(|_| self.authentication_type == AuthenticationType::UserPassword),
+            user_certificate: text(&self.user_certificate_input)
+                .filter(|_| self.authentication_type == AuthenticationType::X509Certificate),
+            user_private_key: text(&self.user_private_key_input)
+                .filter(|_| self.authentication_type == AuthenticationType::X509Certificate),
+        }
+    }
+
+    /// Open the "save profile" prompt, suggesting the name of the profile in use
+    pub fn open_save_profile_prompt(&mut self) {
+        let name = self
+            .active_profile
+            .as_ref()
+            .map(|profile| profile.name.clone())
+            .unwrap_or_default();
+        self.profile_name_input = Some(Input::default().with_value(name));
+    }
+
+    pub(super) fn handle_save_profile_input(&mut self, key: KeyCode, modifiers: KeyModifiers) {
+        let Some(input) = &mut self.profile_name_input else {
+            return;
+        };
+        match key {
+            KeyCode::Esc => self.profile_name_input = None,
+            KeyCode::Enter => {
+                let name = input.value().trim().to_string();
+                if name.is_empty() {
+                    error!("Profile: a name is required");
+                    return;
+                }
+                self.profile_name_input = None;
+                self.save_profile(&name);
+            }
+            _ => {
+                input.handle_event(&crossterm::event::Event::Key(
+                    crossterm::event::KeyEvent::new(key, modifiers),
+                ));
+            }
+        }
+    }
+
+    fn save_profile(&mut self, name: &str) {
+        let profile = self.profile_from_settings(name);
+        let result = OpcUaConfig::load().and_then(|mut config| {
+            config.upsert_profile(profile.clone());
+            config.save()?;
+            Ok(config.profiles)
+        });
+        match result {
+            Ok(profiles) => {
+                info!("Saved connection profile '{name}'");
+                self.profiles = profiles;
+                self.active_profile = Some(profile);
+            }
+            Err(e) => error!("Failed to save profile '{name}': {e}"),
+        }
+    }
+
+    fn delete_selected_profile(&mut self) {
+        let Some(name) = self
+            .selected_profile_index
+            .checked_sub(1)
+            .and_then(|index| self.profiles.get(index))
+            .map(|profile| profile.name.clone())
+        else {
+            return;
+        };
+        let result = OpcUaConfig::load().and_then(|mut config| {
+            config.remove_profile(&name);
+            config.save()?;
+            Ok(config.profiles)
+        });
+        match result {
+            Ok(profiles) => {
+                info!("Deleted connection profile '{name}'");
+                self.profiles = profiles;
+                self.selected_profile_index = self.selected_profile_index.min(self.profiles.len());
+            }
+            Err(e) => error!("Failed to delete profile '{name}': {e}"),
+        }
+    }
+}
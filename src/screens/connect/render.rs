@@ -21,7 +21,9 @@ impl ConnectScreen {
             ])
             .split(area);
         match self.step {
+            ConnectDialogStep::ProfileSelection => self.render_profile_step(f, chunks[0]),
             ConnectDialogStep::ServerUrl => self.render_server_url_step(f, chunks[0]),
+            ConnectDialogStep::ServerSelection => self.render_server_selection_step(f, chunks[0]),
             ConnectDialogStep::EndpointSelection => self.render_endpoint_step(f, chunks[0]),
             ConnectDialogStep::SecurityConfiguration => self.render_security_step(f, chunks[0]),
             ConnectDialogStep::Authentication => self.render_auth_step(f, chunks[0]),
@@ -51,29 +53,53 @@ impl ConnectScreen {
         f.render_widget(logger_widget, chunks[1]);
         // Show connecting popup if discovery or connection is in progress
         if self.connect_in_progress {
-            if self.step == ConnectDialogStep::ServerUrl {
+            if self.pending_server_discovery {
+                self.render_connecting_popup(f, area, "Finding Servers");
+            } else if matches!(
+                self.step,
+                ConnectDialogStep::ServerUrl | ConnectDialogStep::ServerSelection
+            ) {
                 self.render_connecting_popup(f, area, "Discovering Endpoints");
             } else if self.step == ConnectDialogStep::Authentication {
                 self.render_connecting_popup(f, area, "Connecting to Server");
             }
         }
+        if self.profile_name_input.is_some() {
+            self.render_save_profile_popup(f, area);
+        }
+        if self.server_certificate_prompt.is_some() {
+            self.render_server_trust_popup(f, area);
+        }
     }
     pub fn render_help_line(&self, f: &mut Frame, area: Rect) {
         let help_text = match self.step {
+            _ if self.profile_name_input.is_some() => "Enter - Save profile | Esc - Cancel",
+            _ if self.server_certificate_prompt.is_some() => {
+                "o - Trust once | t - Trust permanently | r/Esc - Reject"
+            }
+            ConnectDialogStep::ProfileSelection => {
+                "↑↓ - Select profile | Del - Delete profile | F2 - Certificates | Esc/Alt+C - Cancel | Enter/Alt+N - Next"
+            }
+            ConnectDialogStep::ServerUrl if !self.profiles.is_empty() => {
+                "Space - toggle URL override | Ctrl+L - Find servers | Ctrl+R - Reverse Connect | F2 - Certificates | Esc - Profiles | Alt+C - Cancel | Enter/Alt+N - Next"
+            }
             ConnectDialogStep::ServerUrl => {
-                "Space - toggle URL override | Esc/Alt+C - Cancel | Enter/Alt+N - Next"
+                "Space - toggle URL override | Ctrl+L - Find servers | Ctrl+R - Reverse Connect | F2 - Certificates | Esc/Alt+C - Cancel | Enter/Alt+N - Next"
+            }
+            ConnectDialogStep::ServerSelection => {
+                "↑↓ - Select server | Alt+C - Cancel | Esc/Alt+B - Back | Enter/Alt+N - Endpoints"
             }
             ConnectDialogStep::EndpointSelection => {
                 "↑↓ - Select endpoint | Alt+C - Cancel | Esc/Alt+B - Back | Enter/Alt+N - Next"
             }
             ConnectDialogStep::SecurityConfiguration => {
-                "Tab - Next field | Space - Toggle auto-trust | Alt+C - Cancel | Esc/Alt+B - Back | Enter/Alt+N - Next"
+                "Tab - Next field | Space - Toggle auto-trust | Ctrl+G - Generate certificate | Alt+C - Cancel | Esc/Alt+B - Back | Enter/Alt+N - Next"
             }
             ConnectDialogStep::Authentication => {
                 if self.authentication_type == AuthenticationType::UserPassword {
-                    "↑↓ - Change auth type | Tab - Next field | Alt+C - Cancel | Esc/Alt+B - Back | Enter/Alt+N - Connect"
+                    "↑↓ - Change auth type | Tab - Next field | Ctrl+S - Save profile | Alt+C - Cancel | Esc/Alt+B - Back | Enter/Alt+N - Connect"
                 } else {
-                    "↑↓ - Change auth type | Alt+C - Cancel | Esc/Alt+B - Back | Enter/Alt+N - Connect"
+                    "↑↓ - Change auth type | Ctrl+S - Save profile | Alt+C - Cancel | Esc/Alt+B - Back | Enter/Alt+N - Connect"
                 }
             }
         };
@@ -0,0 +1,96 @@
+use super::types::*;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+
+impl ConnectScreen {
+    pub fn render_profile_step(&mut self, f: &mut Frame, area: Rect) {
+        let chunks = self.create_step_layout(area);
+        let title = crate::ui_utils::LayoutUtils::create_title_paragraph(
+            "Connect to OPC UA Server - Connection Profile",
+        );
+        f.render_widget(title, chunks[0]);
+
+        let new_connection = std::iter::once("➕ New connection".to_string());
+        let profiles = self.profiles.iter().map(|profile| {
+            let user = match profile.username.as_deref() {
+                Some(username) if profile.auth_type == "UserPassword" => format!(" as {username}"),
+                _ => String::new(),
+            };
+            format!(
+                "{} - {} [{}, {}]{user}",
+                profile.name, profile.server_url, profile.security_policy, profile.security_mode
+            )
+        });
+        let items: Vec<ListItem> = new_connection
+            .chain(profiles)
+            .enumerate()
+            .map(|(i, text)| {
+                let prefix = if i == self.selected_profile_index {
+                    "▶ "
+                } else {
+                    "  "
+                };
+                ListItem::new(format!("{prefix}{text}"))
+            })
+            .collect();
+
+        let profile_list = List::new(items).block(
+            Block::default()
+                .title(format!(
+                    "Saved Profiles ({}) - {}",
+                    self.profiles.len(),
+                    crate::config::CONFIG_FILE
+                ))
+                .borders(Borders::ALL)
+                .title_style(Style::default().fg(Color::White)),
+        );
+        f.render_widget(profile_list, chunks[1]);
+
+        let button_chunks = crate::ui_utils::LayoutUtils::create_button_layout(chunks[2]);
+        if self.connect_in_progress {
+            self.button_manager.set_button_enabled("next", false);
+        } else {
+            self.button_manager.set_button_enabled("next", true);
+        }
+        let button_rects = &[button_chunks[1], button_chunks[3]];
+        self.button_manager.render_buttons(f, button_rects);
+    }
+
+    /// Small centered prompt for the name of the profile to save
+    pub(super) fn render_save_profile_popup(&self, f: &mut Frame, area: Rect) {
+        let Some(input) = &self.profile_name_input else {
+            return;
+        };
+        let popup_width = 60.min(area.width);
+        let popup_height = 3;
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(popup_width)) / 2,
+            y: area.y + (area.height.saturating_sub(popup_height)) / 2,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        f.render_widget(Clear, popup_area);
+        let width = popup_area.width.max(3) - 3;
+        let scroll = input.visual_scroll(width as usize);
+        let prompt = Paragraph::new(input.value())
+            .style(Style::default().fg(Color::White))
+            .scroll((0, scroll as u16))
+            .block(
+                Block::default()
+                    .title("Save profile as (password is not saved)")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Yellow))
+                    .title_style(Style::default().fg(Color::Yellow))
+                    .style(Style::default().bg(Color::Black)),
+            );
+        f.render_widget(prompt, popup_area);
+
+        let cursor_x = input.visual_cursor().max(scroll) - scroll + 1;
+        f.set_cursor_position((popup_area.x + cursor_x as u16, popup_area.y + 1));
+    }
+}
@@ -0,0 +1,106 @@
+use super::types::*;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+impl ConnectScreen {
+    pub(super) fn render_server_trust_popup(&self, f: &mut Frame, area: Rect) {
+        let Some(untrusted) = &self.server_certificate_prompt else {
+            return;
+        };
+        let certificate = &untrusted.certificate;
+        let popup_width = 90.min(area.width);
+        let popup_height = 14.min(area.height);
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(popup_width)) / 2,
+            y: area.y + (area.height.saturating_sub(popup_height)) / 2,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        f.render_widget(Clear, popup_area);
+        let block = Block::default()
+            .title(" Untrusted Server Certificate ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title_style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .style(Style::default().bg(Color::Black).fg(Color::White));
+        f.render_widget(block, popup_area);
+
+        let inner_area = Rect::new(
+            popup_area.x + 1,
+            popup_area.y + 1,
+            popup_area.width.saturating_sub(2),
+            popup_area.height.saturating_sub(2),
+        );
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),    // Certificate details
+                Constraint::Length(1), // Choices
+            ])
+            .split(inner_area);
+
+        let label_style = Style::default().fg(Color::Yellow);
+        let field = |label: &str, value: Span<'static>| {
+            Line::from(vec![
+                Span::styled(format!("{label:<17}"), label_style),
+                value,
+            ])
+        };
+        let valid = format!("{} to {}", certificate.not_before, certificate.not_after);
+        let valid = if certificate.expired {
+            Span::styled(
+                format!("{valid} (expired)"),
+                Style::default().fg(Color::LightRed),
+            )
+        } else {
+            Span::raw(valid)
+        };
+        let application_uri = certificate.application_uri.clone().unwrap_or_default();
+        let application_uri = if untrusted.application_uri_matches() {
+            Span::styled(
+                format!("{application_uri} (matches the endpoint)"),
+                Style::default().fg(Color::LightGreen),
+            )
+        } else {
+            Span::styled(
+                format!(
+                    "{application_uri} (endpoint announces {})",
+                    untrusted.server_application_uri
+                ),
+                Style::default().fg(Color::LightRed),
+            )
+        };
+
+        let lines = vec![
+            Line::from(format!(
+                "{} presented a certificate that is not in the trusted store:",
+                untrusted.endpoint_url
+            )),
+            Line::from(""),
+            field("Subject", Span::raw(certificate.subject.clone())),
+            field("Issuer", Span::raw(certificate.issuer.clone())),
+            field("Thumbprint", Span::raw(certificate.thumbprint.clone())),
+            field("Valid", valid),
+            field("Application URI", application_uri),
+            field("File", Span::raw(certificate.path.display().to_string())),
+        ];
+        f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+
+        let choices = Paragraph::new(
+            "o - Trust once | t - Trust permanently (copy to pki/trusted) | r/Esc - Reject",
+        )
+        .style(Style::default().fg(Color::Yellow))
+        .alignment(Alignment::Center);
+        f.render_widget(choices, chunks[1]);
+    }
+}
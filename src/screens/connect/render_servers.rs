@@ -0,0 +1,78 @@
+use super::types::*;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, List, ListItem},
+    Frame,
+};
+
+impl ConnectScreen {
+    pub fn render_server_selection_step(&mut self, f: &mut Frame, area: Rect) {
+        let chunks = self.create_step_layout(area);
+        let title_text = format!(
+            "Connect to OPC UA Server - Step {}/{}: Select Server",
+            self.get_current_step_number(),
+            self.get_total_steps()
+        );
+        let title = crate::ui_utils::LayoutUtils::create_title_paragraph(&title_text);
+        f.render_widget(title, chunks[0]);
+
+        let mut items: Vec<ListItem> = self
+            .discovered_servers
+            .iter()
+            .enumerate()
+            .map(|(i, server)| {
+                let prefix = if i == self.selected_server_index {
+                    "▶ "
+                } else {
+                    "  "
+                };
+                if let Some(reverse_server) = &server.reverse_server {
+                    return ListItem::new(format!(
+                        "{prefix}{} - {} from {} ({} open)",
+                        reverse_server.server_uri,
+                        reverse_server.endpoint_url,
+                        reverse_server.peer,
+                        reverse_server.open_sockets
+                    ));
+                }
+                ListItem::new(format!(
+                    "{prefix}{} [{}] - {} ({})",
+                    server.application_name,
+                    server.application_type,
+                    server.discovery_urls.join(", "),
+                    server.application_uri
+                ))
+            })
+            .collect();
+
+        let title = match &self.reverse_connect {
+            Some(listener) if self.listing_reverse_servers => {
+                if items.is_empty() {
+                    items.push(ListItem::new("  Waiting for servers to connect..."));
+                }
+                format!(
+                    "Reverse Connect Servers ({}) - listening on {}",
+                    self.discovered_servers.len(),
+                    listener.address()
+                )
+            }
+            _ => format!(
+                "Registered Servers ({}) - {}",
+                self.discovered_servers.len(),
+                self.discovery_server_url
+            ),
+        };
+        let server_list = List::new(items).block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .title_style(Style::default().fg(Color::White)),
+        );
+        f.render_widget(server_list, chunks[1]);
+
+        let button_chunks = self.create_button_layout(chunks[2]);
+        let button_rects = self.get_button_rects(&button_chunks);
+        self.button_manager.render_buttons(f, &button_rects);
+    }
+}
@@ -0,0 +1,51 @@
+use super::types::*;
+use crate::cert_utils::{CertUtils, CertificateLocation, PKI_DIR};
+use crossterm::event::KeyCode;
+use log::{error, info, warn};
+use std::path::Path;
+
+impl ConnectScreen {
+    /// Answer the prompt for an untrusted server certificate; trusting it connects again
+    pub(super) fn handle_server_trust_input(&mut self, key: KeyCode) {
+        let Some(untrusted) = &self.server_certificate_prompt else {
+            return;
+        };
+        let certificate = &untrusted.certificate;
+        match key {
+            KeyCode::Char('o') | KeyCode::Char('O') => {
+                info!(
+                    "Trusting server certificate {} for this connection",
+                    certificate.subject
+                );
+                self.trust_once_thumbprint = Some(certificate.thumbprint.clone());
+            }
+            KeyCode::Char('t') | KeyCode::Char('T') => {
+                match CertUtils::move_certificate(
+                    Path::new(PKI_DIR),
+                    certificate,
+                    CertificateLocation::Trusted,
+                ) {
+                    Ok(path) => info!("Trusted server certificate {}", path.display()),
+                    Err(e) => {
+                        error!("Failed to trust the server certificate: {e}");
+                        return;
+                    }
+                }
+            }
+            KeyCode::Char('r') | KeyCode::Char('R') | KeyCode::Esc => {
+                warn!(
+                    "Rejected server certificate {}; it stays in {PKI_DIR}/{} and can be trusted on the certificate screen (F2)",
+                    certificate.subject,
+                    CertificateLocation::Rejected.dir_name()
+                );
+                self.server_certificate_prompt = None;
+                return;
+            }
+            _ => return,
+        }
+
+        self.server_certificate_prompt = None;
+        self.connect_in_progress = true;
+        self.pending_connection = true;
+    }
+}
@@ -0,0 +1,214 @@
+use super::types::*;
+use crate::client::ConnectionStatus;
+use crate::connection_manager::{ConnectionConfig, ConnectionManager};
+use crate::reverse_connect::ReverseServer;
+use anyhow::{anyhow, Result};
+use crossterm::event::{KeyCode, KeyModifiers};
+use log::{error, info};
+use tui_input::Input;
+use tui_logger::TuiWidgetEvent;
+
+impl ConnectScreen {
+    /// Treat the server URL as a Local Discovery Server and list its servers instead of endpoints
+    pub(super) fn start_server_discovery(&mut self) {
+        self.validate_server_url();
+        if let Some(error) = &self.server_url_validation_error {
+            error!("URL Validation: {error}");
+            return;
+        }
+        self.connect_in_progress = true;
+        self.pending_server_discovery = true;
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub(super) async fn find_servers(&mut self) -> Result<()> {
+        let url = self.get_server_url();
+        info!("Calling FindServers on discovery server: {url}");
+        self.discovery_server_url = url.clone();
+        self.listing_reverse_servers = false;
+
+        let servers =
+            ConnectionManager::find_servers(&url, &ConnectionConfig::ui_discovery()).await?;
+        self.discovered_servers = servers
+            .into_iter()
+            .map(|server| ServerInfo {
+                application_name: server.application_name.text.as_ref().to_string(),
+                application_uri: server.application_uri.as_ref().to_string(),
+                application_type: format!("{:?}", server.application_type),
+                discovery_urls: server
+                    .discovery_urls
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|url| url.as_ref().to_string())
+                    .collect(),
+                reverse_server: None,
+            })
+            .collect();
+        self.selected_server_index = 0;
+
+        if self.discovered_servers.is_empty() {
+            error!("Discovery server returned no servers");
+            return Err(anyhow!("No servers registered"));
+        }
+        for (i, server) in self.discovered_servers.iter().enumerate() {
+            info!(
+                "Server {}: {} ({}) {}",
+                i + 1,
+                server.application_name,
+                server.application_type,
+                server.discovery_urls.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    /// List the servers that connected to the Reverse Connect listener
+    pub(super) fn show_reverse_servers(&mut self) {
+        let Some(listener) = &self.reverse_connect else {
+            error!(
+                "Reverse Connect is off, start the client with --reverse-connect-listen <address>"
+            );
+            return;
+        };
+        info!(
+            "Listing servers connected to the Reverse Connect listener on {}",
+            listener.address()
+        );
+        self.discovery_server_url = self.get_server_url();
+        self.listing_reverse_servers = true;
+        self.selected_server_index = 0;
+        self.refresh_reverse_servers();
+        self.step = ConnectDialogStep::ServerSelection;
+        self.input_mode = InputMode::Normal;
+        self.setup_buttons_for_current_step();
+    }
+
+    /// Servers open and close reverse connections at any time, so the list follows the listener
+    pub(super) fn refresh_reverse_servers(&mut self) {
+        let Some(listener) = &self.reverse_connect else {
+            return;
+        };
+        self.discovered_servers = listener
+            .servers()
+            .into_iter()
+            .map(|server| ServerInfo {
+                application_name: server.server_uri.clone(),
+                application_uri: server.server_uri.clone(),
+                application_type: "Server".to_string(),
+                discovery_urls: vec![server.endpoint_url.clone()],
+                reverse_server: Some(server),
+            })
+            .collect();
+        self.selected_server_index = self
+            .selected_server_index
+            .min(self.discovered_servers.len().saturating_sub(1));
+    }
+
+    pub(super) async fn handle_server_selection_input(
+        &mut self,
+        key: KeyCode,
+        _modifiers: KeyModifiers,
+    ) -> Result<Option<ConnectionStatus>> {
+        let last_index = self.discovered_servers.len().saturating_sub(1);
+        match key {
+            KeyCode::Up => {
+                self.selected_server_index = if self.selected_server_index == 0 {
+                    last_index
+                } else {
+                    self.selected_server_index - 1
+                };
+            }
+            KeyCode::Down => {
+                self.selected_server_index = if self.selected_server_index >= last_index {
+                    0
+                } else {
+                    self.selected_server_index + 1
+                };
+            }
+            KeyCode::Enter => self.select_server(),
+            KeyCode::Esc => self.back_to_server_url(),
+            KeyCode::PageUp => {
+                self.logger_widget_state
+                    .transition(TuiWidgetEvent::PrevPageKey);
+            }
+            KeyCode::PageDown => {
+                self.logger_widget_state
+                    .transition(TuiWidgetEvent::NextPageKey);
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    /// Discover the endpoints of the selected server through its first opc.tcp discovery URL
+    pub(super) fn select_server(&mut self) {
+        let Some(server) = self.discovered_servers.get(self.selected_server_index) else {
+            return;
+        };
+        if let Some(reverse_server) = &server.reverse_server {
+            self.select_reverse_server(reverse_server.clone());
+            return;
+        }
+        let Some(url) = server
+            .discovery_urls
+            .iter()
+            .find(|url| url.to_lowercase().starts_with("opc.tcp://"))
+            .cloned()
+        else {
+            error!(
+                "Server '{}' has no opc.tcp discovery URL",
+                server.application_name
+            );
+            return;
+        };
+
+        info!("Selected server '{}' at {url}", server.application_name);
+        // Discovery URLs may carry a path, which the typed URL validation does not allow
+        self.server_url_input = Input::default().with_value(url);
+        self.server_url_validation_error = None;
+        self.connect_in_progress = true;
+        self.pending_discovery = true;
+    }
+
+    /// Discover and connect through a relay to the sockets the server opened. The server's
+    /// endpoint URLs are not reachable, so the relay URL replaces them. The relay stays open
+    /// until the connection is made or the server is deselected.
+    fn select_reverse_server(&mut self, server: ReverseServer) {
+        let Some(listener) = &self.reverse_connect else {
+            return;
+        };
+        let relay = match listener.relay(&server) {
+            Ok(relay) => relay,
+            Err(e) => {
+                error!("Cannot relay to {}: {e}", server.server_uri);
+                return;
+            }
+        };
+        let url = relay.url().to_string();
+        self.reverse_relay = Some(relay);
+
+        info!(
+            "Selected reverse connected server '{}' from {}",
+            server.server_uri, server.peer
+        );
+        self.server_url_input = Input::default().with_value(url);
+        self.server_url_validation_error = None;
+        self.use_original_url = true;
+        self.connect_in_progress = true;
+        self.pending_discovery = true;
+    }
+
+    pub(super) fn back_to_server_url(&mut self) {
+        if self.listing_reverse_servers {
+            self.listing_reverse_servers = false;
+            self.use_original_url = false;
+            self.reverse_relay = None;
+        }
+        self.discovered_servers.clear();
+        self.server_url_input = Input::default().with_value(self.discovery_server_url.clone());
+        self.validate_server_url();
+        self.step = ConnectDialogStep::ServerUrl;
+        self.input_mode = InputMode::Editing;
+        self.setup_buttons_for_current_step();
+    }
+}
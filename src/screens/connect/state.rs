@@ -1,6 +1,6 @@
 use super::types::*;
 use crate::components::ButtonManager;
-use log::{debug, info, warn};
+use log::{debug, info};
 use tui_input::Input;
 use tui_logger::TuiWidgetState;
 
@@ -8,10 +8,20 @@ impl ConnectScreen {
     pub fn new() -> Self {
         let mut screen = Self {
             step: ConnectDialogStep::ServerUrl,
+            profiles: Vec::new(),
+            selected_profile_index: 0,
+            active_profile: None,
+            profile_name_input: None,
             server_url_input: Input::default()
                 .with_value(crate::screens::connect::constants::ui::DEFAULT_SERVER_URL.to_string()),
             server_url_validation_error: None,
             use_original_url: false, // Default to false (use discovered endpoint URLs)
+            discovery_server_url: String::new(),
+            discovered_servers: Vec::new(),
+            reverse_connect: None,
+            listing_reverse_servers: false,
+            reverse_relay: None,
+            selected_server_index: 0,
             discovered_endpoints: Vec::new(),
             selected_endpoint_index: usize::default(),
             endpoint_scroll_offset: 0,
@@ -31,6 +41,7 @@ impl ConnectScreen {
             user_private_key_input: Input::default(),
             connect_in_progress: false,
             pending_discovery: false,
+            pending_server_discovery: false,
             pending_connection: false,
             show_security_validation: false,
             show_auth_validation: false,
@@ -38,15 +49,17 @@ impl ConnectScreen {
             // OPC UA connection state
             client: None,
             session: None,
+            connection_target: None,
+            server_certificate_prompt: None,
+            trust_once_thumbprint: None,
 
             input_mode: InputMode::Editing,
             logger_widget_state: TuiWidgetState::new(),
             button_manager: ButtonManager::new(),
         }; // Add initial log messages using the log crate
         info!("OPC UA Client initialized");
-        info!("Loading configuration from config.json");
-        warn!("Configuration file not found, using defaults");
-        info!("Default server URL loaded");
+        screen.load_profiles();
+        screen.step = screen.initial_step();
         debug!("Button manager created with hotkeys");
         debug!("Input handlers configured");
         screen.setup_buttons_for_current_step();
@@ -54,11 +67,20 @@ impl ConnectScreen {
     }
     /// Async version of reset that properly handles session cleanup
     pub async fn async_reset(&mut self) {
-        self.step = ConnectDialogStep::ServerUrl;
+        self.load_profiles();
+        self.step = self.initial_step();
+        self.active_profile = None;
+        self.profile_name_input = None;
+        self.server_certificate_prompt = None;
+        self.trust_once_thumbprint = None;
         self.server_url_input = Input::default()
             .with_value(crate::screens::connect::constants::ui::DEFAULT_SERVER_URL.to_string());
         self.server_url_validation_error = None;
         self.use_original_url = false; // Reset to default
+        self.discovered_servers.clear();
+        self.listing_reverse_servers = false;
+        self.reverse_relay = None;
+        self.selected_server_index = 0;
         self.discovered_endpoints.clear();
         self.selected_endpoint_index = 0;
         self.endpoint_scroll_offset = 0;
@@ -76,6 +98,7 @@ impl ConnectScreen {
         self.connect_in_progress = false;
 
         self.pending_discovery = false;
+        self.pending_server_discovery = false;
         self.pending_connection = false; // Clean up OPC UA connection properly using async methods
         if let Some(session) = self.session.take() {
             // Use spawn_blocking to safely disconnect the session
@@ -89,6 +112,7 @@ impl ConnectScreen {
             }
         }
         self.client = None;
+        self.connection_target = None;
         self.input_mode = InputMode::Editing;
         self.setup_buttons_for_current_step();
     }
@@ -97,6 +121,7 @@ impl ConnectScreen {
     pub async fn clear_connection(&mut self) {
         self.connect_in_progress = false;
         self.pending_discovery = false;
+        self.pending_server_discovery = false;
         self.pending_connection = false;
 
         // Clean up OPC UA connection properly using async methods
@@ -113,6 +138,7 @@ impl ConnectScreen {
         }
 
         self.client = None;
+        self.connection_target = None;
         self.input_mode = InputMode::Editing;
         self.setup_buttons_for_current_step();
     }
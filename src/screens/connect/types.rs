@@ -1,4 +1,7 @@
 use crate::components::ButtonManager;
+use crate::config::ConnectionProfile;
+use crate::connection_manager::{ConnectionTarget, UntrustedServerCertificate};
+use crate::reverse_connect::{ReverseConnectListener, ReverseRelay, ReverseServer};
 use opcua::client::prelude::*;
 use opcua::types::EndpointDescription;
 use parking_lot::RwLock;
@@ -8,12 +11,24 @@ use tui_logger::TuiWidgetState;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConnectDialogStep {
+    ProfileSelection, // Saved connection profiles, shown first when any exist
     ServerUrl,
+    ServerSelection, // Servers registered with the Local Discovery Server at the URL, or reverse connected
     EndpointSelection,
     SecurityConfiguration, // New step for security settings
     Authentication,
 }
 
+/// A server returned by FindServers or announced with ReverseHello
+#[derive(Debug, Clone)]
+pub struct ServerInfo {
+    pub application_name: String,
+    pub application_uri: String,
+    pub application_type: String, // Server, ClientAndServer or DiscoveryServer
+    pub discovery_urls: Vec<String>,
+    pub reverse_server: Option<ReverseServer>, // Set when the server connected to the listener
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum SecurityPolicy {
     None,
@@ -71,9 +86,21 @@ pub enum SecurityField {
 pub struct ConnectScreen {
     // Connection dialog state
     pub step: ConnectDialogStep,
+
+    // Connection profiles from config.json
+    pub profiles: Vec<ConnectionProfile>,
+    pub selected_profile_index: usize, // 0 is "New connection", profiles follow
+    pub active_profile: Option<ConnectionProfile>, // Profile whose endpoint is preselected
+    pub profile_name_input: Option<Input>, // Open "save profile" prompt
     pub server_url_input: Input,
     pub server_url_validation_error: Option<String>,
     pub use_original_url: bool, // New field for forcing original URL usage
+    pub discovery_server_url: String, // URL FindServers was called on
+    pub discovered_servers: Vec<ServerInfo>, // From FindServers, empty when the URL was typed
+    pub reverse_connect: Option<ReverseConnectListener>, // From --reverse-connect-listen
+    pub listing_reverse_servers: bool, // Server list comes from the Reverse Connect listener
+    pub reverse_relay: Option<ReverseRelay>, // Relay to the chosen reverse server while connecting
+    pub selected_server_index: usize,
     pub discovered_endpoints: Vec<EndpointInfo>,
     pub selected_endpoint_index: usize,
     pub endpoint_scroll_offset: usize, // New field for scrolling
@@ -93,6 +120,7 @@ pub struct ConnectScreen {
     pub user_private_key_input: Input,
     pub connect_in_progress: bool,
     pub pending_discovery: bool, // New field to track if discovery should happen
+    pub pending_server_discovery: bool, // FindServers should run on the server URL
     pub pending_connection: bool, // New field to track if connection should happen
     pub show_security_validation: bool, // Track whether to show validation highlighting
     pub show_auth_validation: bool, // Track whether to show authentication validation highlighting
@@ -100,6 +128,9 @@ pub struct ConnectScreen {
     // OPC UA connection state
     pub client: Option<Client>,
     pub session: Option<Arc<RwLock<Session>>>,
+    pub connection_target: Option<ConnectionTarget>,
+    pub server_certificate_prompt: Option<UntrustedServerCertificate>, // Open trust prompt
+    pub trust_once_thumbprint: Option<String>, // Server certificate trusted for this connection
 
     // Input handling
     pub input_mode: InputMode,
@@ -441,7 +472,8 @@ impl ConnectScreen {
     /// Get current step number (1-based)
     pub fn get_current_step_number(&self) -> u8 {
         match self.step {
-            ConnectDialogStep::ServerUrl => 1,
+            ConnectDialogStep::ProfileSelection => 0,
+            ConnectDialogStep::ServerUrl | ConnectDialogStep::ServerSelection => 1,
             ConnectDialogStep::EndpointSelection => 2,
             ConnectDialogStep::SecurityConfiguration => 3,
             ConnectDialogStep::Authentication => {
@@ -22,10 +22,8 @@ impl ConnectValidator {
         let mut errors = Vec::new();
 
         match auth_type {
-            AuthenticationType::UserPassword => {
-                if inputs.username.trim().is_empty() {
-                    errors.push("Username is required".to_string());
-                }
+            AuthenticationType::UserPassword if inputs.username.trim().is_empty() => {
+                errors.push("Username is required".to_string());
             }
             AuthenticationType::X509Certificate => {
                 if inputs.cert_path.trim().is_empty() {
@@ -1,5 +1,7 @@
 pub mod browse;
+pub mod certificates;
 pub mod connect;
 
 pub use browse::BrowseScreen;
+pub use certificates::CertificateScreen;
 pub use connect::ConnectScreen;
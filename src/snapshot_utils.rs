@@ -0,0 +1,611 @@
+//! Address-space snapshots: a subtree with the attributes of every node, saved as JSON and compared
+//! node by node to find what changed between two servers or two points in time.
+
+use crate::client::{OpcUaClientManager, OpcUaExportAttributes};
+use crate::value_utils::ValueUtils;
+use anyhow::{anyhow, Result};
+use opcua::types::{LocalizedText, NodeClass, NodeId};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// A captured subtree, as written to a snapshot file
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub server_url: String,
+    pub root: String, // NodeId of the subtree root
+    pub depth: usize, // Levels captured below the root
+    pub include_values: bool,
+    pub created: String,
+    // NamespaceArray of the server, to compare NodeIds and BrowseNames by namespace URI
+    #[serde(default)]
+    pub namespaces: Vec<String>,
+    pub nodes: Vec<SnapshotNode>, // Depth-first
+}
+
+/// A node of a snapshot, identified across snapshots by its BrowseName path from the root
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SnapshotNode {
+    pub depth: usize, // 0 for the root
+    pub path: String,
+    pub node_id: String,
+    pub node_class: String,
+    pub attributes: BTreeMap<String, String>, // Only the attributes the node has
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiffKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl DiffKind {
+    pub fn marker(self) -> &'static str {
+        match self {
+            DiffKind::Added => "+",
+            DiffKind::Removed => "-",
+            DiffKind::Changed => "~",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DiffKind::Added => "added",
+            DiffKind::Removed => "removed",
+            DiffKind::Changed => "changed",
+        }
+    }
+}
+
+/// An attribute that differs; `None` when the node has no such attribute on that side
+#[derive(Clone, Debug)]
+pub struct AttributeChange {
+    pub name: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// A node that was added, removed or changed between two snapshots
+#[derive(Clone, Debug)]
+pub struct NodeDiff {
+    pub kind: DiffKind,
+    pub path: String,
+    pub node_id: String,
+    pub node_class: String,
+    pub changes: Vec<AttributeChange>, // Empty for added and removed nodes
+}
+
+pub struct SnapshotUtils;
+
+impl SnapshotUtils {
+    /// Browse the subtree below `root_id` depth-first and read the attributes of every node.
+    /// `on_progress` gets the node count and current path and returns false to cancel, in which
+    /// case `None` is returned.
+    pub async fn capture(
+        client: &Arc<RwLock<OpcUaClientManager>>,
+        root_id: &NodeId,
+        depth: usize,
+        include_values: bool,
+        on_progress: &mut (dyn FnMut(usize, &str) -> bool + Send),
+    ) -> Result<Option<Snapshot>> {
+        let (server_url, namespaces, root) = {
+            let client_guard = client.read().await;
+            let namespaces = client_guard.read_namespace_array().await?;
+            let search_attributes = client_guard
+                .read_nodes_search_attributes(std::slice::from_ref(root_id), false)
+                .await?
+                .pop()
+                .ok_or_else(|| anyhow!("No attributes for {}", root_id))?;
+            let attributes = client_guard
+                .read_nodes_export_attributes(std::slice::from_ref(root_id))
+                .await?
+                .pop()
+                .unwrap_or_default();
+            let root = Self::snapshot_node(
+                0,
+                search_attributes.browse_name,
+                root_id,
+                search_attributes.node_class,
+                &search_attributes.display_name,
+                &attributes,
+                include_values,
+            );
+            (client_guard.server_url.clone(), namespaces, root)
+        };
+
+        // A node under two parents, or in a cycle such as HasNotifier, is captured once
+        let mut visited: HashSet<NodeId> = HashSet::new();
+        let mut nodes: Vec<SnapshotNode> = Vec::new();
+        let mut stack = vec![(root, root_id.clone())];
+        while let Some((node, node_id)) = stack.pop() {
+            if !visited.insert(node_id.clone()) {
+                continue;
+            }
+            if !on_progress(nodes.len() + 1, &node.path) {
+                return Ok(None);
+            }
+            if node.depth < depth {
+                let children = Self::read_children(client, &node, &node_id, include_values).await?;
+                stack.extend(children.into_iter().rev());
+            }
+            nodes.push(node);
+        }
+
+        Ok(Some(Snapshot {
+            server_url,
+            root: root_id.to_string(),
+            depth,
+            include_values,
+            created: chrono::Local::now().to_rfc3339(),
+            namespaces,
+            nodes,
+        }))
+    }
+
+    /// Browse the children of a snapshot node, sorted by BrowseName, with their attributes
+    async fn read_children(
+        client: &Arc<RwLock<OpcUaClientManager>>,
+        parent: &SnapshotNode,
+        parent_id: &NodeId,
+        include_values: bool,
+    ) -> Result<Vec<(SnapshotNode, NodeId)>> {
+        let client_guard = client.read().await;
+        let mut children = match client_guard.browse_node(parent_id).await {
+            Ok(children) => children,
+            Err(e) => {
+                log::warn!("snapshot: failed to browse {parent_id}: {e}");
+                return Ok(Vec::new());
+            }
+        };
+        children.sort_by(|a, b| a.browse_name.cmp(&b.browse_name));
+
+        let child_ids: Vec<NodeId> = children.iter().map(|child| child.node_id.clone()).collect();
+        let attributes = client_guard
+            .read_nodes_export_attributes(&child_ids)
+            .await?;
+
+        // Paths must be unique, so siblings sharing a BrowseName are told apart by NodeId
+        let mut name_counts: HashMap<String, usize> = HashMap::new();
+        for child in &children {
+            *name_counts.entry(child.browse_name.clone()).or_default() += 1;
+        }
+
+        Ok(children
+            .into_iter()
+            .zip(attributes)
+            .map(|(child, attributes)| {
+                let name = if name_counts[&child.browse_name] > 1 {
+                    format!("{} [{}]", child.browse_name, child.node_id)
+                } else {
+                    child.browse_name.clone()
+                };
+                let node = Self::snapshot_node(
+                    parent.depth + 1,
+                    format!("{}/{}", parent.path, name),
+                    &child.node_id,
+                    child.node_class,
+                    &child.display_name,
+                    &attributes,
+                    include_values,
+                );
+                (node, child.node_id)
+            })
+            .collect())
+    }
+
+    fn snapshot_node(
+        depth: usize,
+        path: String,
+        node_id: &NodeId,
+        node_class: NodeClass,
+        display_name: &str,
+        attributes: &OpcUaExportAttributes,
+        include_values: bool,
+    ) -> SnapshotNode {
+        let localized = |text: &LocalizedText| text.text.value().clone().unwrap_or_default();
+        let mut values = vec![
+            (
+                "BrowseName",
+                attributes.browse_name.as_ref().map(|name| {
+                    let text = name.name.value().clone().unwrap_or_default();
+                    format!("{}:{}", name.namespace_index, text)
+                }),
+            ),
+            ("DisplayName", Some(display_name.to_string())),
+            (
+                "Description",
+                attributes.description.as_ref().map(localized),
+            ),
+            (
+                "DataType",
+                attributes
+                    .data_type
+                    .as_ref()
+                    .map(OpcUaClientManager::format_data_type),
+            ),
+            (
+                "ValueRank",
+                attributes.value_rank.map(|rank| rank.to_string()),
+            ),
+            (
+                "ArrayDimensions",
+                attributes
+                    .array_dimensions
+                    .as_ref()
+                    .map(|dimensions| format!("{dimensions:?}")),
+            ),
+            (
+                "AccessLevel",
+                attributes
+                    .access_level
+                    .map(OpcUaClientManager::format_access_level),
+            ),
+            (
+                "UserAccessLevel",
+                attributes
+                    .user_access_level
+                    .map(OpcUaClientManager::format_access_level),
+            ),
+            (
+                "MinimumSamplingInterval",
+                attributes
+                    .minimum_sampling_interval
+                    .map(|interval| interval.to_string()),
+            ),
+            ("Historizing", attributes.historizing.map(|b| b.to_string())),
+            (
+                "EventNotifier",
+                attributes.event_notifier.map(|n| n.to_string()),
+            ),
+            ("Executable", attributes.executable.map(|b| b.to_string())),
+            ("IsAbstract", attributes.is_abstract.map(|b| b.to_string())),
+            ("Symmetric", attributes.symmetric.map(|b| b.to_string())),
+            (
+                "InverseName",
+                attributes.inverse_name.as_ref().map(localized),
+            ),
+        ];
+        if include_values {
+            values.push((
+                "Value",
+                attributes.value.as_ref().map(ValueUtils::format_for_edit),
+            ));
+        }
+
+        SnapshotNode {
+            depth,
+            path,
+            node_id: node_id.to_string(),
+            node_class: format!("{node_class:?}"),
+            attributes: values
+                .into_iter()
+                .filter_map(|(name, value)| Some((name.to_string(), value?)))
+                .collect(),
+        }
+    }
+
+    pub fn save(path: &Path, snapshot: &Snapshot) -> Result<()> {
+        let text = serde_json::to_string_pretty(snapshot)?;
+        std::fs::write(path, text)
+            .map_err(|e| anyhow!("Cannot write {}: {}", path.display(), e))?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Snapshot> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Cannot read {}: {}", path.display(), e))?;
+        serde_json::from_str(&text)
+            .map_err(|e| anyhow!("{} is not a snapshot: {}", path.display(), e))
+    }
+
+    /// Compare two snapshots by path and return the differences in tree order. Only the levels
+    /// both snapshots captured are compared, and values only when both include them. NodeIds,
+    /// BrowseNames and DataTypes are compared by namespace URI, since two servers often number
+    /// the same namespaces differently.
+    pub fn diff(old: &Snapshot, new: &Snapshot) -> Vec<NodeDiff> {
+        let depth = old.depth.min(new.depth);
+        let compare_values = old.include_values && new.include_values;
+        let old_nodes: HashMap<&str, &SnapshotNode> = old
+            .nodes
+            .iter()
+            .filter(|node| node.depth <= depth)
+            .map(|node| (node.path.as_str(), node))
+            .collect();
+        let new_paths: HashSet<&str> = new.nodes.iter().map(|node| node.path.as_str()).collect();
+
+        let mut diffs = Vec::new();
+        for node in new.nodes.iter().filter(|node| node.depth <= depth) {
+            match old_nodes.get(node.path.as_str()) {
+                None => diffs.push(Self::node_diff(DiffKind::Added, node, Vec::new())),
+                Some(old_node) => {
+                    let changes = Self::attribute_changes(
+                        (old_node, &old.namespaces),
+                        (node, &new.namespaces),
+                        compare_values,
+                    );
+                    if !changes.is_empty() {
+                        diffs.push(Self::node_diff(DiffKind::Changed, node, changes));
+                    }
+                }
+            }
+        }
+        for node in old_nodes.values() {
+            if !new_paths.contains(node.path.as_str()) {
+                diffs.push(Self::node_diff(DiffKind::Removed, node, Vec::new()));
+            }
+        }
+
+        diffs.sort_by(|a, b| a.path.split('/').cmp(b.path.split('/')));
+        diffs
+    }
+
+    fn attribute_changes(
+        (old, old_namespaces): (&SnapshotNode, &[String]),
+        (new, new_namespaces): (&SnapshotNode, &[String]),
+        compare_values: bool,
+    ) -> Vec<AttributeChange> {
+        let mut changes = Vec::new();
+        let mut push = |name: &str, old_value: Option<&String>, new_value: Option<&String>| {
+            let portable = |value: Option<&String>, namespaces: &[String]| {
+                value.map(|value| Self::with_namespace_uris(name, value, namespaces))
+            };
+            if portable(old_value, old_namespaces) != portable(new_value, new_namespaces) {
+                changes.push(AttributeChange {
+                    name: name.to_string(),
+                    old: old_value.cloned(),
+                    new: new_value.cloned(),
+                });
+            }
+        };
+
+        push("NodeId", Some(&old.node_id), Some(&new.node_id));
+        push("NodeClass", Some(&old.node_class), Some(&new.node_class));
+        let names: BTreeSet<&String> = old
+            .attributes
+            .keys()
+            .chain(new.attributes.keys())
+            .filter(|name| compare_values || name.as_str() != "Value")
+            .collect();
+        for name in names {
+            push(name, old.attributes.get(name), new.attributes.get(name));
+        }
+        changes
+    }
+
+    /// An attribute with namespace indexes replaced by their URIs, where it holds any: "ns=2;"
+    /// in NodeIds and DataTypes, and the "2:" prefix of BrowseNames. Indexes missing from the
+    /// NamespaceArray, e.g. in snapshots saved without it, are left as they are.
+    fn with_namespace_uris(name: &str, value: &str, namespaces: &[String]) -> String {
+        static NODE_ID_NAMESPACE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+        let uri = |index: &str| {
+            index
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| namespaces.get(index))
+        };
+        match name {
+            "NodeId" | "DataType" => NODE_ID_NAMESPACE
+                .get_or_init(|| regex::Regex::new(r"ns=(\d+);").expect("Invalid regex pattern"))
+                .replace_all(
+                    value,
+                    |captures: &regex::Captures| match uri(&captures[1]) {
+                        Some(uri) => format!("nsu={uri};"),
+                        None => captures[0].to_string(),
+                    },
+                )
+                .into_owned(),
+            "BrowseName" => match value
+                .split_once(':')
+                .and_then(|(index, text)| Some((uri(index)?, text)))
+            {
+                Some((uri, text)) => format!("{uri}:{text}"),
+                None => value.to_string(),
+            },
+            _ => value.to_string(),
+        }
+    }
+
+    fn node_diff(kind: DiffKind, node: &SnapshotNode, changes: Vec<AttributeChange>) -> NodeDiff {
+        NodeDiff {
+            kind,
+            path: node.path.clone(),
+            node_id: node.node_id.clone(),
+            node_class: node.node_class.clone(),
+            changes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(depth: usize, include_values: bool, nodes: Vec<SnapshotNode>) -> Snapshot {
+        Snapshot {
+            server_url: "opc.tcp://localhost:4840".to_string(),
+            root: "i=85".to_string(),
+            depth,
+            include_values,
+            created: String::new(),
+            namespaces: vec!["http://opcfoundation.org/UA/".to_string()],
+            nodes,
+        }
+    }
+
+    fn node(path: &str, node_id: &str, attributes: &[(&str, &str)]) -> SnapshotNode {
+        SnapshotNode {
+            depth: path.matches('/').count(),
+            path: path.to_string(),
+            node_id: node_id.to_string(),
+            node_class: "Object".to_string(),
+            attributes: attributes
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    fn summary(diffs: &[NodeDiff]) -> Vec<(DiffKind, &str)> {
+        diffs
+            .iter()
+            .map(|diff| (diff.kind, diff.path.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn identical_snapshots_have_no_differences() {
+        let nodes = vec![
+            node("Objects", "i=85", &[("BrowseName", "Objects")]),
+            node("Objects/Server", "i=2253", &[("BrowseName", "Server")]),
+        ];
+        let old = snapshot(1, false, nodes.clone());
+        let new = snapshot(1, false, nodes);
+        assert!(SnapshotUtils::diff(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn nodes_are_matched_by_path_and_sorted_in_tree_order() {
+        let old = snapshot(
+            2,
+            false,
+            vec![
+                node("Objects", "i=85", &[]),
+                node("Objects/B", "ns=1;s=B", &[]),
+                node("Objects/B/Gone", "ns=1;s=Gone", &[]),
+            ],
+        );
+        let new = snapshot(
+            2,
+            false,
+            vec![
+                node("Objects", "i=85", &[]),
+                node("Objects/A", "ns=1;s=A", &[]),
+                node("Objects/B", "ns=1;s=B2", &[]),
+            ],
+        );
+        let diffs = SnapshotUtils::diff(&old, &new);
+        assert_eq!(
+            summary(&diffs),
+            vec![
+                (DiffKind::Added, "Objects/A"),
+                (DiffKind::Changed, "Objects/B"),
+                (DiffKind::Removed, "Objects/B/Gone"),
+            ]
+        );
+        let change = &diffs[1].changes[0];
+        assert_eq!(change.name, "NodeId");
+        assert_eq!(change.old.as_deref(), Some("ns=1;s=B"));
+        assert_eq!(change.new.as_deref(), Some("ns=1;s=B2"));
+    }
+
+    #[test]
+    fn levels_below_the_shallower_snapshot_are_ignored() {
+        let old = snapshot(
+            1,
+            false,
+            vec![
+                node("Objects", "i=85", &[]),
+                node("Objects/Server", "i=2253", &[]),
+            ],
+        );
+        let new = snapshot(
+            2,
+            false,
+            vec![
+                node("Objects", "i=85", &[]),
+                node("Objects/Server", "i=2253", &[]),
+                node("Objects/Server/Status", "i=2256", &[]),
+            ],
+        );
+        assert!(SnapshotUtils::diff(&old, &new).is_empty());
+        assert!(SnapshotUtils::diff(&new, &old).is_empty());
+    }
+
+    #[test]
+    fn values_are_compared_only_when_both_snapshots_include_them() {
+        let old_nodes = vec![node("Objects/Speed", "ns=1;i=1", &[("Value", "1")])];
+        let new_nodes = vec![node("Objects/Speed", "ns=1;i=1", &[("Value", "2")])];
+
+        let without_values = SnapshotUtils::diff(
+            &snapshot(1, true, old_nodes.clone()),
+            &snapshot(1, false, new_nodes.clone()),
+        );
+        assert!(without_values.is_empty());
+
+        let with_values =
+            SnapshotUtils::diff(&snapshot(1, true, old_nodes), &snapshot(1, true, new_nodes));
+        assert_eq!(with_values.len(), 1);
+        assert_eq!(with_values[0].changes[0].name, "Value");
+    }
+
+    #[test]
+    fn attributes_missing_on_one_side_are_reported() {
+        let old = snapshot(
+            1,
+            false,
+            vec![node(
+                "Objects/Speed",
+                "ns=1;i=1",
+                &[("Historizing", "false")],
+            )],
+        );
+        let new = snapshot(
+            1,
+            false,
+            vec![node("Objects/Speed", "ns=1;i=1", &[("ValueRank", "-1")])],
+        );
+        let diffs = SnapshotUtils::diff(&old, &new);
+        let changes: Vec<(&str, Option<&str>, Option<&str>)> = diffs[0]
+            .changes
+            .iter()
+            .map(|c| (c.name.as_str(), c.old.as_deref(), c.new.as_deref()))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("Historizing", Some("false"), None),
+                ("ValueRank", None, Some("-1")),
+            ]
+        );
+    }
+
+    #[test]
+    fn namespace_indexes_are_compared_by_uri() {
+        let mut old = snapshot(
+            1,
+            false,
+            vec![node(
+                "Objects/Pump",
+                "ns=2;s=Pump",
+                &[("BrowseName", "2:Pump"), ("DataType", "ns=2;i=3001")],
+            )],
+        );
+        old.namespaces
+            .extend(["urn:a".to_string(), "urn:plant".to_string()]);
+        let mut new = snapshot(
+            1,
+            false,
+            vec![node(
+                "Objects/Pump",
+                "ns=1;s=Pump",
+                &[("BrowseName", "1:Pump"), ("DataType", "ns=1;i=3001")],
+            )],
+        );
+        new.namespaces.push("urn:plant".to_string());
+        assert!(SnapshotUtils::diff(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn same_index_of_different_namespaces_is_a_change() {
+        let mut old = snapshot(1, false, vec![node("Objects/Pump", "ns=1;s=Pump", &[])]);
+        old.namespaces.push("urn:plant".to_string());
+        let mut new = snapshot(1, false, vec![node("Objects/Pump", "ns=1;s=Pump", &[])]);
+        new.namespaces.push("urn:other".to_string());
+        let diffs = SnapshotUtils::diff(&old, &new);
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].changes[0].name, "NodeId");
+    }
+}
@@ -10,6 +10,7 @@ use ratatui::{
     backend::CrosstermBackend,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
+    text::{Line, Span},
     widgets::Paragraph,
     Frame, Terminal,
 };
@@ -21,11 +22,12 @@ use std::{
 use tokio::sync::RwLock;
 
 use crate::client::{ConnectionStatus, OpcUaClientManager};
+use crate::reverse_connect::ReverseConnectListener;
 use crate::screens::connect::ConnectDialogStep;
-use crate::screens::{BrowseScreen, ConnectScreen};
+use crate::screens::{BrowseScreen, CertificateScreen, ConnectScreen};
 
 pub struct App {
-    client_manager: Arc<RwLock<OpcUaClientManager>>,
+    client_manager: Arc<RwLock<OpcUaClientManager>>, // Connection being set up on the connect screen
     should_quit: bool,
 
     // App state
@@ -33,7 +35,9 @@ pub struct App {
 
     // Screens
     connect_screen: ConnectScreen,
-    browse_screen: Option<BrowseScreen>,
+    tabs: Vec<SessionTab>, // One per open connection
+    active_tab: usize,
+    certificate_screen: Option<CertificateScreen>, // Shown over the other screens while open
 
     // Mouse handling
     dialog_area: Option<Rect>,
@@ -43,17 +47,56 @@ pub struct App {
 #[derive(Debug, Clone)]
 enum AppState {
     Connecting,
-    Connected(String), // Store server URL
+    Connected, // Showing the active tab
+}
+
+/// An open connection with its own tree, attributes, search state and dialogs
+struct SessionTab {
+    server_url: String,
+    client_manager: Arc<RwLock<OpcUaClientManager>>,
+    browse_screen: BrowseScreen,
+}
+
+impl SessionTab {
+    fn new(server_url: String, client_manager: Arc<RwLock<OpcUaClientManager>>) -> Self {
+        Self {
+            browse_screen: BrowseScreen::new(server_url.clone(), client_manager.clone()),
+            server_url,
+            client_manager,
+        }
+    }
+
+    fn title(&self, index: usize) -> String {
+        const MAX_LABEL: usize = 30;
+        let label = self
+            .server_url
+            .strip_prefix("opc.tcp://")
+            .unwrap_or(&self.server_url);
+        let label = if label.chars().count() > MAX_LABEL {
+            let truncated: String = label.chars().take(MAX_LABEL - 1).collect();
+            format!("{truncated}…")
+        } else {
+            label.to_string()
+        };
+        format!(" {}: {label} ", index + 1)
+    }
 }
 
 impl App {
-    pub fn new(client_manager: Arc<RwLock<OpcUaClientManager>>) -> Self {
+    pub fn new(
+        client_manager: Arc<RwLock<OpcUaClientManager>>,
+        reverse_connect: Option<ReverseConnectListener>,
+    ) -> Self {
+        let mut connect_screen = ConnectScreen::new();
+        connect_screen.reverse_connect = reverse_connect;
         Self {
             client_manager,
             should_quit: false,
             app_state: AppState::Connecting,
-            connect_screen: ConnectScreen::new(),
-            browse_screen: None,
+            connect_screen,
+            tabs: Vec::new(),
+            active_tab: 0,
+            certificate_screen: None,
             dialog_area: None,
             progress_dialog_area: None,
         }
@@ -63,11 +106,13 @@ impl App {
         server_url: String,
     ) -> Self {
         Self {
-            client_manager: client_manager.clone(),
+            client_manager: Arc::new(RwLock::new(OpcUaClientManager::new())),
             should_quit: false,
-            app_state: AppState::Connected(server_url.clone()),
+            app_state: AppState::Connected,
             connect_screen: ConnectScreen::new(),
-            browse_screen: Some(BrowseScreen::new(server_url, client_manager)),
+            tabs: vec![SessionTab::new(server_url, client_manager)],
+            active_tab: 0,
+            certificate_screen: None,
             dialog_area: None,
             progress_dialog_area: None,
         }
@@ -110,11 +155,9 @@ impl App {
 
             if event::poll(timeout)? {
                 match event::read()? {
-                    Event::Key(key) => {
-                        // Only process key press events, not key release
-                        if key.kind == KeyEventKind::Press {
-                            self.handle_key_input(key.code, key.modifiers).await?;
-                        }
+                    // Only process key press events, not key release
+                    Event::Key(key) if key.kind == KeyEventKind::Press => {
+                        self.handle_key_input(key.code, key.modifiers).await?;
                     }
                     Event::Mouse(mouse) => {
                         self.handle_mouse_input(mouse, terminal).await?;
@@ -135,6 +178,18 @@ impl App {
         Ok(())
     }
     async fn handle_key_input(&mut self, key: KeyCode, modifiers: KeyModifiers) -> Result<()> {
+        // F2 toggles the certificate screen from anywhere
+        if let Some(certificate_screen) = &mut self.certificate_screen {
+            if certificate_screen.handle_input(key) {
+                self.certificate_screen = None;
+            }
+            return Ok(());
+        }
+        if key == KeyCode::F(2) {
+            self.certificate_screen = Some(CertificateScreen::new());
+            return Ok(());
+        }
+
         match &self.app_state {
             AppState::Connecting => {
                 // Handle connect screen input
@@ -144,19 +199,29 @@ impl App {
                     self.handle_connection_result(connection_result).await;
                 }
             }
-            AppState::Connected(_server_url) => {
-                // Handle browse screen input
-                if let Some(browse_screen) = &mut self.browse_screen {
-                    if let Some(connection_result) =
-                        browse_screen.handle_input(key, modifiers).await?
-                    {
-                        if connection_result == ConnectionStatus::Disconnected {
-                            // User wants to quit
-                            self.should_quit = true;
+            AppState::Connected => match key {
+                KeyCode::Char('t') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.open_connect_screen().await;
+                }
+                KeyCode::Left if modifiers.contains(KeyModifiers::ALT) => {
+                    self.switch_tab(self.active_tab + self.tabs.len() - 1);
+                }
+                KeyCode::Right if modifiers.contains(KeyModifiers::ALT) => {
+                    self.switch_tab(self.active_tab + 1);
+                }
+                _ => {
+                    // Handle browse screen input
+                    if let Some(tab) = self.tabs.get_mut(self.active_tab) {
+                        if let Some(connection_result) =
+                            tab.browse_screen.handle_input(key, modifiers).await?
+                        {
+                            if connection_result == ConnectionStatus::Disconnected {
+                                self.close_active_tab().await;
+                            }
                         }
                     }
                 }
-            }
+            },
         }
         Ok(())
     }
@@ -170,6 +235,9 @@ impl App {
         if let MouseEventKind::Moved = mouse.kind {
             return Ok(());
         }
+        if self.certificate_screen.is_some() {
+            return Ok(());
+        }
 
         match &self.app_state {
             AppState::Connecting => {
@@ -206,16 +274,26 @@ impl App {
                     _ => {}
                 }
             }
-            AppState::Connected(_) => {
+            AppState::Connected => {
+                let size = terminal.size()?;
+                let (tab_bar_area, full_area) = Self::split_tab_bar(Rect {
+                    x: 0,
+                    y: 0,
+                    width: size.width,
+                    height: size.height,
+                });
+                if mouse.row == tab_bar_area.y {
+                    if let MouseEventKind::Down(MouseButton::Left) = mouse.kind {
+                        if let Some(index) = self.tab_at(mouse.column) {
+                            self.switch_tab(index);
+                        }
+                    }
+                    return Ok(());
+                }
+
                 // Handle browse screen mouse events
-                if let Some(browse_screen) = &mut self.browse_screen {
-                    let size = terminal.size()?;
-                    let full_area = ratatui::layout::Rect {
-                        x: 0,
-                        y: 0,
-                        width: size.width,
-                        height: size.height,
-                    };
+                if let Some(tab) = self.tabs.get_mut(self.active_tab) {
+                    let browse_screen = &mut tab.browse_screen;
 
                     // Calculate the tree area (70% of the main content area)
                     let main_chunks = Layout::default()
@@ -226,13 +304,22 @@ impl App {
                         ])
                         .split(full_area);
 
+                    // The event panel, when shown, takes the bottom of the content area
+                    let body_chunks = Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints([
+                            Constraint::Min(0),
+                            Constraint::Length(browse_screen.events_panel_height()),
+                        ])
+                        .split(main_chunks[0]);
+
                     let content_chunks = Layout::default()
                         .direction(Direction::Horizontal)
                         .constraints([
                             Constraint::Percentage(50), // Tree view
                             Constraint::Percentage(50), // Attributes panel
                         ])
-                        .split(main_chunks[0]);
+                        .split(body_chunks[0]);
 
                     // Tree area with borders - inner area for actual content
                     let tree_area = Rect {
@@ -251,7 +338,7 @@ impl App {
                         .await?
                     {
                         if connection_result == ConnectionStatus::Disconnected {
-                            self.should_quit = true;
+                            self.close_active_tab().await;
                         }
                     }
                 }
@@ -262,44 +349,128 @@ impl App {
     }
 
     async fn handle_tick(&mut self) {
-        match &self.app_state {
-            AppState::Connecting => {
-                // Handle pending operations for connect screen
-                match self.connect_screen.handle_pending_operations().await {
-                    Ok(Some(connection_result)) => {
-                        // Handle connection result using helper
-                        self.handle_connection_result(connection_result).await;
-                    }
-                    Ok(None) => {
-                        // No change, continue as normal
-                    }
-                    Err(e) => {
-                        log::error!("Error handling connect screen operations: {e}");
-                    }
+        if let AppState::Connecting = self.app_state {
+            // Handle pending operations for connect screen
+            match self.connect_screen.handle_pending_operations().await {
+                Ok(Some(connection_result)) => {
+                    // Handle connection result using helper
+                    self.handle_connection_result(connection_result).await;
                 }
-            }
-            AppState::Connected(_) => {
-                // Process search messages from background tasks
-                if let Some(browse_screen) = &mut self.browse_screen {
-                    browse_screen.process_search_messages().await;
+                Ok(None) => {
+                    // No change, continue as normal
                 }
-
-                // Update connection status from client manager
-                if let Ok(client) = self.client_manager.try_read() {
-                    let status = client.get_connection_status();
-                    if status == ConnectionStatus::Disconnected {
-                        // Connection was lost, go back to connect screen
-                        log::warn!("Lost connection to server, returning to connect screen");
-                        self.app_state = AppState::Connecting;
-                        self.browse_screen = None;
-                        self.connect_screen.async_reset().await;
-                    }
+                Err(e) => {
+                    log::error!("Error handling connect screen operations: {e}");
                 }
+            }
+        }
 
-                // Note: Background search processing removed - using synchronous depth-first search instead
+        // Tabs in the background keep their subscriptions, searches and reconnects running
+        let mut lost_tabs = Vec::new();
+        for (index, tab) in self.tabs.iter_mut().enumerate() {
+            // Process search messages from background tasks
+            let browse_screen = &mut tab.browse_screen;
+            if index == self.active_tab && matches!(self.app_state, AppState::Connected) {
+                browse_screen.check_visible_children().await;
             }
+            browse_screen.process_search_messages().await;
+            browse_screen.release_stale_continuation_points().await;
+            browse_screen.process_watch_notifications();
+            browse_screen.process_event_notifications();
+            browse_screen.process_export_messages();
+            browse_screen.process_diff_messages();
+
+            // Apply reconnect progress; once the session is back, restore what was shown
+            let recovered = match tab.client_manager.try_write() {
+                Ok(mut client) => client.process_session_events(),
+                Err(_) => false,
+            };
+            if recovered {
+                browse_screen.restore_after_reconnect().await;
+            }
+
+            // Update connection status from client manager
+            if let Ok(client) = tab.client_manager.try_read() {
+                let status = client.get_connection_status();
+                browse_screen.connection_status = status.clone();
+                if status == ConnectionStatus::Disconnected {
+                    log::warn!("Lost connection to {}, closing its tab", tab.server_url);
+                    lost_tabs.push(index);
+                }
+            }
+        }
+        for index in lost_tabs.into_iter().rev() {
+            self.tabs.remove(index);
+        }
+        self.active_tab = self.active_tab.min(self.tabs.len().saturating_sub(1));
+
+        if self.tabs.is_empty() && matches!(self.app_state, AppState::Connected) {
+            // The last connection was lost, go back to connect screen
+            log::warn!("No open connections, returning to connect screen");
+            self.app_state = AppState::Connecting;
+            self.connect_screen.async_reset().await;
+        }
+    }
+
+    /// Show the connect screen for another connection; the open tabs stay connected
+    async fn open_connect_screen(&mut self) {
+        log::info!("Opening a new connection, Esc on the first step returns to the open tabs");
+        self.client_manager = Arc::new(RwLock::new(OpcUaClientManager::new()));
+        self.connect_screen.async_reset().await;
+        self.app_state = AppState::Connecting;
+    }
+
+    /// Make the tab at `index` (wrapping around) the active one
+    fn switch_tab(&mut self, index: usize) {
+        if self.tabs.is_empty() {
+            return;
+        }
+        self.active_tab = index % self.tabs.len();
+        // Dialog areas belong to the previous tab until the next render
+        self.dialog_area = None;
+        self.progress_dialog_area = None;
+    }
+
+    /// Disconnect the active tab; the application quits when it was the last one
+    async fn close_active_tab(&mut self) {
+        if self.active_tab >= self.tabs.len() {
+            return;
+        }
+        let tab = self.tabs.remove(self.active_tab);
+        log::info!("Disconnecting from {}", tab.server_url);
+        let client_manager = tab.client_manager.clone();
+        let disconnect_result = tokio::task::spawn_blocking(move || {
+            client_manager.blocking_write().disconnect();
+        })
+        .await;
+        if let Err(e) = disconnect_result {
+            log::warn!("Error during session cleanup: {e}");
+        }
+
+        if self.tabs.is_empty() {
+            self.should_quit = true;
+        } else {
+            self.switch_tab(self.active_tab.min(self.tabs.len() - 1));
         }
     }
+
+    /// Open a tab for the connection set up on the connect screen and show it
+    async fn open_tab(&mut self, server_url: String) {
+        let client_manager = std::mem::replace(
+            &mut self.client_manager,
+            Arc::new(RwLock::new(OpcUaClientManager::new())),
+        );
+        let mut tab = SessionTab::new(server_url, client_manager);
+
+        // Load real tree data asynchronously
+        if let Err(e) = tab.browse_screen.load_real_tree().await {
+            log::error!("Failed to load real tree data: {e}");
+        }
+
+        self.tabs.push(tab);
+        self.switch_tab(self.tabs.len() - 1);
+        self.app_state = AppState::Connected;
+    }
     /// Helper method to handle connection results consistently
     async fn handle_connection_result(&mut self, connection_result: ConnectionStatus) {
         match connection_result {
@@ -318,21 +489,17 @@ impl App {
                     // Transfer the established connection to client manager
                     {
                         let mut client_guard = self.client_manager.write().await;
-                        client_guard.set_connection(client, session, server_url.clone());
+                        client_guard.set_connection(
+                            client,
+                            session,
+                            server_url.clone(),
+                            self.connect_screen.take_connection_target(),
+                        );
                         client_guard.set_connection_status(ConnectionStatus::Connected);
                     }
 
                     // Transition to browse screen
-                    self.app_state = AppState::Connected(server_url.clone());
-                    let mut browse_screen =
-                        BrowseScreen::new(server_url.clone(), self.client_manager.clone());
-
-                    // Load real tree data asynchronously
-                    if let Err(e) = browse_screen.load_real_tree().await {
-                        log::error!("Failed to load real tree data: {e}");
-                    }
-
-                    self.browse_screen = Some(browse_screen);
+                    self.open_tab(server_url).await;
                 } else {
                     log::error!("ConnectScreen did not provide client and session");
                     // Set client manager to error state
@@ -347,15 +514,14 @@ impl App {
                 // This shouldn't happen anymore since perform_connection returns Connecting
                 log::warn!("Received Connected status directly - this should not happen");
                 let server_url = self.connect_screen.get_server_url();
-                self.app_state = AppState::Connected(server_url.clone());
-                let mut browse_screen = BrowseScreen::new(server_url, self.client_manager.clone());
-
-                // Load real tree data asynchronously
-                if let Err(e) = browse_screen.load_real_tree().await {
-                    log::error!("Failed to load real tree data: {e}");
-                }
-
-                self.browse_screen = Some(browse_screen);
+                self.open_tab(server_url).await;
+            }
+            ConnectionStatus::Reconnecting(_) => {
+                // Only reported by the session monitor of an established connection
+            }
+            ConnectionStatus::Disconnected if !self.tabs.is_empty() => {
+                // User cancelled another connection, go back to the open tabs
+                self.app_state = AppState::Connected;
             }
             ConnectionStatus::Disconnected => {
                 // User cancelled connection or wants to quit
@@ -391,17 +557,13 @@ impl App {
                 self.connect_screen.render_help_line(f, chunks[1]);
                 self.render_connection_status_bar(f, chunks[2]);
             }
-            AppState::Connected(_) => {
-                // Show browse screen with help line
-                let chunks = Layout::default()
-                    .direction(Direction::Vertical)
-                    .constraints([
-                        Constraint::Min(0), // Browse screen
-                    ])
-                    .split(size);
-                if let Some(browse_screen) = &mut self.browse_screen {
+            AppState::Connected => {
+                // Show the tab bar above the active tab's browse screen
+                let (tab_bar_area, browse_area) = Self::split_tab_bar(size);
+                self.render_tab_bar(f, tab_bar_area);
+                if let Some(tab) = self.tabs.get_mut(self.active_tab) {
                     let (dialog_area, progress_dialog_area, _log_viewer_area) =
-                        browse_screen.render(f, chunks[0]);
+                        tab.browse_screen.render(f, browse_area);
                     // Store dialog areas for mouse handling
                     self.dialog_area = dialog_area;
                     self.progress_dialog_area = progress_dialog_area;
@@ -409,14 +571,81 @@ impl App {
                 }
             }
         }
+
+        if let Some(certificate_screen) = &self.certificate_screen {
+            certificate_screen.render(f, size);
+        }
+    }
+
+    /// Split off the top row for the tab bar
+    fn split_tab_bar(area: Rect) -> (Rect, Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Tab bar
+                Constraint::Min(0),    // Browse screen
+            ])
+            .split(area);
+        (chunks[0], chunks[1])
+    }
+
+    fn render_tab_bar(&self, f: &mut Frame, area: Rect) {
+        let mut spans: Vec<Span> = self
+            .tabs
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| {
+                let style = if index == self.active_tab {
+                    Style::default().fg(Color::Black).bg(Color::Cyan)
+                } else {
+                    Style::default().fg(Color::White).bg(Color::DarkGray)
+                };
+                Span::styled(tab.title(index), style)
+            })
+            .collect();
+        spans.push(Span::styled(
+            " Ctrl+T - New connection | Alt+←/→ - Switch tab",
+            Style::default().fg(Color::DarkGray),
+        ));
+        f.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    /// Index of the tab whose title covers `column` in the tab bar
+    fn tab_at(&self, column: u16) -> Option<usize> {
+        let mut start = 0u16;
+        for (index, tab) in self.tabs.iter().enumerate() {
+            let width = tab.title(index).chars().count() as u16;
+            if column >= start && column < start + width {
+                return Some(index);
+            }
+            start += width;
+        }
+        None
     }
 
     fn render_connection_status_bar(&mut self, f: &mut Frame, area: Rect) {
         let status_text = match self.connect_screen.step {
-            ConnectDialogStep::ServerUrl => {
-                // Show placeholder on first step
-                "Enter valid OPC UA server URL".to_string()
+            ConnectDialogStep::ProfileSelection => {
+                "Select a saved connection profile or start a new connection".to_string()
             }
+            ConnectDialogStep::ServerUrl => match &self.connect_screen.reverse_connect {
+                Some(listener) => format!(
+                    "Enter valid OPC UA server URL | Reverse Connect servers: {} (Ctrl+R)",
+                    listener.servers().len()
+                ),
+                // Show placeholder on first step
+                None => "Enter valid OPC UA server URL".to_string(),
+            },
+            ConnectDialogStep::ServerSelection => match &self.connect_screen.reverse_connect {
+                Some(listener) if self.connect_screen.listing_reverse_servers => format!(
+                    "Servers connected to the Reverse Connect listener on {}",
+                    listener.address()
+                ),
+                _ => format!(
+                    "Servers registered with {}",
+                    self.connect_screen.discovery_server_url
+                ),
+            },
             ConnectDialogStep::EndpointSelection => {
                 // Show the server URL that will be used
                 let url = if self.connect_screen.use_original_url {
@@ -508,9 +737,9 @@ impl App {
     }
 
     pub async fn initialize_browse_screen(&mut self) -> Result<()> {
-        if let Some(browse_screen) = &mut self.browse_screen {
+        if let Some(tab) = self.tabs.get_mut(self.active_tab) {
             // Load the tree data automatically for CLI connections
-            if let Err(e) = browse_screen.load_real_tree().await {
+            if let Err(e) = tab.browse_screen.load_real_tree().await {
                 log::error!("Failed to load real tree data: {e}");
             }
         }
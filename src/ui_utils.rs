@@ -19,7 +19,7 @@ impl LayoutUtils {
             .to_vec()
     }
     /// Create a standard paragraph with title styling
-    pub fn create_title_paragraph(title_text: &str) -> Paragraph {
+    pub fn create_title_paragraph(title_text: &str) -> Paragraph<'_> {
         Paragraph::new(title_text)
             .style(Style::default().fg(Color::White).bg(Color::Blue))
             .block(Block::default().borders(Borders::ALL))
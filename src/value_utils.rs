@@ -0,0 +1,513 @@
+use anyhow::{anyhow, Result};
+use opcua::types::{
+    Array, ByteString, DateTime, Guid, Identifier, LocalizedText, NodeId, QualifiedName,
+    StatusCode, UAString, Variant, VariantTypeId,
+};
+use std::str::FromStr;
+
+/// Utility functions for converting between user input and OPC UA values
+pub struct ValueUtils;
+
+impl ValueUtils {
+    /// Resolve the concrete scalar type a value must be encoded as.
+    /// Abstract or custom DataTypes fall back to the type of the current value.
+    pub fn resolve_scalar_type(
+        data_type: &NodeId,
+        current_value: Option<&Variant>,
+    ) -> Option<VariantTypeId> {
+        let from_data_type = match (&data_type.identifier, data_type.namespace) {
+            (Identifier::Numeric(id), 0) => match *id {
+                1 => Some(VariantTypeId::Boolean),
+                2 => Some(VariantTypeId::SByte),
+                3 => Some(VariantTypeId::Byte),
+                4 => Some(VariantTypeId::Int16),
+                5 => Some(VariantTypeId::UInt16),
+                6 => Some(VariantTypeId::Int32),
+                7 => Some(VariantTypeId::UInt32),
+                8 => Some(VariantTypeId::Int64),
+                9 => Some(VariantTypeId::UInt64),
+                10 => Some(VariantTypeId::Float),
+                11 => Some(VariantTypeId::Double),
+                12 => Some(VariantTypeId::String),
+                13 => Some(VariantTypeId::DateTime),
+                14 => Some(VariantTypeId::Guid),
+                15 => Some(VariantTypeId::ByteString),
+                17 => Some(VariantTypeId::NodeId),
+                19 => Some(VariantTypeId::StatusCode),
+                20 => Some(VariantTypeId::QualifiedName),
+                21 => Some(VariantTypeId::LocalizedText),
+                29 => Some(VariantTypeId::Int32),     // Enumeration
+                290 => Some(VariantTypeId::Double),   // Duration
+                294 => Some(VariantTypeId::DateTime), // UtcTime
+                295 => Some(VariantTypeId::String),   // LocaleId
+                _ => None,
+            },
+            _ => None,
+        };
+
+        from_data_type.or_else(|| match current_value {
+            Some(Variant::Array(array)) => Some(array.value_type),
+            Some(Variant::Empty) | None => None,
+            Some(value) => Some(value.type_id()),
+        })
+    }
+
+    /// Whether a ValueRank (and the typed text) means the value is an array.
+    /// ValueRank -1 is scalar, 0 and above are arrays, -2/-3 accept both so the
+    /// input decides: text wrapped in brackets is treated as an array.
+    pub fn is_array_input(value_rank: i32, input: &str) -> bool {
+        match value_rank {
+            -1 => false,
+            rank if rank >= 0 => true,
+            _ => {
+                let trimmed = input.trim();
+                trimmed.starts_with('[') && trimmed.ends_with(']')
+            }
+        }
+    }
+
+    /// Parse typed text into a Variant matching the node's DataType and ValueRank.
+    /// Arrays are entered as comma separated lists, optionally wrapped in brackets;
+    /// elements containing a comma are wrapped in double or single quotes.
+    pub fn parse_variant(
+        input: &str,
+        value_type: VariantTypeId,
+        value_rank: i32,
+    ) -> Result<Variant> {
+        Self::check_value_rank(value_rank)?;
+        if Self::is_array_input(value_rank, input) {
+            Self::parse_array(input, Some(value_type), |element| {
+                Self::parse_scalar(Self::unquote(element), value_type)
+            })
+        } else {
+            Self::parse_scalar(input.trim(), value_type)
+        }
+    }
+
+    /// Like `parse_variant` for DataTypes without a concrete encoding, such as BaseDataType
+    /// or Number: every value takes the type its literal suggests
+    pub fn infer_variant(input: &str, value_rank: i32) -> Result<Variant> {
+        Self::check_value_rank(value_rank)?;
+        if Self::is_array_input(value_rank, input) {
+            Self::parse_array(input, None, |element| Ok(Self::infer_scalar(element)))
+        } else {
+            Ok(Self::infer_scalar(input.trim()))
+        }
+    }
+
+    /// Parse the elements of an array input. Without a `value_type` the array takes the type
+    /// of its first element.
+    fn parse_array(
+        input: &str,
+        value_type: Option<VariantTypeId>,
+        parse_element: impl Fn(&str) -> Result<Variant>,
+    ) -> Result<Variant> {
+        let trimmed = input.trim();
+        let inner = trimmed
+            .strip_prefix('[')
+            .and_then(|s| s.strip_suffix(']'))
+            .unwrap_or(trimmed);
+
+        let values = if inner.trim().is_empty() {
+            Vec::new()
+        } else {
+            Self::split_elements(inner)
+                .into_iter()
+                .enumerate()
+                .map(|(index, element)| {
+                    parse_element(element.trim()).map_err(|e| anyhow!("Element [{}]: {}", index, e))
+                })
+                .collect::<Result<Vec<Variant>>>()?
+        };
+
+        let value_type = value_type
+            .or_else(|| values.first().map(Variant::type_id))
+            .unwrap_or(VariantTypeId::String);
+        if let Some(index) = values.iter().position(|v| v.type_id() != value_type) {
+            return Err(anyhow!(
+                "Element [{}] is not a {:?} like the elements before it",
+                index,
+                value_type
+            ));
+        }
+        let array =
+            Array::new(value_type, values).map_err(|e| anyhow!("Invalid array value: {}", e))?;
+        Ok(Variant::Array(Box::new(array)))
+    }
+
+    /// Matrices need ArrayDimensions, which a comma separated list can't express
+    fn check_value_rank(value_rank: i32) -> Result<()> {
+        if value_rank > 1 {
+            return Err(anyhow!(
+                "Values with ValueRank {} (multi-dimensional arrays) are not supported",
+                value_rank
+            ));
+        }
+        Ok(())
+    }
+
+    /// Split an array input on the commas outside of quotes. An element is quoted when it
+    /// starts with `"` or `'`, and the quotes are kept for the element parser.
+    fn split_elements(input: &str) -> Vec<&str> {
+        let mut elements = Vec::new();
+        let mut start = 0;
+        let mut quote = None;
+        for (index, c) in input.char_indices() {
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None if c == ',' => {
+                    elements.push(input[start..index].trim());
+                    start = index + 1;
+                }
+                None if (c == '"' || c == '\'') && input[start..index].trim().is_empty() => {
+                    quote = Some(c)
+                }
+                None => {}
+            }
+        }
+        elements.push(input[start..].trim());
+        elements
+    }
+
+    /// Strip the quotes around an array element
+    fn unquote(element: &str) -> &str {
+        ['"', '\'']
+            .iter()
+            .find_map(|&q| element.strip_prefix(q)?.strip_suffix(q))
+            .unwrap_or(element)
+    }
+
+    /// Infer a value from its literal when there is no DataType to parse it by: quoted text,
+    /// true/false, an integer, a floating-point number or a NodeId, and plain text otherwise
+    pub fn infer_scalar(literal: &str) -> Variant {
+        let quoted = (literal.starts_with('\'') && literal.ends_with('\''))
+            || (literal.starts_with('"') && literal.ends_with('"'));
+        if quoted && literal.len() >= 2 {
+            return Variant::from(&literal[1..literal.len() - 1]);
+        }
+
+        match literal {
+            "true" => return Variant::Boolean(true),
+            "false" => return Variant::Boolean(false),
+            _ => {}
+        }
+        if let Ok(value) = literal.parse::<i32>() {
+            return Variant::Int32(value);
+        }
+        if let Ok(value) = literal.parse::<i64>() {
+            return Variant::Int64(value);
+        }
+        if let Ok(value) = literal.parse::<f64>() {
+            return Variant::Double(value);
+        }
+        // Event filters compare EventTypes with NodeIds such as i=2915
+        if literal.contains('=') {
+            if let Ok(node_id) = NodeId::from_str(literal) {
+                return Variant::from(node_id);
+            }
+        }
+        Variant::from(literal)
+    }
+
+    /// Parse a single scalar value of the given type
+    pub fn parse_scalar(input: &str, value_type: VariantTypeId) -> Result<Variant> {
+        let variant = match value_type {
+            VariantTypeId::Boolean => match input.to_lowercase().as_str() {
+                "true" | "1" | "on" | "yes" => Variant::Boolean(true),
+                "false" | "0" | "off" | "no" => Variant::Boolean(false),
+                _ => return Err(anyhow!("'{}' is not a Boolean (true/false)", input)),
+            },
+            VariantTypeId::SByte => Variant::SByte(Self::parse_number(input, "SByte")?),
+            VariantTypeId::Byte => Variant::Byte(Self::parse_number(input, "Byte")?),
+            VariantTypeId::Int16 => Variant::Int16(Self::parse_number(input, "Int16")?),
+            VariantTypeId::UInt16 => Variant::UInt16(Self::parse_number(input, "UInt16")?),
+            VariantTypeId::Int32 => Variant::Int32(Self::parse_number(input, "Int32")?),
+            VariantTypeId::UInt32 => Variant::UInt32(Self::parse_number(input, "UInt32")?),
+            VariantTypeId::Int64 => Variant::Int64(Self::parse_number(input, "Int64")?),
+            VariantTypeId::UInt64 => Variant::UInt64(Self::parse_number(input, "UInt64")?),
+            VariantTypeId::Float => Variant::Float(Self::parse_number(input, "Float")?),
+            VariantTypeId::Double => Variant::Double(Self::parse_number(input, "Double")?),
+            VariantTypeId::String => Variant::String(UAString::from(input)),
+            VariantTypeId::DateTime => Variant::DateTime(Box::new(
+                DateTime::from_str(input)
+                    .map_err(|_| anyhow!("'{}' is not an RFC 3339 date/time", input))?,
+            )),
+            VariantTypeId::Guid => Variant::Guid(Box::new(
+                Guid::from_str(input).map_err(|_| anyhow!("'{}' is not a Guid", input))?,
+            )),
+            VariantTypeId::ByteString => Variant::ByteString(
+                ByteString::from_base64(input)
+                    .ok_or_else(|| anyhow!("ByteString values must be base64 encoded"))?,
+            ),
+            VariantTypeId::NodeId => Variant::NodeId(Box::new(
+                NodeId::from_str(input).map_err(|_| anyhow!("'{}' is not a NodeId", input))?,
+            )),
+            VariantTypeId::QualifiedName => {
+                // Accept "ns:name" or a plain name in namespace 0
+                let (namespace, name) = match input.split_once(':') {
+                    Some((ns, name)) if ns.parse::<u16>().is_ok() => {
+                        (ns.parse::<u16>().unwrap_or(0), name)
+                    }
+                    _ => (0, input),
+                };
+                Variant::QualifiedName(Box::new(QualifiedName::new(namespace, name)))
+            }
+            VariantTypeId::LocalizedText => {
+                Variant::LocalizedText(Box::new(LocalizedText::new("", input)))
+            }
+            VariantTypeId::StatusCode => Variant::StatusCode(Self::parse_status_code(input)?),
+            other => return Err(anyhow!("Writing {:?} values is not supported", other)),
+        };
+        Ok(variant)
+    }
+
+    /// Accept a StatusCode name such as "BadOutOfRange" or its code, e.g. 0x803C0000
+    fn parse_status_code(input: &str) -> Result<StatusCode> {
+        let code = match input
+            .strip_prefix("0x")
+            .or_else(|| input.strip_prefix("0X"))
+        {
+            Some(hex) => u32::from_str_radix(hex, 16).ok(),
+            None => input.parse::<u32>().ok(),
+        };
+        match code {
+            Some(code) => StatusCode::from_u32(code),
+            None => StatusCode::from_str(input).ok(),
+        }
+        .ok_or_else(|| anyhow!("'{}' is not a StatusCode name or code", input))
+    }
+
+    /// Format a value as editable text, the inverse of `parse_variant`
+    pub fn format_for_edit(value: &Variant) -> String {
+        match value {
+            Variant::Array(array) => {
+                let elements: Vec<String> = array.values.iter().map(Self::format_element).collect();
+                format!("[{}]", elements.join(", "))
+            }
+            Variant::String(s) => s.value().clone().unwrap_or_default(),
+            Variant::LocalizedText(lt) => lt.text.value().clone().unwrap_or_default(),
+            Variant::QualifiedName(qn) => {
+                let name = qn.name.value().clone().unwrap_or_default();
+                if qn.namespace_index == 0 {
+                    name
+                } else {
+                    format!("{}:{}", qn.namespace_index, name)
+                }
+            }
+            Variant::ByteString(bs) => bs.as_base64(),
+            // Codes with flag bits have no name that parse_variant would accept
+            Variant::StatusCode(code) if !code.bitflags().is_empty() => {
+                format!("0x{:08X}", code.bits())
+            }
+            Variant::Empty => String::new(),
+            other => other.to_string(),
+        }
+    }
+
+    /// Format an array element, quoting text that parse_variant would otherwise split or unquote
+    fn format_element(value: &Variant) -> String {
+        let text = Self::format_for_edit(value);
+        let is_text = matches!(value, Variant::String(_) | Variant::LocalizedText(_));
+        if !is_text || !(text.contains(',') || text.starts_with(['"', '\''])) {
+            return text;
+        }
+        if text.contains('"') {
+            format!("'{text}'")
+        } else {
+            format!("\"{text}\"")
+        }
+    }
+
+    /// Map numbers, booleans and arrays to their JSON counterparts and everything else to text
+    pub fn to_json(value: &Variant) -> serde_json::Value {
+        match value {
+            Variant::Empty => serde_json::Value::Null,
+            Variant::Boolean(v) => serde_json::json!(v),
+            Variant::SByte(v) => serde_json::json!(v),
+            Variant::Byte(v) => serde_json::json!(v),
+            Variant::Int16(v) => serde_json::json!(v),
+            Variant::UInt16(v) => serde_json::json!(v),
+            Variant::Int32(v) => serde_json::json!(v),
+            Variant::UInt32(v) => serde_json::json!(v),
+            Variant::Int64(v) => serde_json::json!(v),
+            Variant::UInt64(v) => serde_json::json!(v),
+            Variant::Float(v) => serde_json::json!(v),
+            Variant::Double(v) => serde_json::json!(v),
+            Variant::Array(array) => {
+                serde_json::Value::Array(array.values.iter().map(Self::to_json).collect())
+            }
+            other => serde_json::Value::String(Self::format_for_edit(other)),
+        }
+    }
+
+    fn parse_number<T: FromStr>(input: &str, type_name: &str) -> Result<T> {
+        input
+            .parse::<T>()
+            .map_err(|_| anyhow!("'{}' is not a valid {}", input, type_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literals_take_the_type_they_suggest() {
+        assert_eq!(ValueUtils::infer_scalar("true"), Variant::Boolean(true));
+        assert_eq!(ValueUtils::infer_scalar("42"), Variant::Int32(42));
+        assert_eq!(
+            ValueUtils::infer_scalar("5000000000"),
+            Variant::Int64(5_000_000_000)
+        );
+        assert_eq!(ValueUtils::infer_scalar("2.5"), Variant::Double(2.5));
+        assert_eq!(
+            ValueUtils::infer_scalar("ns=2;s=Pump"),
+            Variant::from(NodeId::new(2, "Pump"))
+        );
+        assert_eq!(ValueUtils::infer_scalar("'42'"), Variant::from("42"));
+        assert_eq!(ValueUtils::infer_scalar("Pump"), Variant::from("Pump"));
+    }
+
+    #[test]
+    fn inferred_arrays_share_one_element_type() {
+        let Variant::Array(array) = ValueUtils::infer_variant("[1, 2, 3]", 1).unwrap() else {
+            panic!("not an array");
+        };
+        assert_eq!(array.value_type, VariantTypeId::Int32);
+        assert_eq!(array.values.len(), 3);
+
+        assert!(ValueUtils::infer_variant("[1, 2.5]", 1).is_err());
+        // ValueRank -2 only makes bracketed input an array
+        assert_eq!(
+            ValueUtils::infer_variant("7", -2).unwrap(),
+            Variant::Int32(7)
+        );
+    }
+
+    fn array_values(variant: Variant) -> Vec<Variant> {
+        match variant {
+            Variant::Array(array) => array.values,
+            other => panic!("not an array: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn scalars_parse_by_their_data_type() {
+        let parse = |input, value_type| ValueUtils::parse_scalar(input, value_type).unwrap();
+        assert_eq!(parse("yes", VariantTypeId::Boolean), Variant::Boolean(true));
+        assert_eq!(parse("0", VariantTypeId::Boolean), Variant::Boolean(false));
+        assert_eq!(parse("-12", VariantTypeId::SByte), Variant::SByte(-12));
+        assert_eq!(
+            parse("65535", VariantTypeId::UInt16),
+            Variant::UInt16(65535)
+        );
+        assert_eq!(parse("1.5", VariantTypeId::Float), Variant::Float(1.5));
+        assert_eq!(parse("a, b", VariantTypeId::String), Variant::from("a, b"));
+        assert_eq!(
+            parse("2024-01-02T03:04:05Z", VariantTypeId::DateTime),
+            Variant::from(DateTime::from_str("2024-01-02T03:04:05Z").unwrap())
+        );
+        assert_eq!(
+            parse("72962b91-fa75-4ae6-8d28-b404dc7daf63", VariantTypeId::Guid),
+            Variant::from(Guid::from_str("72962b91-fa75-4ae6-8d28-b404dc7daf63").unwrap())
+        );
+        assert_eq!(
+            parse("AQID", VariantTypeId::ByteString),
+            Variant::from(ByteString::from(vec![1u8, 2, 3]))
+        );
+        assert_eq!(
+            parse("ns=2;i=7", VariantTypeId::NodeId),
+            Variant::from(NodeId::new(2, 7u32))
+        );
+        assert_eq!(
+            parse("3:Pump", VariantTypeId::QualifiedName),
+            Variant::from(QualifiedName::new(3, "Pump"))
+        );
+        assert_eq!(
+            parse("Pump:A", VariantTypeId::QualifiedName),
+            Variant::from(QualifiedName::new(0, "Pump:A"))
+        );
+        assert_eq!(
+            parse("Pump", VariantTypeId::LocalizedText),
+            Variant::from(LocalizedText::new("", "Pump"))
+        );
+        assert_eq!(
+            parse("BadOutOfRange", VariantTypeId::StatusCode),
+            Variant::StatusCode(StatusCode::BadOutOfRange)
+        );
+        assert_eq!(
+            parse("0x803C0000", VariantTypeId::StatusCode),
+            Variant::StatusCode(StatusCode::BadOutOfRange)
+        );
+    }
+
+    #[test]
+    fn invalid_scalars_are_rejected() {
+        assert!(ValueUtils::parse_scalar("maybe", VariantTypeId::Boolean).is_err());
+        assert!(ValueUtils::parse_scalar("256", VariantTypeId::Byte).is_err());
+        assert!(ValueUtils::parse_scalar("-1", VariantTypeId::UInt32).is_err());
+        assert!(ValueUtils::parse_scalar("yesterday", VariantTypeId::DateTime).is_err());
+        assert!(ValueUtils::parse_scalar("not-a-guid", VariantTypeId::Guid).is_err());
+        assert!(ValueUtils::parse_scalar("Pump", VariantTypeId::NodeId).is_err());
+        assert!(ValueUtils::parse_scalar("BadNoSuchCode", VariantTypeId::StatusCode).is_err());
+    }
+
+    #[test]
+    fn arrays_parse_with_or_without_brackets() {
+        assert_eq!(
+            array_values(ValueUtils::parse_variant("[1, 2, 3]", VariantTypeId::UInt16, 1).unwrap()),
+            vec![Variant::UInt16(1), Variant::UInt16(2), Variant::UInt16(3)]
+        );
+        assert_eq!(
+            array_values(ValueUtils::parse_variant("1,2", VariantTypeId::Double, 0).unwrap()),
+            vec![Variant::Double(1.0), Variant::Double(2.0)]
+        );
+        assert!(
+            array_values(ValueUtils::parse_variant("[]", VariantTypeId::Int32, 1).unwrap())
+                .is_empty()
+        );
+
+        let error = ValueUtils::parse_variant("[1, x]", VariantTypeId::Int32, 1).unwrap_err();
+        assert!(error.to_string().starts_with("Element [1]"), "{error}");
+    }
+
+    #[test]
+    fn quoted_array_elements_keep_their_commas() {
+        let values =
+            ValueUtils::parse_variant(r#"["a, b", 'say "hi"', plain]"#, VariantTypeId::String, 1)
+                .unwrap();
+        assert_eq!(
+            array_values(values.clone()),
+            vec![
+                Variant::from("a, b"),
+                Variant::from(r#"say "hi""#),
+                Variant::from("plain")
+            ]
+        );
+        // The edit text parses back to the same value
+        let edit = ValueUtils::format_for_edit(&values);
+        assert_eq!(
+            ValueUtils::parse_variant(&edit, VariantTypeId::String, 1).unwrap(),
+            values
+        );
+    }
+
+    #[test]
+    fn value_rank_decides_between_scalar_and_array() {
+        assert_eq!(
+            ValueUtils::parse_variant("[1]", VariantTypeId::String, -1).unwrap(),
+            Variant::from("[1]")
+        );
+        assert_eq!(
+            ValueUtils::parse_variant("1", VariantTypeId::Int32, -2).unwrap(),
+            Variant::Int32(1)
+        );
+        assert_eq!(
+            array_values(ValueUtils::parse_variant("[1]", VariantTypeId::Int32, -2).unwrap()),
+            vec![Variant::Int32(1)]
+        );
+        assert!(ValueUtils::parse_variant("[1, 2]", VariantTypeId::Int32, 2).is_err());
+        assert!(ValueUtils::infer_variant("[1, 2]", 2).is_err());
+    }
+}